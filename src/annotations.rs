@@ -0,0 +1,100 @@
+//! GitHub Actions workflow-command annotations for Verus output.
+//!
+//! Turns the structured [`CompilationError`]/[`VerificationFailure`] values the
+//! parsers in [`crate::verification`] already produce into `::error`/`::warning`
+//! workflow commands, so failures show up inline on the PR diff instead of only
+//! in the raw log.
+//! See <https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions>.
+
+use crate::verification::{CompilationError, VerificationFailure};
+
+/// Emit one workflow command per compilation error, compilation warning, and
+/// verification failure. Items with a file/line get `file=...,line=...,col=...`;
+/// everything else falls back to a bare `::error::`/`::warning::`.
+pub fn emit_github_annotations(
+    errors: &[CompilationError],
+    warnings: &[CompilationError],
+    failures: &[VerificationFailure],
+) {
+    for error in errors {
+        emit_compilation_annotation("error", error);
+    }
+    for warning in warnings {
+        emit_compilation_annotation("warning", warning);
+    }
+    for failure in failures {
+        emit_failure_annotation(failure);
+    }
+}
+
+fn emit_compilation_annotation(level: &str, error: &CompilationError) {
+    let message = escape_data(&error.full_message.join("\n"));
+    emit_command(level, error.file.as_deref(), error.line, error.column, &message);
+}
+
+fn emit_failure_annotation(failure: &VerificationFailure) {
+    let mut full_message = failure.message.clone();
+    if !failure.full_error_text.is_empty() {
+        full_message.push('\n');
+        full_message.push_str(&failure.full_error_text);
+    }
+    if !failure.assertion_details.is_empty() {
+        full_message.push('\n');
+        full_message.push_str(&failure.assertion_details.join("\n"));
+    }
+
+    let message = escape_data(&full_message);
+    emit_command(
+        "error",
+        failure.file.as_deref(),
+        failure.line,
+        failure.column,
+        &message,
+    );
+}
+
+fn emit_command(level: &str, file: Option<&str>, line: Option<i32>, column: Option<i32>, message: &str) {
+    match (file, line) {
+        (Some(file), Some(line)) => {
+            let col = column.unwrap_or(1);
+            println!(
+                "::{} file={},line={},col={}::{}",
+                level,
+                escape_property(file),
+                line,
+                col,
+                message
+            );
+        }
+        _ => println!("::{}::{}", level, message),
+    }
+}
+
+/// Escape a workflow-command *data* value (the part after `::`).
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow-command *property* value (e.g. `file=...`), which also
+/// can't contain a bare `:` or `,` without being mistaken for the next property.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_percent_and_newlines_in_data() {
+        assert_eq!(escape_data("100% done\r\nnext line"), "100%25 done%0D%0Anext line");
+    }
+
+    #[test]
+    fn escapes_colon_and_comma_in_property() {
+        assert_eq!(escape_property("src/a,b.rs:1"), "src/a%2Cb.rs%3A1");
+    }
+}
@@ -0,0 +1,214 @@
+//! Archived, mmap-able on-disk cache for the atom table ([`AtomWithLines`]),
+//! so a warm `atoms` run can skip both SCIP-JSON parsing and span
+//! extraction entirely when nothing has changed since the last run.
+//!
+//! Behind the `rkyv-impl` feature, the atom table is serialized with
+//! `rkyv` into a buffer whose layout mirrors the in-memory structs, so
+//! reading it back on a warm run is `bytecheck` pointer validation against
+//! an `mmap`, not parsing. [`read`] then deserializes the validated archive
+//! into an owned `Vec<AtomWithLines>`, so it does allocate the full atom
+//! table; callers get owned values, not references into the mmap. Without
+//! the feature, [`read`] and [`write`] are no-ops (an unconditional cache
+//! miss), so call sites don't need to `cfg`-gate themselves.
+
+use crate::AtomWithLines;
+use std::path::{Path, PathBuf};
+
+/// Bumped whenever [`AtomWithLines`]'s archived layout changes
+/// incompatibly, so a cache written by an older binary is rejected rather
+/// than misinterpreted.
+const SCHEMA_VERSION: u32 = 1;
+
+/// Cache file, relative to the project's `data/` directory.
+const CACHE_FILE_NAME: &str = "atoms.rkyv";
+
+/// Path the archived atom cache is read from/written to for `project_root`.
+pub fn cache_path(project_root: &Path) -> PathBuf {
+    project_root.join("data").join(CACHE_FILE_NAME)
+}
+
+/// Fixed-size header written before the archived payload: a schema version
+/// and a hash of the SCIP JSON the atoms were derived from, so a cache
+/// whose input has since changed -- or that predates this binary's
+/// archived layout -- is rejected rather than returning stale atoms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CacheHeader {
+    schema_version: u32,
+    source_hash: u64,
+}
+
+impl CacheHeader {
+    const ENCODED_LEN: usize = 4 + 8;
+
+    fn encode(self) -> [u8; Self::ENCODED_LEN] {
+        let mut buf = [0u8; Self::ENCODED_LEN];
+        buf[0..4].copy_from_slice(&self.schema_version.to_le_bytes());
+        buf[4..12].copy_from_slice(&self.source_hash.to_le_bytes());
+        buf
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let schema_version = u32::from_le_bytes(bytes.get(0..4)?.try_into().ok()?);
+        let source_hash = u64::from_le_bytes(bytes.get(4..12)?.try_into().ok()?);
+        Some(CacheHeader {
+            schema_version,
+            source_hash,
+        })
+    }
+
+    fn for_source(source_bytes: &[u8]) -> Self {
+        CacheHeader {
+            schema_version: SCHEMA_VERSION,
+            source_hash: hash_source(source_bytes),
+        }
+    }
+}
+
+/// Hash the bytes a cache entry is derived from (the SCIP JSON that fed
+/// atom extraction), so regenerating that input invalidates the cache
+/// without requiring an explicit `--regenerate-scip`.
+fn hash_source(source_bytes: &[u8]) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    source_bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(feature = "rkyv-impl")]
+mod rkyv_impl {
+    use super::*;
+    use memmap2::Mmap;
+    use std::fs::File;
+    use std::io::Write;
+
+    /// Serialize `atoms` and write them to [`cache_path`], prefixed by a
+    /// [`CacheHeader`] keyed on `source_bytes` (the SCIP JSON the atoms
+    /// were extracted from).
+    pub fn write(
+        project_root: &Path,
+        source_bytes: &[u8],
+        atoms: &[AtomWithLines],
+    ) -> std::io::Result<()> {
+        let archived = rkyv::to_bytes::<_, 4096>(atoms)
+            .map_err(|e| std::io::Error::other(format!("failed to archive atoms: {e}")))?;
+
+        let path = cache_path(project_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = File::create(&path)?;
+        file.write_all(&CacheHeader::for_source(source_bytes).encode())?;
+        file.write_all(&archived)?;
+        Ok(())
+    }
+
+    /// Validate and `mmap` [`cache_path`], returning the decoded atom
+    /// table only if the header's schema version and source hash match
+    /// `source_bytes`. A missing file, header mismatch, or validation
+    /// failure is a cache miss, not an error -- the caller falls back to
+    /// recomputing from `source_bytes`.
+    pub fn read(project_root: &Path, source_bytes: &[u8]) -> Option<Vec<AtomWithLines>> {
+        let path = cache_path(project_root);
+        let file = File::open(path).ok()?;
+        // Safety: the cache file is only written by `write` above (via
+        // `File::create` + sequential writes, not an atomic rename) and this
+        // process never mutates it concurrently with a read.
+        let mmap = unsafe { Mmap::map(&file).ok()? };
+
+        let header = CacheHeader::decode(&mmap)?;
+        if header != CacheHeader::for_source(source_bytes) {
+            return None;
+        }
+
+        let payload = &mmap[CacheHeader::ENCODED_LEN..];
+        let archived = rkyv::check_archived_root::<Vec<AtomWithLines>>(payload).ok()?;
+        rkyv::Deserialize::deserialize(archived, &mut rkyv::Infallible).ok()
+    }
+}
+
+#[cfg(feature = "rkyv-impl")]
+pub use rkyv_impl::{read, write};
+
+/// No-op fallback when built without `rkyv-impl`: every write is silently
+/// skipped and every read is an unconditional cache miss.
+#[cfg(not(feature = "rkyv-impl"))]
+pub fn write(
+    _project_root: &Path,
+    _source_bytes: &[u8],
+    _atoms: &[AtomWithLines],
+) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(not(feature = "rkyv-impl"))]
+pub fn read(_project_root: &Path, _source_bytes: &[u8]) -> Option<Vec<AtomWithLines>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_path_is_under_the_project_data_directory() {
+        let root = Path::new("/tmp/some-project");
+        assert_eq!(
+            cache_path(root),
+            Path::new("/tmp/some-project/data/atoms.rkyv")
+        );
+    }
+
+    #[test]
+    fn header_round_trips_through_encode_decode() {
+        let header = CacheHeader::for_source(b"hello world");
+        let decoded = CacheHeader::decode(&header.encode()).unwrap();
+        assert_eq!(header, decoded);
+    }
+
+    #[test]
+    fn header_differs_for_different_source_bytes() {
+        let a = CacheHeader::for_source(b"one");
+        let b = CacheHeader::for_source(b"two");
+        assert_ne!(a, b);
+    }
+
+    #[cfg(not(feature = "rkyv-impl"))]
+    #[test]
+    fn read_is_an_unconditional_cache_miss_without_the_feature() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read(dir.path(), b"anything").is_none());
+    }
+
+    #[cfg(feature = "rkyv-impl")]
+    #[test]
+    fn write_then_read_round_trips_the_atom_table() {
+        use crate::CodeTextInfo;
+
+        let dir = tempfile::tempdir().unwrap();
+        let source = b"scip json contents";
+        let atoms = vec![AtomWithLines {
+            display_name: "foo".to_string(),
+            scip_name: "foo#".to_string(),
+            dependencies: Default::default(),
+            ambiguous_dependencies: Default::default(),
+            code_path: "src/lib.rs".to_string(),
+            code_text: CodeTextInfo {
+                lines_start: 1,
+                lines_end: 3,
+            },
+        }];
+
+        write(dir.path(), source, &atoms).unwrap();
+        let cached = read(dir.path(), source).unwrap();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].display_name, "foo");
+    }
+
+    #[cfg(feature = "rkyv-impl")]
+    #[test]
+    fn read_misses_when_the_source_bytes_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), b"old source", &[]).unwrap();
+        assert!(read(dir.path(), b"new source").is_none());
+    }
+}
@@ -0,0 +1,132 @@
+//! A deterministic binary encoding for [`AtomWithLines`] output, alongside
+//! the default JSON path produced by `serde_json::to_string_pretty`.
+//!
+//! The rules are deliberately simple (Binary Canonical Serialization
+//! style), so the same input always produces the exact same bytes:
+//!
+//! - Fields are written in [`AtomWithLines`]'s declared order -- no
+//!   serializer-dependent field reordering.
+//! - Every string and sequence is length-prefixed with a big-endian `u32`
+//!   count, then its bytes/elements; there's no delimiter to disagree on.
+//! - `dependencies` and `ambiguous_dependencies` are `HashSet`s, whose
+//!   iteration order isn't stable across runs or processes, so both are
+//!   sorted before encoding -- a set's *content* determines its bytes,
+//!   never its hash-table layout.
+//! - `AtomWithLines` has no floating-point fields, so there's no
+//!   float-reordering concern; `lines_start`/`lines_end` are written as
+//!   big-endian `u64`.
+//!
+//! Two processes encoding the same atom table, in any order, on any
+//! machine, produce byte-identical output -- so callers can hash it to
+//! detect real changes and key caches on the digest.
+//!
+//! That guarantee depends on the *top-level* atom order being fixed too,
+//! not just the `HashSet` fields within each atom: callers such as
+//! `run_atoms` build `atoms` from `call_graph.values()`, a `HashMap`
+//! whose iteration order is randomized per process, so [`encode_atoms`]
+//! sorts by `(scip_name, code_path, lines_start)` before encoding.
+
+use crate::{AtomWithLines, CodeTextInfo};
+use std::collections::HashSet;
+
+/// Encode `atoms` as canonical bytes. Same logical content always yields
+/// the same bytes, regardless of input ordering from e.g. a `HashSet` or
+/// a `HashMap`-derived slice.
+pub fn encode_atoms(atoms: &[AtomWithLines]) -> Vec<u8> {
+    let mut sorted: Vec<&AtomWithLines> = atoms.iter().collect();
+    sorted.sort_unstable_by(|a, b| {
+        (&a.scip_name, &a.code_path, a.code_text.lines_start).cmp(&(
+            &b.scip_name,
+            &b.code_path,
+            b.code_text.lines_start,
+        ))
+    });
+
+    let mut buf = Vec::new();
+    encode_u32(&mut buf, sorted.len() as u32);
+    for atom in sorted {
+        encode_atom(&mut buf, atom);
+    }
+    buf
+}
+
+fn encode_atom(buf: &mut Vec<u8>, atom: &AtomWithLines) {
+    encode_str(buf, &atom.display_name);
+    encode_str(buf, &atom.scip_name);
+    encode_str_set(buf, &atom.dependencies);
+    encode_str_set(buf, &atom.ambiguous_dependencies);
+    encode_str(buf, &atom.code_path);
+    encode_code_text(buf, &atom.code_text);
+}
+
+fn encode_code_text(buf: &mut Vec<u8>, code_text: &CodeTextInfo) {
+    encode_u64(buf, code_text.lines_start as u64);
+    encode_u64(buf, code_text.lines_end as u64);
+}
+
+fn encode_str_set(buf: &mut Vec<u8>, set: &HashSet<String>) {
+    let mut sorted: Vec<&str> = set.iter().map(String::as_str).collect();
+    sorted.sort_unstable();
+    encode_u32(buf, sorted.len() as u32);
+    for s in sorted {
+        encode_str(buf, s);
+    }
+}
+
+fn encode_str(buf: &mut Vec<u8>, s: &str) {
+    encode_u32(buf, s.len() as u32);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn encode_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn encode_u64(buf: &mut Vec<u8>, value: u64) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn atom(scip_name: &str, deps: &[&str]) -> AtomWithLines {
+        AtomWithLines {
+            display_name: "foo".to_string(),
+            scip_name: scip_name.to_string(),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ambiguous_dependencies: HashSet::new(),
+            code_path: "src/lib.rs".to_string(),
+            code_text: CodeTextInfo {
+                lines_start: 1,
+                lines_end: 3,
+            },
+        }
+    }
+
+    #[test]
+    fn encoding_is_deterministic_regardless_of_hashset_insertion_order() {
+        let a = atom("foo#", &["bar#", "baz#", "qux#"]);
+        let b = atom("foo#", &["qux#", "bar#", "baz#"]);
+        assert_eq!(encode_atoms(&[a]), encode_atoms(&[b]));
+    }
+
+    #[test]
+    fn different_content_encodes_to_different_bytes() {
+        let a = atom("foo#", &["bar#"]);
+        let b = atom("foo#", &["baz#"]);
+        assert_ne!(encode_atoms(&[a]), encode_atoms(&[b]));
+    }
+
+    #[test]
+    fn empty_atom_list_encodes_to_a_single_zero_count() {
+        assert_eq!(encode_atoms(&[]), 0u32.to_be_bytes().to_vec());
+    }
+
+    #[test]
+    fn encoding_is_deterministic_regardless_of_top_level_atom_order() {
+        let forward = [atom("a#", &[]), atom("b#", &[]), atom("c#", &[])];
+        let shuffled = [atom("c#", &[]), atom("a#", &[]), atom("b#", &[])];
+        assert_eq!(encode_atoms(&forward), encode_atoms(&shuffled));
+    }
+}
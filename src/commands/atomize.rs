@@ -1,12 +1,29 @@
 //! Atomize command - Generate call graph atoms from SCIP indexes.
 
+use super::manifest;
 use probe_verus::{
-    build_call_graph, convert_to_atoms_with_parsed_spans, find_duplicate_code_names,
-    parse_scip_json, scip_cache::ScipCache, AtomWithLines,
+    build_call_graph, constants::SCIP_INDEX_FILE, convert_to_atoms_with_parsed_spans,
+    find_duplicate_code_names, parse_scip_json, parse_scip_protobuf,
+    probe_config::ProbeConfig, scip_cache::ScipCache, scip_symbol, AtomWithLines, ScipIndex,
 };
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+/// Output format for the atomize command.
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum AtomizeFormat {
+    /// Pretty-printed JSON, the default -- human-readable and diffable.
+    Json,
+    /// A zero-copy `rkyv` archive of the `code_name`-keyed atom map.
+    ///
+    /// Downstream commands that only need to read the atoms back
+    /// (`verify`, `specs_data`) can `mmap` the file and reach an
+    /// `&ArchivedHashMap` via `rkyv::access`/`archived_root` under the
+    /// `validation` feature, skipping a full deserialize pass -- the same
+    /// trade-off [`probe_verus::atom_cache`] makes for the warm-run cache.
+    Rkyv,
+}
+
 /// Execute the atomize command.
 ///
 /// Generates call graph atoms with line numbers from SCIP indexes.
@@ -15,6 +32,7 @@ pub fn cmd_atomize(
     output: PathBuf,
     regenerate_scip: bool,
     with_locations: bool,
+    format: AtomizeFormat,
 ) {
     println!("═══════════════════════════════════════════════════════════");
     println!("  Probe Verus - Atomize: Generate Call Graph Data");
@@ -28,17 +46,30 @@ pub fn cmd_atomize(
     }
     println!("  ✓ Valid Rust project found");
 
+    // Load any project-specific matching tolerances and kind tables before
+    // the SCIP index is parsed, so every tunable lookup below sees them.
+    ProbeConfig::load_from_project(&project_path).set_global();
+
+    if !regenerate_scip && output.exists() && manifest::is_atoms_fresh(&project_path) {
+        println!(
+            "  ✓ {} is already fresh (manifest.json inputs unchanged)",
+            output.display()
+        );
+        println!("    (use --regenerate-scip to force regeneration)");
+        return;
+    }
+
     // Get or generate SCIP JSON
     let scip_cache = ScipCache::new(&project_path);
     let json_path = get_scip_json(&scip_cache, regenerate_scip);
 
-    // Parse SCIP JSON and build call graph
-    println!("Parsing SCIP JSON and building call graph...");
+    // Parse the SCIP index and build call graph
+    println!("Parsing SCIP index and building call graph...");
 
-    let scip_index = match parse_scip_json(json_path.to_str().unwrap()) {
+    let scip_index = match load_scip_index(&json_path) {
         Ok(idx) => idx,
         Err(e) => {
-            eprintln!("✗ Failed to parse SCIP JSON: {}", e);
+            eprintln!("✗ Failed to parse SCIP index: {}", e);
             std::process::exit(1);
         }
     };
@@ -51,7 +82,7 @@ pub fn cmd_atomize(
     println!("Converting to atoms format with accurate line numbers...");
     println!("  Parsing source files with verus_syn for accurate function spans...");
 
-    let atoms = convert_to_atoms_with_parsed_spans(
+    let mut atoms = convert_to_atoms_with_parsed_spans(
         &call_graph,
         &symbol_to_display_name,
         &project_path,
@@ -62,6 +93,16 @@ pub fn cmd_atomize(
         println!("    (including dependencies-with-locations)");
     }
 
+    // Disambiguate code_names that collide across trait impls before
+    // treating any remaining collision as fatal.
+    let resolved = resolve_duplicate_code_names(&mut atoms);
+    if !resolved.is_empty() {
+        println!("  ✓ Disambiguated {} colliding code_name(s):", resolved.len());
+        for (original, qualified) in &resolved {
+            println!("    - '{}' -> '{}'", original, qualified);
+        }
+    }
+
     // Check for duplicate code_names - these are a fatal error
     if let Err(msg) = check_duplicates(&atoms) {
         eprintln!();
@@ -76,8 +117,23 @@ pub fn cmd_atomize(
         .collect();
 
     // Write the output
-    let json = serde_json::to_string_pretty(&atoms_dict).expect("Failed to serialize JSON");
-    std::fs::write(&output, &json).expect("Failed to write output file");
+    write_atoms(&output, &atoms_dict, format).expect("Failed to write output file");
+
+    // Record what produced this output, so the next run can tell whether
+    // it's still fresh without regenerating anything.
+    let indexer_version = format!(
+        "{} {}",
+        scip_index.metadata.tool_info.name, scip_index.metadata.tool_info.version
+    );
+    if let Err(e) = manifest::write_manifest(
+        &project_path,
+        &[json_path.clone()],
+        &indexer_version,
+        atoms_dict.len(),
+        &output.display().to_string(),
+    ) {
+        eprintln!("  ⚠ Failed to write manifest.json: {}", e);
+    }
 
     // Print success summary
     print_success_summary(&output, &atoms_dict);
@@ -132,6 +188,95 @@ fn get_scip_json(cache: &ScipCache, regenerate: bool) -> PathBuf {
     }
 }
 
+/// Load the SCIP index, preferring the native `index.scip` protobuf over
+/// the JSON round-trip when both are present -- same data, one less parse
+/// and one less serialization step in between.
+fn load_scip_index(json_path: &Path) -> Result<ScipIndex, String> {
+    let protobuf_path = json_path.with_file_name(SCIP_INDEX_FILE);
+    if protobuf_path.exists() {
+        match parse_scip_protobuf(&protobuf_path) {
+            Ok(index) => return Ok(index),
+            Err(e) => eprintln!(
+                "  ⚠ Failed to parse native SCIP protobuf ({}), falling back to JSON",
+                e
+            ),
+        }
+    }
+    parse_scip_json(json_path.to_str().unwrap()).map_err(|e| e.to_string())
+}
+
+/// Write the `code_name`-keyed atom map in the requested [`AtomizeFormat`].
+fn write_atoms(
+    output: &Path,
+    atoms_dict: &HashMap<String, AtomWithLines>,
+    format: AtomizeFormat,
+) -> std::io::Result<()> {
+    match format {
+        AtomizeFormat::Json => {
+            let json = serde_json::to_string_pretty(atoms_dict)
+                .map_err(|e| std::io::Error::other(format!("failed to serialize atoms: {e}")))?;
+            std::fs::write(output, json)
+        }
+        AtomizeFormat::Rkyv => {
+            #[cfg(feature = "rkyv-impl")]
+            {
+                let archived = rkyv::to_bytes::<_, 4096>(atoms_dict).map_err(|e| {
+                    std::io::Error::other(format!("failed to archive atoms: {e}"))
+                })?;
+                std::fs::write(output, archived)
+            }
+            #[cfg(not(feature = "rkyv-impl"))]
+            {
+                Err(std::io::Error::other(
+                    "rkyv output requires building with --features rkyv-impl",
+                ))
+            }
+        }
+    }
+}
+
+/// Disambiguate colliding `code_name`s from trait impls the indexer can't
+/// otherwise tell apart (e.g. `impl From<T> for Container<X>` vs
+/// `Container<Y>`), by appending a `for <Type>` qualifier parsed from the
+/// SCIP symbol's descriptor suffix -- the enclosing `impl` type is encoded
+/// there as a `Type` descriptor, the same structured data
+/// the library's `symbol_to_scip_name_full` decodes for the analogous
+/// `scip_name` collisions.
+///
+/// Falls back to a `@<path>:<line>` qualifier when a symbol doesn't parse
+/// or carries no enclosing type, so every atom still ends up with a
+/// unique key even when the indexer gives us nothing to disambiguate
+/// with. Returns the `(original, qualified)` pairs that were renamed, in
+/// no particular order, so the caller can report how each impl was
+/// distinguished.
+fn resolve_duplicate_code_names(atoms: &mut [AtomWithLines]) -> Vec<(String, String)> {
+    let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, atom) in atoms.iter().enumerate() {
+        groups.entry(atom.code_name.clone()).or_default().push(i);
+    }
+
+    let mut resolved = Vec::new();
+    for (code_name, indices) in groups {
+        if indices.len() < 2 {
+            continue;
+        }
+        for i in indices {
+            let qualifier = scip_symbol::parse_symbol(&atoms[i].scip_name)
+                .and_then(|parsed| parsed.type_name().map(|t| format!(" for {t}")))
+                .unwrap_or_else(|| {
+                    format!(
+                        " @{}:{}",
+                        atoms[i].code_path, atoms[i].code_text.lines_start
+                    )
+                });
+            let qualified = format!("{code_name}{qualifier}");
+            atoms[i].code_name = qualified.clone();
+            resolved.push((code_name.clone(), qualified));
+        }
+    }
+    resolved
+}
+
 /// Check for duplicate code_names and return an error message if found.
 fn check_duplicates(atoms: &[AtomWithLines]) -> Result<(), String> {
     let duplicates = find_duplicate_code_names(atoms);
@@ -188,7 +333,21 @@ pub fn atomize_internal(
     output: &PathBuf,
     regenerate_scip: bool,
     verbose: bool,
+    format: AtomizeFormat,
 ) -> Result<usize, String> {
+    ProbeConfig::load_from_project(project_path).set_global();
+
+    if matches!(format, AtomizeFormat::Json)
+        && !regenerate_scip
+        && output.exists()
+        && manifest::is_atoms_fresh(project_path)
+    {
+        let contents = std::fs::read_to_string(output).map_err(|e| e.to_string())?;
+        let atoms_dict: HashMap<String, AtomWithLines> =
+            serde_json::from_str(&contents).map_err(|e| e.to_string())?;
+        return Ok(atoms_dict.len());
+    }
+
     let cache = ScipCache::new(project_path);
 
     // Get or generate SCIP JSON
@@ -197,20 +356,21 @@ pub fn atomize_internal(
         .map_err(|e| e.to_string())?;
 
     // Parse and build call graph
-    let scip_index = parse_scip_json(json_path.to_str().unwrap())
-        .map_err(|e| format!("Failed to parse SCIP JSON: {}", e))?;
+    let scip_index = load_scip_index(&json_path).map_err(|e| format!("Failed to parse SCIP index: {}", e))?;
 
     let (call_graph, symbol_to_display_name) = build_call_graph(&scip_index);
 
     // For `run` command, default to basic output (no locations)
-    let atoms = convert_to_atoms_with_parsed_spans(
+    let mut atoms = convert_to_atoms_with_parsed_spans(
         &call_graph,
         &symbol_to_display_name,
         project_path,
         false,
     );
 
-    // Check for duplicates
+    // Disambiguate code_names that collide across trait impls, then check
+    // for duplicates that still remain.
+    resolve_duplicate_code_names(&mut atoms);
     let duplicates = find_duplicate_code_names(&atoms);
     if !duplicates.is_empty() {
         return Err(format!("Found {} duplicate code_name(s)", duplicates.len()));
@@ -224,9 +384,21 @@ pub fn atomize_internal(
         .map(|atom| (atom.code_name.clone(), atom))
         .collect();
 
-    let json = serde_json::to_string_pretty(&atoms_dict)
-        .map_err(|e| format!("Failed to serialize JSON: {}", e))?;
-    std::fs::write(output, &json).map_err(|e| format!("Failed to write output: {}", e))?;
+    write_atoms(output, &atoms_dict, format).map_err(|e| format!("Failed to write output: {}", e))?;
+
+    let indexer_version = format!(
+        "{} {}",
+        scip_index.metadata.tool_info.name, scip_index.metadata.tool_info.version
+    );
+    if let Err(e) = manifest::write_manifest(
+        project_path,
+        &[json_path.clone()],
+        &indexer_version,
+        count,
+        &output.display().to_string(),
+    ) {
+        eprintln!("  ⚠ Failed to write manifest.json: {}", e);
+    }
 
     Ok(count)
 }
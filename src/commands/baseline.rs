@@ -0,0 +1,173 @@
+//! Regression-gating baseline comparison for CI.
+//!
+//! A freshly produced `proofs.json` almost always carries some pre-existing
+//! failures in a codebase still adopting Verus incrementally. Comparing against
+//! a `--baseline <path>` snapshot lets CI fail only on *regressions* -- a
+//! function that verified in the baseline but fails or is unverified now -- so
+//! teams don't need a green-everything bar before they can turn on gating.
+
+use probe_verus::verification::{AnalysisResult, FunctionLocation};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A function's verification state, coarse enough to diff across runs.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionState {
+    Verified,
+    Failed,
+    Unverified,
+}
+
+/// A function whose state changed between the baseline and the fresh run.
+#[derive(Serialize)]
+pub struct StateChange {
+    pub symbol: String,
+    pub from: FunctionState,
+    pub to: FunctionState,
+}
+
+/// Result of comparing a fresh run against a baseline.
+#[derive(Serialize, Default)]
+pub struct BaselineComparison {
+    /// Functions that verified in the baseline but fail or are unverified now.
+    pub regressions: Vec<StateChange>,
+    /// Functions that didn't verify in the baseline but do now.
+    pub newly_fixed: Vec<StateChange>,
+    /// Functions present in the fresh run but absent from the baseline.
+    pub newly_appeared: Vec<String>,
+    /// Functions present in the baseline but absent from the fresh run.
+    pub removed: Vec<String>,
+}
+
+impl BaselineComparison {
+    pub fn has_regressions(&self) -> bool {
+        !self.regressions.is_empty()
+    }
+}
+
+/// Load a previously committed `proofs.json` baseline.
+pub fn load_baseline(path: &Path) -> Result<AnalysisResult, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read baseline {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse baseline {}: {}", path.display(), e))
+}
+
+/// Normalize the one volatile field `FunctionLocation` carries -- its source
+/// path -- by rewriting it relative to `project_path`, then pair it with the
+/// display name and start line to stand in for the function's fully
+/// qualified SCIP symbol.
+///
+/// `FunctionLocation` has no `scip_name` of its own (that's only computed
+/// downstream in the atomize pipeline), so `display_name` plus relative
+/// path alone isn't enough: several `impl Mul<...> for RistrettoPoint`-style
+/// blocks in one file can all define a same-named method, and without a
+/// distinguishing line number they'd collide onto one key here and silently
+/// overwrite each other -- dropping a real regression on whichever one
+/// lands last. Appending `code_text.lines_start` disambiguates those. This
+/// is a line-number key, not the signature-hash `@impl=` suffix the atoms
+/// pipeline gives colliding `scip_name`s, so it changes when lines above
+/// the function are added or removed.
+fn normalize_symbol(loc: &FunctionLocation, project_path: &Path) -> String {
+    let path = Path::new(&loc.code_path);
+    let relative = path
+        .strip_prefix(project_path)
+        .unwrap_or(path)
+        .display()
+        .to_string();
+    format!("{}@{}:{}", relative, loc.display_name, loc.code_text.lines_start)
+}
+
+fn index_locations<'a>(
+    groups: impl IntoIterator<Item = (&'a [FunctionLocation], FunctionState)>,
+    project_path: &Path,
+) -> HashMap<String, FunctionState> {
+    let mut index = HashMap::new();
+    for (locations, state) in groups {
+        for loc in locations {
+            index.insert(normalize_symbol(loc, project_path), state);
+        }
+    }
+    index
+}
+
+/// Compare a baseline `AnalysisResult` against a freshly produced set of
+/// verified/failed/unverified locations, keyed by normalized symbol.
+pub fn compare(
+    baseline: &AnalysisResult,
+    fresh_verified: &[FunctionLocation],
+    fresh_failed: &[FunctionLocation],
+    fresh_unverified: &[FunctionLocation],
+    project_path: &Path,
+) -> BaselineComparison {
+    let baseline_index = index_locations(
+        [
+            (
+                baseline.verification.verified_functions.as_slice(),
+                FunctionState::Verified,
+            ),
+            (
+                baseline.verification.failed_functions.as_slice(),
+                FunctionState::Failed,
+            ),
+            (
+                baseline.verification.unverified_functions.as_slice(),
+                FunctionState::Unverified,
+            ),
+        ],
+        project_path,
+    );
+    let fresh_index = index_locations(
+        [
+            (fresh_verified, FunctionState::Verified),
+            (fresh_failed, FunctionState::Failed),
+            (fresh_unverified, FunctionState::Unverified),
+        ],
+        project_path,
+    );
+
+    let mut comparison = BaselineComparison::default();
+
+    for (symbol, &before) in &baseline_index {
+        let Some(&after) = fresh_index.get(symbol) else {
+            continue;
+        };
+        if before == FunctionState::Verified && after != FunctionState::Verified {
+            comparison.regressions.push(StateChange {
+                symbol: symbol.clone(),
+                from: before,
+                to: after,
+            });
+        } else if before != FunctionState::Verified && after == FunctionState::Verified {
+            comparison.newly_fixed.push(StateChange {
+                symbol: symbol.clone(),
+                from: before,
+                to: after,
+            });
+        }
+    }
+
+    comparison
+        .regressions
+        .sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    comparison
+        .newly_fixed
+        .sort_by(|a, b| a.symbol.cmp(&b.symbol));
+
+    comparison.newly_appeared = fresh_index
+        .keys()
+        .filter(|symbol| !baseline_index.contains_key(*symbol))
+        .cloned()
+        .collect();
+    comparison.removed = baseline_index
+        .keys()
+        .filter(|symbol| !fresh_index.contains_key(*symbol))
+        .cloned()
+        .collect();
+    comparison.newly_appeared.sort();
+    comparison.removed.sort();
+
+    comparison
+}
@@ -0,0 +1,144 @@
+//! Content-addressed incremental verification cache.
+//!
+//! Re-running `cmd_run` normally re-verifies every function even when nothing
+//! changed. This cache keys each function by the hash of its own source text
+//! plus the hashes of all its transitive dependencies (from the atomize call
+//! graph), so a change to a callee invalidates its callers the same way
+//! dep-info-driven incremental builds do. Entries live under
+//! `output_dir/.probe-cache/<key>.json`, one small JSON file per function.
+
+use probe_verus::AtomWithLines;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// The cached outcome of a previous verification run for one function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CachedStatus {
+    Verified,
+    Failed,
+    Unverified,
+}
+
+/// A content-addressed store of prior verification outcomes.
+pub struct VerificationCache {
+    dir: PathBuf,
+    enabled: bool,
+}
+
+impl VerificationCache {
+    /// Open (creating if needed) the cache under `output_dir/.probe-cache`.
+    /// Pass `enabled = false` for `--no-cache`, which makes every lookup miss
+    /// and every store a no-op without disturbing files already on disk.
+    pub fn new(output_dir: &Path, enabled: bool) -> Self {
+        let dir = output_dir.join(".probe-cache");
+        if enabled {
+            let _ = std::fs::create_dir_all(&dir);
+        }
+        Self { dir, enabled }
+    }
+
+    /// Wipe the cache directory entirely, for `--clean`.
+    pub fn clean(output_dir: &Path) {
+        let _ = std::fs::remove_dir_all(output_dir.join(".probe-cache"));
+    }
+
+    /// Compute the cache key for `name`: the hash of its own source text,
+    /// combined with the (sorted, so call-site order doesn't matter) hashes of
+    /// every transitive dependency.
+    pub fn key_for(
+        &self,
+        name: &str,
+        atoms: &HashMap<String, AtomWithLines>,
+        project_path: &Path,
+    ) -> u64 {
+        let mut memo = HashMap::new();
+        let mut in_progress = HashSet::new();
+        transitive_hash(name, atoms, project_path, &mut memo, &mut in_progress)
+    }
+
+    pub fn lookup(&self, key: u64) -> Option<CachedStatus> {
+        if !self.enabled {
+            return None;
+        }
+        let content = std::fs::read_to_string(self.entry_path(key)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn store(&self, key: u64, status: CachedStatus) {
+        if !self.enabled {
+            return;
+        }
+        if let Ok(json) = serde_json::to_string(&status) {
+            let _ = std::fs::write(self.entry_path(key), json);
+        }
+    }
+
+    fn entry_path(&self, key: u64) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", key))
+    }
+}
+
+/// Hash the source text of a single function, read from its recorded line
+/// range in `atoms.json`.
+fn source_hash(atom: &AtomWithLines, project_path: &Path) -> u64 {
+    let full_path = project_path.join(&atom.code_path);
+    let text = std::fs::read_to_string(&full_path).unwrap_or_default();
+    let lines: Vec<&str> = text.lines().collect();
+    let start = atom.code_text.lines_start.saturating_sub(1);
+    let end = atom.code_text.lines_end.min(lines.len());
+
+    let mut hasher = DefaultHasher::new();
+    if start < end {
+        lines[start..end].join("\n").hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Hash `name` combined with its transitive dependency closure, memoizing as
+/// it goes. Mutually recursive functions form a cycle in the call graph; it's
+/// broken by having a node re-entered while still in progress contribute only
+/// its own source hash rather than recursing forever.
+fn transitive_hash(
+    name: &str,
+    atoms: &HashMap<String, AtomWithLines>,
+    project_path: &Path,
+    memo: &mut HashMap<String, u64>,
+    in_progress: &mut HashSet<String>,
+) -> u64 {
+    if let Some(&h) = memo.get(name) {
+        return h;
+    }
+
+    let Some(atom) = atoms.get(name) else {
+        // External symbol with no atom of its own: hash just its name, since
+        // there's no source text or dependency list to hash instead.
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        return hasher.finish();
+    };
+
+    let own = source_hash(atom, project_path);
+    if !in_progress.insert(name.to_string()) {
+        return own;
+    }
+
+    let mut dep_hashes: Vec<u64> = atom
+        .dependencies
+        .iter()
+        .map(|dep| transitive_hash(dep, atoms, project_path, memo, in_progress))
+        .collect();
+    dep_hashes.sort_unstable();
+
+    in_progress.remove(name);
+
+    let mut hasher = DefaultHasher::new();
+    own.hash(&mut hasher);
+    dep_hashes.hash(&mut hasher);
+    let combined = hasher.finish();
+    memo.insert(name.to_string(), combined);
+    combined
+}
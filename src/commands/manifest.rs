@@ -0,0 +1,167 @@
+//! Machine-readable run manifest, recording the exact inputs that produced
+//! a given `atoms.json` (or `atoms.rkyv`), the same way rustc's build
+//! tooling writes a checksum manifest listing each artifact alongside its
+//! hash and the toolchain version that built it.
+//!
+//! Without this, nothing ties a cached `atoms.json` to the SCIP files and
+//! config it was derived from, so downstream tooling and CI have no way to
+//! tell whether it's stale short of re-running the whole pipeline.
+//! [`write_manifest`] is called at the end of `cmd_atomize`/
+//! `atomize_internal`; [`is_atoms_fresh`] lets the next run compare stored
+//! hashes against current inputs before regenerating anything.
+
+use probe_verus::constants::DATA_DIR;
+use probe_verus::probe_config::ProbeConfig;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Filename for the run manifest, relative to [`DATA_DIR`].
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// One input file the manifest records a checksum for (an `index.scip`,
+/// `index.scip.json`, or similar SCIP artifact consumed while atomizing).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputChecksum {
+    pub path: String,
+    pub sha256: String,
+}
+
+/// A complete record of one atomize run: what it consumed, what it
+/// produced, and with which config -- enough to decide, on the next run,
+/// whether `atoms.json` is still valid without regenerating anything.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunManifest {
+    pub project_path: String,
+    pub indexer_version: String,
+    pub tool_version: String,
+    pub inputs: Vec<InputChecksum>,
+    pub config: ProbeConfig,
+    pub atom_count: usize,
+    pub output_file: String,
+}
+
+/// Path the manifest is read from/written to for `project_root`.
+pub fn manifest_path(project_root: &Path) -> PathBuf {
+    project_root.join(DATA_DIR).join(MANIFEST_FILE_NAME)
+}
+
+/// SHA-256 of a file's contents, hex-encoded. Returns `None` (rather than
+/// failing the whole manifest) when the file can't be read, so a manifest
+/// can still be written for whichever inputs were available.
+fn sha256_file(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Some(format!("{:x}", hasher.finalize()))
+}
+
+/// Checksum every path in `input_paths` that exists and is readable,
+/// skipping the rest -- a manifest with fewer inputs than expected is
+/// still useful; no manifest at all is not.
+fn checksum_inputs(input_paths: &[PathBuf]) -> Vec<InputChecksum> {
+    input_paths
+        .iter()
+        .filter_map(|path| {
+            sha256_file(path).map(|sha256| InputChecksum {
+                path: path.display().to_string(),
+                sha256,
+            })
+        })
+        .collect()
+}
+
+/// Build and write a [`RunManifest`] for `project_root` into
+/// `DATA_DIR/manifest.json`, recording the SHA-256 of every path in
+/// `input_paths`, the config in effect, and the atom count/output
+/// filename this run produced.
+pub fn write_manifest(
+    project_root: &Path,
+    input_paths: &[PathBuf],
+    indexer_version: &str,
+    atom_count: usize,
+    output_file: &str,
+) -> std::io::Result<()> {
+    let manifest = RunManifest {
+        project_path: project_root.display().to_string(),
+        indexer_version: indexer_version.to_string(),
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        inputs: checksum_inputs(input_paths),
+        config: ProbeConfig::global().clone(),
+        atom_count,
+        output_file: output_file.to_string(),
+    };
+
+    let path = manifest_path(project_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| std::io::Error::other(format!("failed to serialize manifest: {e}")))?;
+    std::fs::write(path, json)
+}
+
+/// Whether `project_root`'s cached `atoms.json` is still fresh: a manifest
+/// exists, every recorded input still exists with the same SHA-256, and
+/// the config in effect hasn't changed. A missing manifest, a missing
+/// input, or any hash mismatch means "no", not an error -- the caller
+/// should just regenerate.
+pub fn is_atoms_fresh(project_root: &Path) -> bool {
+    let Ok(contents) = std::fs::read_to_string(manifest_path(project_root)) else {
+        return false;
+    };
+    let Ok(manifest) = serde_json::from_str::<RunManifest>(&contents) else {
+        return false;
+    };
+    if manifest.config != *ProbeConfig::global() {
+        return false;
+    }
+    manifest.inputs.iter().all(|input| {
+        sha256_file(Path::new(&input.path)).as_deref() == Some(input.sha256.as_str())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_path_is_under_the_project_data_directory() {
+        let root = Path::new("/tmp/some-project");
+        assert_eq!(
+            manifest_path(root),
+            Path::new("/tmp/some-project/data/manifest.json")
+        );
+    }
+
+    #[test]
+    fn sha256_of_known_content_matches_a_known_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("empty.txt");
+        std::fs::write(&path, b"").unwrap();
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn is_atoms_fresh_is_false_without_a_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!is_atoms_fresh(dir.path()));
+    }
+
+    #[test]
+    fn write_then_check_is_fresh_when_nothing_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("index.scip.json");
+        std::fs::write(&input, b"{}").unwrap();
+
+        write_manifest(dir.path(), &[input.clone()], "verus-analyzer 0.1", 3, "atoms.json")
+            .unwrap();
+        assert!(is_atoms_fresh(dir.path()));
+
+        std::fs::write(&input, b"{\"changed\": true}").unwrap();
+        assert!(!is_atoms_fresh(dir.path()));
+    }
+}
@@ -8,23 +8,33 @@
 //! - `specs-data`: Generate specs_data.json for the specs browser
 //! - `tracked-csv`: Generate curve25519_functions.csv for the dashboard
 //! - `stubify`: Convert .md files with YAML frontmatter to JSON
+//! - `destubify`: Regenerate .md stubs with YAML frontmatter from the JSON index
 //! - `run`: Run both atomize and verify (for CI/Docker)
 
 mod atomize;
+mod baseline;
+mod cache;
 mod functions;
+mod manifest;
+mod reporter;
 mod run;
+mod scheduler;
 mod specify;
 mod specs_data;
+mod specs_metrics;
 mod stubify;
+mod tracked_annotations;
 mod tracked_csv;
 mod verify;
+mod verus_json;
+mod workspace;
 
 pub use atomize::cmd_atomize;
 pub use functions::cmd_functions;
 pub use run::cmd_run;
 pub use specify::cmd_specify;
 pub use specs_data::cmd_specs_data;
-pub use stubify::cmd_stubify;
+pub use stubify::{cmd_destubify, cmd_stubify, StubOutputFormat};
 pub use tracked_csv::cmd_tracked_csv;
 pub use verify::cmd_verify;
 
@@ -0,0 +1,159 @@
+//! Pluggable reporters for the `run` command.
+//!
+//! `cmd_run` used to hard-code `println!` banners for every step. Reporters let the
+//! same run drive different presentations: a live progress bar on a TTY, a quiet
+//! mode that only leaves JSON behind, and a CI mode that annotates failures
+//! directly on the GitHub Actions diff.
+
+use super::run::{FunctionOutcome, RunResult};
+use std::io::{IsTerminal, Write};
+
+/// Emits progress and result events for a `run` invocation.
+///
+/// Methods are called in order: `on_step_start` once per step ("atomize", "verify"),
+/// `on_function_result` once per verified function (in verify's step), and
+/// `finalize` once at the end with the complete `RunResult`.
+pub trait Reporter {
+    /// Called when a step (atomize/verify) begins. `total` is the expected
+    /// number of units of work for this step, if known in advance.
+    fn on_step_start(&mut self, step: &str, total: Option<usize>);
+
+    /// Called once per function as its verification outcome becomes known.
+    fn on_function_result(&mut self, outcome: &FunctionOutcome);
+
+    /// Called when a step completes.
+    fn on_step_end(&mut self, step: &str);
+
+    /// Called once at the very end of the run with the final result.
+    fn finalize(&mut self, result: &RunResult);
+}
+
+/// Pick the right reporter for the current environment.
+///
+/// - `GITHUB_ACTIONS=true` → [`CiReporter`], so failures show up inline on the diff.
+/// - `quiet` → [`QuietReporter`], for Docker/batch logs that shouldn't scroll.
+/// - otherwise, a live TTY → [`TtyReporter`] with a progress bar; non-TTY falls
+///   back to the same plain banners `cmd_run` always printed.
+pub fn select_reporter(quiet: bool) -> Box<dyn Reporter> {
+    if std::env::var("GITHUB_ACTIONS").as_deref() == Ok("true") {
+        Box::new(CiReporter::new())
+    } else if quiet {
+        Box::new(QuietReporter)
+    } else {
+        Box::new(TtyReporter::new())
+    }
+}
+
+/// Reporter that prints nothing beyond what's already written to `run_summary.json`.
+pub struct QuietReporter;
+
+impl Reporter for QuietReporter {
+    fn on_step_start(&mut self, _step: &str, _total: Option<usize>) {}
+    fn on_function_result(&mut self, _outcome: &FunctionOutcome) {}
+    fn on_step_end(&mut self, _step: &str) {}
+    fn finalize(&mut self, _result: &RunResult) {}
+}
+
+/// Reporter that drives a live progress bar when stdout is a terminal, and
+/// otherwise falls back to the classic banner output.
+pub struct TtyReporter {
+    is_tty: bool,
+    total: usize,
+    done: usize,
+}
+
+impl TtyReporter {
+    pub fn new() -> Self {
+        Self {
+            is_tty: std::io::stdout().is_terminal(),
+            total: 0,
+            done: 0,
+        }
+    }
+
+    fn draw_bar(&self) {
+        if !self.is_tty || self.total == 0 {
+            return;
+        }
+        let width = 30;
+        let filled = width * self.done / self.total;
+        let bar: String = "#".repeat(filled) + &"-".repeat(width - filled);
+        print!("\r  [{}] {}/{}", bar, self.done, self.total);
+        let _ = std::io::stdout().flush();
+    }
+}
+
+impl Default for TtyReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Reporter for TtyReporter {
+    fn on_step_start(&mut self, step: &str, total: Option<usize>) {
+        self.total = total.unwrap_or(0);
+        self.done = 0;
+        println!("  Step: {}", step);
+        self.draw_bar();
+    }
+
+    fn on_function_result(&mut self, _outcome: &FunctionOutcome) {
+        self.done += 1;
+        self.draw_bar();
+    }
+
+    fn on_step_end(&mut self, _step: &str) {
+        if self.is_tty && self.total > 0 {
+            println!();
+        }
+    }
+
+    fn finalize(&mut self, _result: &RunResult) {}
+}
+
+/// Reporter that emits GitHub Actions workflow commands for failed/unverified
+/// functions, so they show up as inline annotations on the pull request diff.
+///
+/// See <https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions>.
+pub struct CiReporter;
+
+impl CiReporter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CiReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Reporter for CiReporter {
+    fn on_step_start(&mut self, step: &str, total: Option<usize>) {
+        if let Some(total) = total {
+            println!("::group::{} ({} functions)", step, total);
+        } else {
+            println!("::group::{}", step);
+        }
+    }
+
+    fn on_function_result(&mut self, outcome: &FunctionOutcome) {
+        if let Some(message) = outcome.annotation_message() {
+            println!(
+                "::error file={},line={}::{}",
+                outcome.code_path, outcome.line, message
+            );
+        }
+    }
+
+    fn on_step_end(&mut self, _step: &str) {
+        println!("::endgroup::");
+    }
+
+    fn finalize(&mut self, result: &RunResult) {
+        if result.status == "verification_failed" {
+            println!("::error::Verification found regressions, see annotations above");
+        }
+    }
+}
@@ -1,16 +1,53 @@
 //! Run command - Execute both atomize and verify (designed for Docker/CI usage).
 
-use super::atomize::atomize_internal;
+use super::atomize::{atomize_internal, AtomizeFormat};
+use super::baseline::{self, BaselineComparison, StateChange};
+use super::reporter::{select_reporter, Reporter};
 use super::verify::{verify_internal, VerifySummary};
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 
 /// Result of the run command for JSON output.
 #[derive(Serialize)]
-struct RunResult {
-    status: String,
+pub(crate) struct RunResult {
+    pub(crate) status: String,
     atomize: Option<AtomizeResult>,
     verify: Option<VerifyResult>,
+    /// Functions that verified in `--baseline` but fail or are unverified now.
+    regressions: Option<Vec<StateChange>>,
+    /// Functions that didn't verify in `--baseline` but do now.
+    newly_fixed: Option<Vec<StateChange>>,
+}
+
+/// A single function's verification outcome, as seen by a [`Reporter`].
+pub(crate) struct FunctionOutcome {
+    pub(crate) display_name: String,
+    pub(crate) code_path: String,
+    pub(crate) line: usize,
+    pub(crate) status: FunctionStatus,
+}
+
+pub(crate) enum FunctionStatus {
+    Verified,
+    Failed,
+    Unverified,
+}
+
+impl FunctionOutcome {
+    /// The GitHub Actions annotation message for this outcome, or `None` if it
+    /// verified cleanly and doesn't need an annotation.
+    pub(crate) fn annotation_message(&self) -> Option<String> {
+        match self.status {
+            FunctionStatus::Failed => {
+                Some(format!("Verification failed for `{}`", self.display_name))
+            }
+            FunctionStatus::Unverified => Some(format!(
+                "`{}` is unverified (assume/admit present)",
+                self.display_name
+            )),
+            FunctionStatus::Verified => None,
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -35,6 +72,16 @@ struct VerifySummaryOutput {
     verified: usize,
     failed: usize,
     unverified: usize,
+    /// Dependents of a failed/unverified function that were never dispatched.
+    /// Only non-zero when `--jobs N` scheduled verification.
+    skipped: usize,
+    /// Wall-clock time for the scheduled run, in milliseconds, if `--jobs N` was used.
+    wall_clock_ms: Option<u128>,
+    /// Fraction of worker-time spent verifying vs. idle, if `--jobs N` was used.
+    worker_utilization: Option<f64>,
+    /// Functions reused from the incremental cache instead of re-verified.
+    cache_hits: usize,
+    cache_misses: usize,
 }
 
 impl From<VerifySummary> for VerifySummaryOutput {
@@ -44,6 +91,11 @@ impl From<VerifySummary> for VerifySummaryOutput {
             verified: s.verified,
             failed: s.failed,
             unverified: s.unverified,
+            skipped: s.skipped,
+            wall_clock_ms: s.wall_clock_ms,
+            worker_utilization: s.worker_utilization,
+            cache_hits: s.cache_hits,
+            cache_misses: s.cache_misses,
         }
     }
 }
@@ -51,6 +103,7 @@ impl From<VerifySummary> for VerifySummaryOutput {
 /// Execute the run command.
 ///
 /// Runs both atomize and verify commands (designed for Docker/CI usage).
+#[allow(clippy::too_many_arguments)]
 pub fn cmd_run(
     project_path: PathBuf,
     output_dir: PathBuf,
@@ -59,7 +112,29 @@ pub fn cmd_run(
     package: Option<String>,
     regenerate_scip: bool,
     verbose: bool,
+    quiet: bool,
+    jobs: usize,
+    workspace: bool,
+    exclude: Vec<String>,
+    baseline: Option<PathBuf>,
+    no_cache: bool,
+    clean_cache: bool,
+    progress: bool,
 ) {
+    if workspace {
+        return super::workspace::cmd_run_workspace(
+            project_path,
+            output_dir,
+            atomize_only,
+            verify_only,
+            regenerate_scip,
+            verbose,
+            jobs,
+            exclude,
+        );
+    }
+
+    let mut reporter = select_reporter(quiet);
     // Validate project path
     if !project_path.exists() {
         eprintln!(
@@ -93,6 +168,8 @@ pub fn cmd_run(
         status: "success".to_string(),
         atomize: None,
         verify: None,
+        regressions: None,
+        newly_fixed: None,
     };
 
     // === Run atomize ===
@@ -103,6 +180,7 @@ pub fn cmd_run(
             regenerate_scip,
             verbose,
             &mut run_result,
+            reporter.as_mut(),
         );
     }
 
@@ -114,12 +192,19 @@ pub fn cmd_run(
             &atoms_path,
             package.as_deref(),
             verbose,
+            jobs,
+            baseline.as_deref(),
+            no_cache,
+            clean_cache,
+            progress,
             &mut run_result,
+            reporter.as_mut(),
         );
     }
 
     // === Summary ===
     print_summary(&run_result);
+    reporter.finalize(&run_result);
 
     // Write summary JSON
     let summary_path = output_dir.join("run_summary.json");
@@ -159,13 +244,17 @@ fn run_atomize_step(
     regenerate_scip: bool,
     verbose: bool,
     run_result: &mut RunResult,
+    reporter: &mut dyn Reporter,
 ) {
     println!("───────────────────────────────────────────────────────────────");
     println!("  Step 1: Atomize (generate call graph)");
     println!("───────────────────────────────────────────────────────────────");
     println!();
+    reporter.on_step_start("atomize", None);
 
-    let atomize_result = atomize_internal(project_path, atoms_path, regenerate_scip, verbose);
+    let atomize_result =
+        atomize_internal(project_path, atoms_path, regenerate_scip, verbose, AtomizeFormat::Json);
+    reporter.on_step_end("atomize");
 
     match &atomize_result {
         Ok(count) => {
@@ -193,18 +282,26 @@ fn run_atomize_step(
 }
 
 /// Run the verify step.
+#[allow(clippy::too_many_arguments)]
 fn run_verify_step(
     project_path: &Path,
     results_path: &Path,
     atoms_path: &Path,
     package: Option<&str>,
     verbose: bool,
+    jobs: usize,
+    baseline_path: Option<&Path>,
+    no_cache: bool,
+    clean_cache: bool,
+    progress: bool,
     run_result: &mut RunResult,
+    reporter: &mut dyn Reporter,
 ) {
     println!("───────────────────────────────────────────────────────────────");
     println!("  Step 2: Verify (run Verus verification)");
     println!("───────────────────────────────────────────────────────────────");
     println!();
+    reporter.on_step_start("verify", None);
 
     let verify_result = verify_internal(
         project_path,
@@ -216,6 +313,10 @@ fn run_verify_step(
             None
         },
         verbose,
+        jobs,
+        no_cache,
+        clean_cache,
+        progress,
     );
 
     match &verify_result {
@@ -227,6 +328,31 @@ fn run_verify_step(
             println!("    Unverified: {}", summary.unverified);
             println!("  → {}", results_path.display());
 
+            for loc in &summary.verified_locations {
+                reporter.on_function_result(&FunctionOutcome {
+                    display_name: loc.display_name.clone(),
+                    code_path: loc.code_path.clone(),
+                    line: loc.code_text.lines_start,
+                    status: FunctionStatus::Verified,
+                });
+            }
+            for loc in &summary.failed_locations {
+                reporter.on_function_result(&FunctionOutcome {
+                    display_name: loc.display_name.clone(),
+                    code_path: loc.code_path.clone(),
+                    line: loc.code_text.lines_start,
+                    status: FunctionStatus::Failed,
+                });
+            }
+            for loc in &summary.unverified_locations {
+                reporter.on_function_result(&FunctionOutcome {
+                    display_name: loc.display_name.clone(),
+                    code_path: loc.code_path.clone(),
+                    line: loc.code_text.lines_start,
+                    status: FunctionStatus::Unverified,
+                });
+            }
+
             run_result.verify = Some(VerifyResult {
                 success: true,
                 output_file: results_path.display().to_string(),
@@ -238,6 +364,10 @@ fn run_verify_step(
             if summary.failed > 0 && run_result.status == "success" {
                 run_result.status = "verification_failed".to_string();
             }
+
+            if let Some(baseline_path) = baseline_path {
+                apply_baseline(project_path, baseline_path, summary, run_result);
+            }
         }
         Err(e) => {
             eprintln!("  ✗ Verify failed: {}", e);
@@ -252,9 +382,52 @@ fn run_verify_step(
             });
         }
     }
+    reporter.on_step_end("verify");
     println!();
 }
 
+/// Compare the fresh verification results against a `--baseline` snapshot and
+/// fold the outcome into `run_result`: CI only fails on regressions, not on
+/// pre-existing failures the baseline already had.
+fn apply_baseline(
+    project_path: &Path,
+    baseline_path: &Path,
+    summary: &VerifySummary,
+    run_result: &mut RunResult,
+) {
+    let baseline = match baseline::load_baseline(baseline_path) {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("  ✗ Could not load baseline: {}", e);
+            return;
+        }
+    };
+
+    let comparison: BaselineComparison = baseline::compare(
+        &baseline,
+        &summary.verified_locations,
+        &summary.failed_locations,
+        &summary.unverified_locations,
+        project_path,
+    );
+
+    if comparison.has_regressions() {
+        println!("  ✗ {} regression(s) vs. baseline:", comparison.regressions.len());
+        for change in &comparison.regressions {
+            println!("    - {} (was verified, now {:?})", change.symbol, change.to);
+        }
+        run_result.status = "verification_failed".to_string();
+    } else {
+        println!("  ✓ No regressions vs. baseline");
+    }
+    if !comparison.newly_fixed.is_empty() {
+        println!("  ✓ {} newly fixed vs. baseline", comparison.newly_fixed.len());
+    }
+
+    run_result.regressions = Some(comparison.regressions);
+    run_result.newly_fixed = Some(comparison.newly_fixed);
+}
+
 /// Print the final summary.
 fn print_summary(run_result: &RunResult) {
     println!("═══════════════════════════════════════════════════════════════");
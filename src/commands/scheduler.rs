@@ -0,0 +1,505 @@
+//! Concurrent verification scheduler, driven by the call graph in `atoms.json`.
+//!
+//! `verify_internal` runs `cargo verus verify` once and lets the output speak for
+//! every function. That's fine for `--jobs 1`, but it serializes functions that have
+//! no dependency relationship at all. This module turns the call graph into a
+//! topological work queue: a function is dispatched to a worker only once every
+//! callee it depends on has a terminal result, and a worker pool of size `--jobs N`
+//! drains the queue as dependencies resolve. Mutually recursive functions can't be
+//! ordered against each other, so they're grouped into a single schedulable unit
+//! (a strongly connected component of the call graph) and verified together.
+//!
+//! A dependency that didn't verify cleanly poisons its dependents: rather than
+//! spending a verifier invocation on a function that is almost certainly doomed,
+//! dependents of a failed/unverified group are marked `Skipped` so the summary can
+//! tell "never attempted because a callee failed" apart from a genuine unverified
+//! result.
+
+use super::cache::{CachedStatus, VerificationCache};
+use probe_verus::verification::{
+    AnalysisStatus, FunctionLocation, VerificationAnalyzer, VerusRunner,
+};
+use probe_verus::AtomWithLines;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Instant;
+
+/// Terminal outcome of a single schedulable group.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GroupStatus {
+    Verified,
+    Failed,
+    Unverified,
+    /// Never dispatched because a dependency didn't verify cleanly.
+    Skipped,
+}
+
+/// Timing and outcome for one schedulable group, for the `--jobs N` summary.
+#[derive(Clone)]
+pub struct GroupResult {
+    pub members: Vec<String>,
+    pub status: GroupStatus,
+    pub duration_ms: u128,
+}
+
+/// Result of a scheduled run: per-group outcomes plus enough bookkeeping to report
+/// wall-clock speedup and worker utilization.
+pub struct ScheduleSummary {
+    pub groups: Vec<GroupResult>,
+    pub wall_clock_ms: u128,
+    /// Sum of each group's own duration, divided by wall-clock time and worker
+    /// count -- 1.0 means the pool was busy the entire run, lower means workers
+    /// spent time idle waiting on dependencies.
+    pub worker_utilization: f64,
+    /// Functions whose result was reused from [`VerificationCache`] instead of
+    /// invoking Verus.
+    pub cache_hits: usize,
+    pub cache_misses: usize,
+}
+
+impl ScheduleSummary {
+    pub fn verified_locations(&self) -> Vec<FunctionLocation> {
+        self.locations_with_status(GroupStatus::Verified)
+    }
+
+    pub fn failed_locations(&self) -> Vec<FunctionLocation> {
+        self.locations_with_status(GroupStatus::Failed)
+    }
+
+    pub fn unverified_locations(&self) -> Vec<FunctionLocation> {
+        self.locations_with_status(GroupStatus::Unverified)
+    }
+
+    pub fn skipped_count(&self) -> usize {
+        self.groups
+            .iter()
+            .filter(|g| g.status == GroupStatus::Skipped)
+            .map(|g| g.members.len())
+            .sum()
+    }
+
+    fn locations_with_status(&self, status: GroupStatus) -> Vec<FunctionLocation> {
+        self.groups
+            .iter()
+            .filter(|g| g.status == status)
+            .flat_map(|g| g.members.iter())
+            .map(|name| FunctionLocation {
+                display_name: name.clone(),
+                code_path: String::new(),
+                code_text: probe_verus::verification::CodeTextInfo {
+                    lines_start: 0,
+                    lines_end: 0,
+                },
+            })
+            .collect()
+    }
+}
+
+/// A schedulable unit: either a single function, or a strongly connected component
+/// of mutually recursive functions that must be verified as one.
+struct Group {
+    members: Vec<String>,
+}
+
+/// Find strongly connected components of the call graph via iterative Tarjan,
+/// so mutually recursive functions land in the same schedulable group.
+fn find_groups(atoms: &HashMap<String, AtomWithLines>) -> Vec<Group> {
+    let mut index_counter = 0usize;
+    let mut stack = Vec::new();
+    let mut on_stack: HashSet<String> = HashSet::new();
+    let mut indices: HashMap<String, usize> = HashMap::new();
+    let mut lowlink: HashMap<String, usize> = HashMap::new();
+    let mut groups = Vec::new();
+
+    // Work list of (node, iterator position over its dependencies).
+    for start in atoms.keys() {
+        if indices.contains_key(start) {
+            continue;
+        }
+        let mut call_stack: Vec<(String, usize)> = vec![(start.clone(), 0)];
+        indices.insert(start.clone(), index_counter);
+        lowlink.insert(start.clone(), index_counter);
+        index_counter += 1;
+        stack.push(start.clone());
+        on_stack.insert(start.clone());
+
+        while let Some((node, pos)) = call_stack.pop() {
+            let deps: Vec<String> = atoms
+                .get(&node)
+                .map(|a| a.dependencies.iter().cloned().collect())
+                .unwrap_or_default();
+
+            if pos < deps.len() {
+                let dep = deps[pos].clone();
+                call_stack.push((node.clone(), pos + 1));
+
+                if !atoms.contains_key(&dep) {
+                    // External symbol with no atom of its own; not part of this graph.
+                    continue;
+                }
+
+                if !indices.contains_key(&dep) {
+                    indices.insert(dep.clone(), index_counter);
+                    lowlink.insert(dep.clone(), index_counter);
+                    index_counter += 1;
+                    stack.push(dep.clone());
+                    on_stack.insert(dep.clone());
+                    call_stack.push((dep, 0));
+                } else if on_stack.contains(&dep) {
+                    let dep_index = indices[&dep];
+                    let node_low = lowlink[&node];
+                    lowlink.insert(node.clone(), node_low.min(dep_index));
+                }
+            } else {
+                // Finished exploring `node`'s dependencies: propagate lowlink to parent.
+                if let Some((parent, _)) = call_stack.last() {
+                    let node_low = lowlink[&node];
+                    let parent_low = lowlink[parent];
+                    lowlink.insert(parent.clone(), parent_low.min(node_low));
+                }
+
+                if lowlink[&node] == indices[&node] {
+                    let mut members = Vec::new();
+                    loop {
+                        let popped = stack.pop().unwrap();
+                        on_stack.remove(&popped);
+                        let is_root = popped == node;
+                        members.push(popped);
+                        if is_root {
+                            break;
+                        }
+                    }
+                    groups.push(Group { members });
+                }
+            }
+        }
+    }
+
+    groups
+}
+
+/// Run verification concurrently over the call graph, dispatching schedulable
+/// groups to a pool of `jobs` workers as their dependencies resolve.
+pub fn run_scheduled_verification(
+    project_path: &std::path::Path,
+    atoms: HashMap<String, AtomWithLines>,
+    package: Option<&str>,
+    jobs: usize,
+    cache: VerificationCache,
+) -> ScheduleSummary {
+    let groups = find_groups(&atoms);
+
+    // Map each function to the index of the group it belongs to.
+    let mut group_of: HashMap<String, usize> = HashMap::new();
+    for (idx, group) in groups.iter().enumerate() {
+        for member in &group.members {
+            group_of.insert(member.clone(), idx);
+        }
+    }
+
+    // Outstanding dependency-group count and reverse edges (dependents), deduped
+    // so a group with two call sites into another group isn't double-counted.
+    let mut outstanding = vec![0usize; groups.len()];
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); groups.len()];
+    for (idx, group) in groups.iter().enumerate() {
+        let mut seen = HashSet::new();
+        for member in &group.members {
+            if let Some(atom) = atoms.get(member) {
+                for dep in &atom.dependencies {
+                    if let Some(&dep_idx) = group_of.get(dep) {
+                        if dep_idx != idx && seen.insert(dep_idx) {
+                            outstanding[idx] += 1;
+                            dependents[dep_idx].push(idx);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let state = Arc::new((
+        Mutex::new(SchedulerState {
+            ready: (0..groups.len())
+                .filter(|&i| outstanding[i] == 0)
+                .collect(),
+            outstanding,
+            poisoned: vec![false; groups.len()],
+            results: vec![None; groups.len()],
+            finished: 0,
+            cache_hits: 0,
+            cache_misses: 0,
+        }),
+        Condvar::new(),
+    ));
+
+    let wall_clock_start = Instant::now();
+    let groups = Arc::new(groups);
+    let dependents = Arc::new(dependents);
+    let atoms = Arc::new(atoms);
+    let cache = Arc::new(cache);
+    let total_groups = groups.len();
+    let worker_count = jobs.max(1);
+
+    std::thread::scope(|scope| {
+        for _ in 0..worker_count {
+            let state = Arc::clone(&state);
+            let groups = Arc::clone(&groups);
+            let dependents = Arc::clone(&dependents);
+            let atoms = Arc::clone(&atoms);
+            let cache = Arc::clone(&cache);
+            scope.spawn(move || {
+                worker_loop(
+                    project_path,
+                    package,
+                    &state,
+                    &groups,
+                    &dependents,
+                    &atoms,
+                    &cache,
+                    total_groups,
+                );
+            });
+        }
+    });
+
+    let (lock, _) = &*state;
+    let final_state = lock.lock().unwrap();
+    let busy_ms: u128 = final_state
+        .results
+        .iter()
+        .filter_map(|r| r.as_ref())
+        .map(|r| r.duration_ms)
+        .sum();
+    let wall_clock_ms = wall_clock_start.elapsed().as_millis();
+    let worker_utilization = if wall_clock_ms == 0 || worker_count == 0 {
+        1.0
+    } else {
+        (busy_ms as f64) / (wall_clock_ms as f64 * worker_count as f64)
+    };
+
+    let group_results = final_state
+        .results
+        .iter()
+        .cloned()
+        .map(|r| r.expect("every group is dispatched exactly once"))
+        .collect();
+    let cache_hits = final_state.cache_hits;
+    let cache_misses = final_state.cache_misses;
+
+    ScheduleSummary {
+        groups: group_results,
+        wall_clock_ms,
+        worker_utilization,
+        cache_hits,
+        cache_misses,
+    }
+}
+
+struct SchedulerState {
+    ready: VecDeque<usize>,
+    outstanding: Vec<usize>,
+    /// Set as soon as any (transitive) dependency fails/is-unverified, even if
+    /// other dependencies are still outstanding -- a later clean dependency must
+    /// not erase an earlier poison signal.
+    poisoned: Vec<bool>,
+    results: Vec<Option<GroupResult>>,
+    finished: usize,
+    cache_hits: usize,
+    cache_misses: usize,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn worker_loop(
+    project_path: &std::path::Path,
+    package: Option<&str>,
+    state: &(Mutex<SchedulerState>, Condvar),
+    groups: &[Group],
+    dependents: &[Vec<usize>],
+    atoms: &HashMap<String, AtomWithLines>,
+    cache: &VerificationCache,
+    total_groups: usize,
+) {
+    let (lock, cvar) = state;
+    loop {
+        let idx = {
+            let mut guard = lock.lock().unwrap();
+            loop {
+                if let Some(idx) = guard.ready.pop_front() {
+                    break idx;
+                }
+                if guard.finished == total_groups {
+                    return;
+                }
+                guard = cvar.wait(guard).unwrap();
+            }
+        };
+
+        let group = &groups[idx];
+        let (status, duration_ms, hits, misses) =
+            verify_group(project_path, package, group, atoms, cache);
+
+        let mut guard = lock.lock().unwrap();
+        guard.finished += 1;
+        guard.cache_hits += hits;
+        guard.cache_misses += misses;
+        guard.results[idx] = Some(GroupResult {
+            members: group.members.clone(),
+            status,
+            duration_ms,
+        });
+
+        // A dependency that didn't verify cleanly poisons its dependents instead
+        // of letting them spend a verifier run they're almost certainly going to
+        // fail anyway. The poison flag is sticky: it must survive even if a
+        // sibling dependency that finishes later turns out clean.
+        let poisons = status != GroupStatus::Verified;
+        for &dep_idx in &dependents[idx] {
+            guard.outstanding[dep_idx] -= 1;
+            if poisons {
+                guard.poisoned[dep_idx] = true;
+            }
+            if guard.outstanding[dep_idx] == 0 && guard.results[dep_idx].is_none() {
+                if guard.poisoned[dep_idx] {
+                    mark_skipped(&mut guard, groups, dependents, dep_idx);
+                } else {
+                    guard.ready.push_back(dep_idx);
+                }
+            }
+        }
+        cvar.notify_all();
+
+        if guard.finished == total_groups {
+            return;
+        }
+    }
+}
+
+/// Recursively mark `idx` and everything downstream of it as `Skipped`, since a
+/// dependency failed to verify. Only called once `idx`'s own dependencies have
+/// all resolved.
+fn mark_skipped(
+    guard: &mut SchedulerState,
+    groups: &[Group],
+    dependents: &[Vec<usize>],
+    idx: usize,
+) {
+    guard.finished += 1;
+    guard.results[idx] = Some(GroupResult {
+        members: groups[idx].members.clone(),
+        status: GroupStatus::Skipped,
+        duration_ms: 0,
+    });
+    for &dep_idx in &dependents[idx] {
+        guard.outstanding[dep_idx] -= 1;
+        guard.poisoned[dep_idx] = true;
+        if guard.outstanding[dep_idx] == 0 && guard.results[dep_idx].is_none() {
+            mark_skipped(guard, groups, dependents, dep_idx);
+        }
+    }
+}
+
+/// Verify every member of a schedulable group, one `--verify-function` invocation
+/// at a time, and fold the results into a single group status (worst result wins).
+/// Each member is looked up in `cache` first; a hit reuses the prior outcome
+/// without invoking Verus at all, a miss verifies normally and stores its result.
+/// Returns `(status, duration_ms, cache_hits, cache_misses)`.
+fn verify_group(
+    project_path: &std::path::Path,
+    package: Option<&str>,
+    group: &Group,
+    atoms: &HashMap<String, AtomWithLines>,
+    cache: &VerificationCache,
+) -> (GroupStatus, u128, usize, usize) {
+    let runner = VerusRunner::new();
+    let analyzer = VerificationAnalyzer::new();
+    let start = Instant::now();
+
+    let mut worst = GroupStatus::Verified;
+    let mut hits = 0usize;
+    let mut misses = 0usize;
+    for member in &group.members {
+        let Some(atom) = atoms.get(member) else {
+            continue;
+        };
+
+        let key = cache.key_for(member, atoms, project_path);
+        if let Some(cached) = cache.lookup(key) {
+            hits += 1;
+            worst = worse_of(worst, cached_status_to_group_status(cached));
+            continue;
+        }
+        misses += 1;
+
+        let (captured, exit_code) = match runner.run_verification(
+            project_path,
+            package,
+            None,
+            Some(&atom.display_name),
+            None,
+        ) {
+            Ok(r) => r,
+            Err(_) => {
+                worst = GroupStatus::Failed;
+                continue;
+            }
+        };
+        let output = captured.text;
+
+        let result = analyzer.analyze_output(
+            project_path,
+            &output,
+            Some(exit_code),
+            None,
+            Some(&atom.display_name),
+            None,
+        );
+
+        let member_status = match result.status {
+            AnalysisStatus::Success if result.summary.unverified_functions > 0 => {
+                GroupStatus::Unverified
+            }
+            AnalysisStatus::Success => GroupStatus::Verified,
+            _ => GroupStatus::Failed,
+        };
+
+        cache.store(key, group_status_to_cached_status(member_status));
+        worst = worse_of(worst, member_status);
+    }
+
+    (worst, start.elapsed().as_millis(), hits, misses)
+}
+
+fn cached_status_to_group_status(status: CachedStatus) -> GroupStatus {
+    match status {
+        CachedStatus::Verified => GroupStatus::Verified,
+        CachedStatus::Failed => GroupStatus::Failed,
+        CachedStatus::Unverified => GroupStatus::Unverified,
+    }
+}
+
+/// `Skipped` has no `CachedStatus` counterpart -- a skipped group was never
+/// verified, so there's nothing to cache -- this is only reached for the
+/// three statuses `verify_group` can actually produce per member.
+fn group_status_to_cached_status(status: GroupStatus) -> CachedStatus {
+    match status {
+        GroupStatus::Verified => CachedStatus::Verified,
+        GroupStatus::Unverified => CachedStatus::Unverified,
+        GroupStatus::Failed | GroupStatus::Skipped => CachedStatus::Failed,
+    }
+}
+
+fn worse_of(a: GroupStatus, b: GroupStatus) -> GroupStatus {
+    fn rank(s: GroupStatus) -> u8 {
+        match s {
+            GroupStatus::Verified => 0,
+            GroupStatus::Unverified => 1,
+            GroupStatus::Failed => 2,
+            GroupStatus::Skipped => 3,
+        }
+    }
+    if rank(b) > rank(a) {
+        b
+    } else {
+        a
+    }
+}
@@ -1,12 +1,12 @@
 //! Specify command - Extract function specifications to JSON.
 
-use probe_verus::constants::LINE_TOLERANCE;
+use probe_verus::line_index::{read_source_file, LineIndex};
 use probe_verus::path_utils::{extract_src_suffix, paths_match_by_suffix};
 use probe_verus::taxonomy;
 use probe_verus::verus_parser::{self, FunctionInfo, ParsedOutput};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 /// Atom entry from atoms.json for code-name lookup.
 #[derive(Deserialize)]
@@ -38,6 +38,19 @@ struct SpecifyEntry {
 ///
 /// Extracts function specifications (requires/ensures) to JSON,
 /// keyed by code-name from atoms.json.
+///
+/// When `metrics` is given together with a taxonomy config, also writes a
+/// [`super::specs_metrics::CoverageMetrics`]
+/// snapshot for this run into the growing history file at that path (keyed by
+/// `metrics_run_id`, defaulting to the current Unix timestamp). When
+/// `metrics_baseline` is also given, diffs the snapshot against it and exits
+/// non-zero if any coverage metric regressed beyond `metrics_threshold_percent`.
+///
+/// When two or more atoms tie as the closest match for a function, the
+/// match is ambiguous: rather than silently picking one, it's reported to
+/// stderr and counted in the metrics as unmatched. When `strict_match` is
+/// set, any unmatched or ambiguous function makes the run exit non-zero.
+#[allow(clippy::too_many_arguments)]
 pub fn cmd_specify(
     path: PathBuf,
     output: PathBuf,
@@ -45,6 +58,11 @@ pub fn cmd_specify(
     with_spec_text: bool,
     taxonomy_config_path: Option<PathBuf>,
     taxonomy_explain: bool,
+    metrics: Option<PathBuf>,
+    metrics_baseline: Option<PathBuf>,
+    metrics_run_id: Option<String>,
+    metrics_threshold_percent: f64,
+    strict_match: bool,
 ) {
     // Validate inputs
     if !path.exists() {
@@ -86,7 +104,30 @@ pub fn cmd_specify(
     );
 
     // Match functions to code-names and build output dictionary
-    let (matched_map, matched_count, unmatched_count) = match_functions_to_atoms(parsed, &atoms);
+    let (matched_map, matched_count, unmatched_count, ambiguous) =
+        match_functions_to_atoms(parsed, &atoms, &path);
+
+    if !ambiguous.is_empty() {
+        eprintln!("Ambiguous matches ({}):", ambiguous.len());
+        for amb in &ambiguous {
+            eprintln!(
+                "  {} ({}:{}) -> candidates: {}",
+                amb.function,
+                amb.file,
+                amb.line,
+                amb.candidates.join(", ")
+            );
+        }
+    }
+
+    if strict_match && unmatched_count > 0 {
+        eprintln!(
+            "✗ --strict-match: {} unmatched function(s) ({} ambiguous)",
+            unmatched_count,
+            ambiguous.len()
+        );
+        std::process::exit(1);
+    }
 
     // Classify with taxonomy and build final output
     let output_map: BTreeMap<String, SpecifyEntry> = matched_map
@@ -168,6 +209,78 @@ pub fn cmd_specify(
                 labeled_total, matched_count
             );
         }
+
+        if let Some(metrics_path) = metrics {
+            let mut label_counts: BTreeMap<String, usize> = BTreeMap::new();
+            for entry in output_map.values() {
+                for label in &entry.spec_labels {
+                    *label_counts.entry(label.clone()).or_insert(0) += 1;
+                }
+            }
+
+            let run_id = metrics_run_id.unwrap_or_else(|| {
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_secs().to_string())
+                    .unwrap_or_else(|_| "0".to_string())
+            });
+
+            let current = super::specs_metrics::CoverageMetrics::compute(
+                run_id,
+                matched_count,
+                unmatched_count,
+                specified_total,
+                labeled_total,
+                specified_labeled,
+                ambiguous.len(),
+                label_counts,
+            );
+
+            if let Err(e) = super::specs_metrics::append_to_history(&metrics_path, &current) {
+                eprintln!("Warning: failed to write metrics to {}: {}", metrics_path.display(), e);
+            } else {
+                println!("Metrics written to {}", metrics_path.display());
+            }
+
+            if let Some(baseline_path) = metrics_baseline {
+                match super::specs_metrics::load_baseline(&baseline_path) {
+                    Ok(baseline) => {
+                        let diff =
+                            super::specs_metrics::MetricsDiff::compute(&baseline, &current);
+                        println!();
+                        println!("Metrics diff vs {}:", baseline_path.display());
+                        for delta in &diff.fields {
+                            println!(
+                                "  {}: {} -> {} ({:+.1})",
+                                delta.field,
+                                delta.before,
+                                delta.after,
+                                delta.change()
+                            );
+                        }
+                        for delta in diff.label_deltas.values() {
+                            println!(
+                                "  label[{}]: {} -> {} ({:+.1})",
+                                delta.field,
+                                delta.before,
+                                delta.after,
+                                delta.change()
+                            );
+                        }
+                        if diff.has_regression(metrics_threshold_percent) {
+                            eprintln!(
+                                "✗ Coverage regressed beyond the {:.1}-point threshold",
+                                metrics_threshold_percent
+                            );
+                            std::process::exit(1);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("Warning: failed to load metrics baseline: {}", e);
+                    }
+                }
+            }
+        }
     } else {
         println!(
             "Wrote {} functions to {} ({} unmatched)",
@@ -184,44 +297,141 @@ fn load_atoms(atoms_path: &PathBuf) -> BTreeMap<String, AtomEntry> {
     serde_json::from_str(&atoms_content).expect("Failed to parse atoms.json")
 }
 
-/// Match parsed functions to atoms by path and line number.
+/// Source text plus its [`LineIndex`] for a single file, cached so each
+/// file is read and indexed at most once per `specify` run.
+struct FileIndex {
+    source: String,
+    line_index: LineIndex,
+}
+
+/// A function whose atom match was ambiguous: two or more atoms tied as
+/// the closest candidate to its declaration line.
+#[derive(Serialize)]
+struct AmbiguousMatch {
+    function: String,
+    file: String,
+    line: usize,
+    candidates: Vec<String>,
+}
+
+/// Outcome of matching a single function against the atom set.
+enum MatchOutcome {
+    Matched(String),
+    Ambiguous(Vec<String>),
+    Unmatched,
+}
+
+/// Match parsed functions to atoms by path and byte-offset span overlap.
 fn match_functions_to_atoms(
     parsed: ParsedOutput,
     atoms: &BTreeMap<String, AtomEntry>,
-) -> (BTreeMap<String, FunctionInfo>, usize, usize) {
+    project_root: &Path,
+) -> (BTreeMap<String, FunctionInfo>, usize, usize, Vec<AmbiguousMatch>) {
     let mut output_map: BTreeMap<String, FunctionInfo> = BTreeMap::new();
     let mut matched_count = 0;
     let mut unmatched_count = 0;
+    let mut ambiguous = Vec::new();
+    let mut file_indices: HashMap<String, FileIndex> = HashMap::new();
 
     for func in parsed.functions {
-        if let Some(code_name) = find_matching_atom(&func, atoms) {
-            output_map.insert(code_name, func);
-            matched_count += 1;
-        } else {
-            unmatched_count += 1;
+        match find_matching_atom(&func, atoms, project_root, &mut file_indices) {
+            MatchOutcome::Matched(code_name) => {
+                output_map.insert(code_name, func);
+                matched_count += 1;
+            }
+            MatchOutcome::Ambiguous(candidates) => {
+                ambiguous.push(AmbiguousMatch {
+                    function: func.name.clone(),
+                    file: func.file.clone().unwrap_or_default(),
+                    line: func.spec_text.lines_start,
+                    candidates,
+                });
+                unmatched_count += 1;
+            }
+            MatchOutcome::Unmatched => {
+                unmatched_count += 1;
+            }
         }
     }
 
-    (output_map, matched_count, unmatched_count)
+    (output_map, matched_count, unmatched_count, ambiguous)
+}
+
+/// Load and cache the [`FileIndex`] for `func_path`, resolved against
+/// `project_root`. Returns `None` if the file can't be read.
+fn file_index<'a>(
+    func_path: &str,
+    project_root: &Path,
+    cache: &'a mut HashMap<String, FileIndex>,
+) -> Option<&'a FileIndex> {
+    if !cache.contains_key(func_path) {
+        let source = read_source_file(&project_root.join(func_path)).ok()?;
+        let line_index = LineIndex::new(&source);
+        cache.insert(func_path.to_string(), FileIndex { source, line_index });
+    }
+    cache.get(func_path)
 }
 
-/// Find the best matching atom for a function.
+/// The first line in `[start_line, end_line]` that isn't a leading
+/// attribute or doc-comment line -- i.e. the `fn`/`spec fn` keyword line
+/// itself, rather than the comment-inflated span start verus_syn reports.
+fn declaration_line(index: &FileIndex, start_line: usize, end_line: usize) -> usize {
+    for line in start_line..=end_line.max(start_line) {
+        let text = index.line_index.line_text(&index.source, line).trim();
+        if text.is_empty()
+            || text.starts_with("///")
+            || text.starts_with("//!")
+            || text.starts_with("//")
+            || text.starts_with("#[")
+            || text.starts_with("#![")
+        {
+            continue;
+        }
+        return line;
+    }
+    start_line
+}
+
+/// Find the best matching atom(s) for a function.
 ///
 /// Matching strategy:
 /// 1. Path must match (by suffix comparison)
 /// 2. Display name must match
-/// 3. SCIP line must fall within the function's span [start_line, end_line]
-///    OR be within LINE_TOLERANCE of start_line
+/// 3. The atom's SCIP line, converted to a byte offset, must land inside
+///    the function's true declaration range -- `[declaration_line,
+///    spec_text.lines_end]` with leading attribute/doc-comment lines
+///    skipped, so a comment-inflated span doesn't shadow the next
+///    closely-stacked function's declaration.
 ///
-/// This handles the case where verus_syn includes doc comments in the span
-/// (reporting an earlier start_line) while verus-analyzer reports the actual
-/// function declaration line.
-fn find_matching_atom(func: &FunctionInfo, atoms: &BTreeMap<String, AtomEntry>) -> Option<String> {
+/// Coordinates are normalized to byte offsets via a per-file
+/// [`LineIndex`] rather than compared as raw, possibly-off-by-a-few line
+/// numbers. When two or more candidate atoms tie at the same distance
+/// from the declaration line (common with trait-method impls and
+/// macro-duplicated functions), the match is reported as
+/// [`MatchOutcome::Ambiguous`] rather than silently picking one.
+fn find_matching_atom(
+    func: &FunctionInfo,
+    atoms: &BTreeMap<String, AtomEntry>,
+    project_root: &Path,
+    file_indices: &mut HashMap<String, FileIndex>,
+) -> MatchOutcome {
     let func_path = func.file.as_deref().unwrap_or("");
     let func_suffix = extract_src_suffix(func_path);
 
-    let mut best_match: Option<&str> = None;
-    let mut best_line_diff = usize::MAX;
+    let Some(index) = file_index(func_path, project_root, file_indices) else {
+        return MatchOutcome::Unmatched;
+    };
+    let decl_line = declaration_line(
+        index,
+        func.spec_text.lines_start,
+        func.spec_text.lines_end,
+    );
+    let decl_offset = index.line_index.offset_of_line(decl_line);
+    let span_end_offset = index
+        .line_index
+        .offset_of_line(func.spec_text.lines_end + 1);
+
+    let mut candidates: Vec<(&str, usize)> = Vec::new();
 
     for (code_name, atom) in atoms {
         let atom_suffix = extract_src_suffix(&atom.code_path);
@@ -229,40 +439,30 @@ fn find_matching_atom(func: &FunctionInfo, atoms: &BTreeMap<String, AtomEntry>)
         let path_matches =
             paths_match_by_suffix(func_path, &atom.code_path) || func_suffix == atom_suffix;
 
-        if path_matches && func.name == atom.display_name {
-            let atom_line = atom.code_text.lines_start;
-
-            // Check if SCIP line falls within the function span [start_line, end_line]
-            // This handles doc comments being included in verus_syn's span
-            let within_span =
-                atom_line >= func.spec_text.lines_start && atom_line <= func.spec_text.lines_end;
-
-            // Also check traditional tolerance for cases without doc comments
-            let line_diff =
-                (func.spec_text.lines_start as isize - atom_line as isize).unsigned_abs();
-            let within_tolerance = line_diff <= LINE_TOLERANCE;
-
-            if within_span || within_tolerance {
-                // Prefer matches closer to start_line
-                let effective_diff = if within_span && !within_tolerance {
-                    // SCIP line is within span but after tolerance - use distance from start
-                    atom_line - func.spec_text.lines_start
-                } else {
-                    line_diff
-                };
-
-                if effective_diff < best_line_diff {
-                    best_match = Some(code_name);
-                    best_line_diff = effective_diff;
-
-                    // Exact match - can't do better
-                    if effective_diff == 0 {
-                        break;
-                    }
-                }
-            }
+        if !path_matches || func.name != atom.display_name {
+            continue;
         }
+
+        let atom_offset = index.line_index.offset_of_line(atom.code_text.lines_start);
+        if atom_offset < decl_offset || atom_offset >= span_end_offset {
+            continue;
+        }
+
+        candidates.push((code_name.as_str(), atom_offset - decl_offset));
     }
 
-    best_match.map(|s| s.to_string())
+    let Some(best_distance) = candidates.iter().map(|(_, d)| *d).min() else {
+        return MatchOutcome::Unmatched;
+    };
+    let tied: Vec<String> = candidates
+        .into_iter()
+        .filter(|(_, d)| *d == best_distance)
+        .map(|(code_name, _)| code_name.to_string())
+        .collect();
+
+    if tied.len() > 1 {
+        MatchOutcome::Ambiguous(tied)
+    } else {
+        MatchOutcome::Matched(tied.into_iter().next().expect("tied is non-empty"))
+    }
 }
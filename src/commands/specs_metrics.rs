@@ -0,0 +1,299 @@
+//! Taxonomy coverage metrics for `specify --metrics`, with baseline
+//! regression detection.
+//!
+//! Mirrors rust-analyzer's `metrics.json` approach: each run emits one
+//! self-contained [`CoverageMetrics`] object keyed by `run_id`, merged into
+//! a single growing history file (`--metrics <path>`) rather than
+//! overwriting it, so the file accumulates one entry per run/commit for
+//! plotting spec-coverage drift over time. `--metrics-baseline <path>`
+//! instead loads a single prior [`CoverageMetrics`] snapshot to diff the
+//! current run against and fail CI on regression.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// One run's taxonomy coverage.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct CoverageMetrics {
+    pub run_id: String,
+    pub total_functions: usize,
+    pub matched_functions: usize,
+    pub unmatched_functions: usize,
+    pub specified_functions: usize,
+    pub labeled_functions: usize,
+    pub specified_labeled_functions: usize,
+    /// Functions whose atom match was ambiguous (multiple atoms tied as
+    /// the closest candidate), counted among `unmatched_functions`.
+    pub ambiguous_functions: usize,
+    pub label_counts: BTreeMap<String, usize>,
+    /// `specified_labeled_functions / specified_functions`, as a percentage.
+    pub specified_coverage_percent: f64,
+    /// `labeled_functions / matched_functions`, as a percentage.
+    pub overall_coverage_percent: f64,
+}
+
+impl CoverageMetrics {
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute(
+        run_id: String,
+        matched_functions: usize,
+        unmatched_functions: usize,
+        specified_functions: usize,
+        labeled_functions: usize,
+        specified_labeled_functions: usize,
+        ambiguous_functions: usize,
+        label_counts: BTreeMap<String, usize>,
+    ) -> Self {
+        let specified_coverage_percent = if specified_functions > 0 {
+            100.0 * specified_labeled_functions as f64 / specified_functions as f64
+        } else {
+            0.0
+        };
+        let overall_coverage_percent = if matched_functions > 0 {
+            100.0 * labeled_functions as f64 / matched_functions as f64
+        } else {
+            0.0
+        };
+
+        CoverageMetrics {
+            run_id,
+            total_functions: matched_functions + unmatched_functions,
+            matched_functions,
+            unmatched_functions,
+            specified_functions,
+            labeled_functions,
+            specified_labeled_functions,
+            ambiguous_functions,
+            label_counts,
+            specified_coverage_percent,
+            overall_coverage_percent,
+        }
+    }
+}
+
+/// Load the growing metrics history file (a map of `run_id` to
+/// [`CoverageMetrics`]), or an empty map if `path` doesn't exist yet.
+pub fn load_history(path: &Path) -> Result<BTreeMap<String, CoverageMetrics>, String> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse metrics history {}: {}", path.display(), e))
+}
+
+/// Merge `metrics` into the history file at `path` (overwriting any prior
+/// entry with the same `run_id`) and write it back.
+pub fn append_to_history(path: &Path, metrics: &CoverageMetrics) -> Result<(), String> {
+    let mut history = load_history(path)?;
+    history.insert(metrics.run_id.clone(), metrics.clone());
+    let json = serde_json::to_string_pretty(&history)
+        .map_err(|e| format!("Failed to serialize metrics history: {}", e))?;
+    std::fs::write(path, json)
+        .map_err(|e| format!("Failed to write metrics history {}: {}", path.display(), e))
+}
+
+/// Load a single baseline [`CoverageMetrics`] snapshot (not a history map).
+pub fn load_baseline(path: &Path) -> Result<CoverageMetrics, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read baseline {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse baseline {}: {}", path.display(), e))
+}
+
+/// Whether a [`FieldDelta`] holds a percentage (comparable to a
+/// percentage-point threshold) or a raw count (where "points of
+/// decrease" has no meaning -- a smaller codebase isn't a regression).
+#[derive(PartialEq, Eq)]
+pub enum FieldKind {
+    Percent,
+    Count,
+}
+
+/// One field's change between a baseline run and the current one.
+pub struct FieldDelta {
+    pub field: String,
+    pub before: f64,
+    pub after: f64,
+    pub kind: FieldKind,
+}
+
+impl FieldDelta {
+    pub fn change(&self) -> f64 {
+        self.after - self.before
+    }
+
+    /// A regression is a decrease in a percentage field beyond
+    /// `threshold_percent` points of tolerance (0.0 means any decrease at
+    /// all is a regression). Count fields never regress here: shrinking
+    /// `total_functions` by deleting or refactoring code is not a coverage
+    /// regression, and comparing a raw-count delta against a
+    /// percentage-point threshold is a unit mismatch.
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.kind == FieldKind::Percent && self.change() < -threshold_percent
+    }
+}
+
+/// Per-field and per-label deltas between `baseline` and `current`.
+pub struct MetricsDiff {
+    pub fields: Vec<FieldDelta>,
+    pub label_deltas: BTreeMap<String, FieldDelta>,
+}
+
+impl MetricsDiff {
+    pub fn compute(baseline: &CoverageMetrics, current: &CoverageMetrics) -> Self {
+        let fields = vec![
+            FieldDelta {
+                field: "total_functions".to_string(),
+                before: baseline.total_functions as f64,
+                after: current.total_functions as f64,
+                kind: FieldKind::Count,
+            },
+            FieldDelta {
+                field: "matched_functions".to_string(),
+                before: baseline.matched_functions as f64,
+                after: current.matched_functions as f64,
+                kind: FieldKind::Count,
+            },
+            FieldDelta {
+                field: "specified_functions".to_string(),
+                before: baseline.specified_functions as f64,
+                after: current.specified_functions as f64,
+                kind: FieldKind::Count,
+            },
+            FieldDelta {
+                field: "labeled_functions".to_string(),
+                before: baseline.labeled_functions as f64,
+                after: current.labeled_functions as f64,
+                kind: FieldKind::Count,
+            },
+            FieldDelta {
+                field: "specified_coverage_percent".to_string(),
+                before: baseline.specified_coverage_percent,
+                after: current.specified_coverage_percent,
+                kind: FieldKind::Percent,
+            },
+            FieldDelta {
+                field: "overall_coverage_percent".to_string(),
+                before: baseline.overall_coverage_percent,
+                after: current.overall_coverage_percent,
+                kind: FieldKind::Percent,
+            },
+        ];
+
+        let mut labels: Vec<&String> = baseline
+            .label_counts
+            .keys()
+            .chain(current.label_counts.keys())
+            .collect();
+        labels.sort();
+        labels.dedup();
+
+        let label_deltas = labels
+            .into_iter()
+            .map(|label| {
+                let before = *baseline.label_counts.get(label).unwrap_or(&0) as f64;
+                let after = *current.label_counts.get(label).unwrap_or(&0) as f64;
+                (
+                    label.clone(),
+                    FieldDelta {
+                        field: label.clone(),
+                        before,
+                        after,
+                        kind: FieldKind::Count,
+                    },
+                )
+            })
+            .collect();
+
+        MetricsDiff {
+            fields,
+            label_deltas,
+        }
+    }
+
+    /// Whether any percentage field regressed beyond `threshold_percent`.
+    /// Count fields (`total_functions`, per-label counts, etc.) are
+    /// reported in the diff but never gate CI -- a percentage-point
+    /// threshold doesn't apply to them, and a smaller function count from
+    /// deleted or refactored code isn't a coverage regression.
+    pub fn has_regression(&self, threshold_percent: f64) -> bool {
+        self.fields
+            .iter()
+            .chain(self.label_deltas.values())
+            .any(|delta| delta.is_regression(threshold_percent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(specified: usize, labeled: usize) -> CoverageMetrics {
+        let mut label_counts = BTreeMap::new();
+        label_counts.insert("pure".to_string(), labeled);
+        CoverageMetrics::compute(
+            "run".to_string(),
+            specified + 1,
+            0,
+            specified,
+            labeled,
+            labeled,
+            0,
+            label_counts,
+        )
+    }
+
+    #[test]
+    fn compute_derives_coverage_percentages() {
+        let m = metrics(10, 5);
+        assert_eq!(m.specified_coverage_percent, 50.0);
+    }
+
+    #[test]
+    fn diff_flags_a_drop_in_labeled_coverage_as_a_regression() {
+        let baseline = metrics(10, 8);
+        let current = metrics(10, 5);
+        let diff = MetricsDiff::compute(&baseline, &current);
+        assert!(diff.has_regression(0.0));
+    }
+
+    #[test]
+    fn diff_tolerates_a_drop_within_threshold() {
+        let baseline = metrics(10, 8);
+        let current = metrics(10, 7);
+        let diff = MetricsDiff::compute(&baseline, &current);
+        assert!(!diff.has_regression(20.0));
+    }
+
+    #[test]
+    fn diff_does_not_flag_an_improvement() {
+        let baseline = metrics(10, 5);
+        let current = metrics(10, 8);
+        let diff = MetricsDiff::compute(&baseline, &current);
+        assert!(!diff.has_regression(0.0));
+    }
+
+    #[test]
+    fn diff_does_not_flag_a_shrinking_function_count_as_a_regression() {
+        // Deleting or refactoring code can drop total/matched/labeled
+        // counts outright while coverage percentages hold steady or
+        // improve; that's not a regression.
+        let baseline = metrics(10, 5);
+        let current = metrics(5, 3);
+        let diff = MetricsDiff::compute(&baseline, &current);
+        assert!(!diff.has_regression(0.0));
+    }
+
+    #[test]
+    fn history_round_trips_through_a_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "{}").unwrap();
+        let m = metrics(10, 5);
+        append_to_history(file.path(), &m).unwrap();
+        let history = load_history(file.path()).unwrap();
+        assert_eq!(history.get("run").unwrap().specified_functions, 10);
+    }
+}
@@ -1,10 +1,178 @@
 //! Stubify command - Convert .md files with YAML frontmatter to JSON.
 
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 use walkdir::WalkDir;
 
+/// Filename searched for upward from the working directory, following the
+/// `md-docs.config.yaml` pattern: a single YAML file holding `input`/
+/// `output` (and a few optional extras) so `stubify`/`destubify` can run
+/// with no positional arguments at all.
+pub const STUBIFY_CONFIG_FILE_NAME: &str = "scip-atoms.config.yaml";
+
+/// On-disk config for `stubify`/`destubify`, loaded from
+/// [`STUBIFY_CONFIG_FILE_NAME`]. CLI flags, when given, override the
+/// corresponding field here.
+#[derive(Debug, Deserialize)]
+pub struct StubifyConfig {
+    pub input: String,
+    pub output: String,
+    /// Glob patterns (matched against each file's path relative to `input`)
+    /// restricting which `.md` files get stubified. Absent means "every
+    /// `.md` file under the tree", the pre-existing behavior.
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Glob patterns excluded even if matched by `include` (or, with no
+    /// `include`, excluded from the full tree).
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    #[serde(default)]
+    pub follow_links: Option<bool>,
+}
+
+/// Search `start_dir` and its ancestors for [`STUBIFY_CONFIG_FILE_NAME`].
+pub fn find_stubify_config(start_dir: &Path) -> Option<PathBuf> {
+    let mut dir = Some(start_dir);
+    while let Some(d) = dir {
+        let candidate = d.join(STUBIFY_CONFIG_FILE_NAME);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        dir = d.parent();
+    }
+    None
+}
+
+/// Load and parse [`STUBIFY_CONFIG_FILE_NAME`], searched upward from
+/// `start_dir`. Returns `None` if no config file is found; a config file
+/// that fails to parse is reported to stderr and also treated as absent,
+/// so a typo'd config doesn't stop a command that was given explicit CLI
+/// arguments from running.
+pub fn load_stubify_config(start_dir: &Path) -> Option<StubifyConfig> {
+    let path = find_stubify_config(start_dir)?;
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| eprintln!("Warning: could not read {}: {}", path.display(), e))
+        .ok()?;
+    serde_yaml::from_str(&content)
+        .map_err(|e| eprintln!("Warning: could not parse {}: {}", path.display(), e))
+        .ok()
+}
+
+/// Does `relative_path` pass the config's `include`/`exclude` glob filters?
+/// `include` absent means everything passes by default; `exclude` is
+/// applied afterward and always wins over `include`.
+fn passes_glob_filters(
+    relative_path: &str,
+    include: Option<&[String]>,
+    exclude: Option<&[String]>,
+) -> bool {
+    let matches_any = |patterns: &[String]| {
+        patterns.iter().any(|pattern| {
+            glob::Pattern::new(pattern)
+                .map(|p| p.matches(relative_path))
+                .unwrap_or(false)
+        })
+    };
+
+    if let Some(include) = include {
+        if !matches_any(include) {
+            return false;
+        }
+    }
+    if let Some(exclude) = exclude {
+        if matches_any(exclude) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Output shape for `cmd_stubify`.
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StubOutputFormat {
+    /// `HashMap<relative_path, StubFrontmatter>`, the original shape.
+    Flat,
+    /// A [`StubNode`] tree mirroring the input directory hierarchy.
+    Tree,
+}
+
+/// One node of the `--output-format tree` output: either an interior
+/// directory node with `children`, or a leaf node carrying the `.md`
+/// file's parsed frontmatter -- matching the `children`-based YAML indexes
+/// doc-tree generators use, so a sidebar/navigation tool can walk the JSON
+/// directly as a hierarchy instead of re-deriving it from flat paths.
+#[derive(Debug, Serialize)]
+pub struct StubNode {
+    /// Path relative to the stubified root.
+    pub path: String,
+    /// This node's own file/directory name (the last path component).
+    pub name: String,
+    /// The parsed frontmatter, present only on leaf (file) nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frontmatter: Option<StubFrontmatter>,
+    /// Child nodes, present only on interior (directory) nodes.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<StubNode>,
+}
+
+/// Build a [`StubNode`] tree from the flat `relative_path -> StubFrontmatter`
+/// map `cmd_stubify` collects, creating interior directory nodes on demand
+/// as each leaf's path components are walked. Taking a `BTreeMap` (rather
+/// than a `HashMap`) means `stubs` is walked in key order, so sibling
+/// `children` come out in a deterministic order regardless of which order
+/// the parallel parse pass happened to finish in.
+fn build_stub_tree(stubs: BTreeMap<String, StubFrontmatter>) -> StubNode {
+    let mut root = StubNode {
+        path: String::new(),
+        name: String::new(),
+        frontmatter: None,
+        children: Vec::new(),
+    };
+
+    for (relative_path, frontmatter) in stubs {
+        let components: Vec<&str> = relative_path.split('/').collect();
+        let mut node = &mut root;
+        let mut built_path = String::new();
+
+        for (i, component) in components.iter().enumerate() {
+            if !built_path.is_empty() {
+                built_path.push('/');
+            }
+            built_path.push_str(component);
+
+            let is_leaf = i == components.len() - 1;
+            let index = match node
+                .children
+                .iter()
+                .position(|child| child.name == *component)
+            {
+                Some(index) => index,
+                None => {
+                    node.children.push(StubNode {
+                        path: built_path.clone(),
+                        name: component.to_string(),
+                        frontmatter: None,
+                        children: Vec::new(),
+                    });
+                    node.children.len() - 1
+                }
+            };
+            node = &mut node.children[index];
+
+            if is_leaf {
+                node.frontmatter = Some(frontmatter);
+                break;
+            }
+        }
+    }
+
+    root
+}
+
 /// YAML frontmatter structure from stub .md files.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct StubFrontmatter {
@@ -14,14 +182,58 @@ pub struct StubFrontmatter {
     pub code_path: String,
     #[serde(rename = "code-name")]
     pub code_name: String,
+    /// Any frontmatter keys beyond the three above, preserved rather than
+    /// dropped, so stub authors can carry extra metadata through the JSON
+    /// round trip.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_yaml::Value>,
+    /// The markdown body below the closing `---`, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
 }
 
 /// Execute the stubify command.
 ///
 /// Walks through a directory hierarchy of .md files with YAML frontmatter
 /// and converts them to a JSON file where keys are file paths and values
-/// are the frontmatter fields.
-pub fn cmd_stubify(path: PathBuf, output: PathBuf) {
+/// are the frontmatter fields, any extra frontmatter keys, and the markdown
+/// body below the closing `---`.
+///
+/// `path`/`output` are optional: when either is omitted, it's filled in
+/// from [`STUBIFY_CONFIG_FILE_NAME`] (searched upward from the current
+/// directory), along with that config's `include`/`exclude` glob filters
+/// and `follow_links` setting. An explicit CLI argument always overrides
+/// the config file's value for that field.
+///
+/// `format` selects between the original flat `HashMap<path,
+/// StubFrontmatter>` JSON and a [`StubNode`] tree mirroring the input
+/// directory hierarchy.
+pub fn cmd_stubify(path: Option<PathBuf>, output: Option<PathBuf>, format: StubOutputFormat) {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let config = load_stubify_config(&cwd);
+
+    let path = path
+        .or_else(|| config.as_ref().map(|c| PathBuf::from(&c.input)))
+        .unwrap_or_else(|| {
+            eprintln!(
+                "Error: no input directory given and no {} found",
+                STUBIFY_CONFIG_FILE_NAME
+            );
+            std::process::exit(1);
+        });
+    let output = output
+        .or_else(|| config.as_ref().map(|c| PathBuf::from(&c.output)))
+        .unwrap_or_else(|| {
+            eprintln!(
+                "Error: no output file given and no {} found",
+                STUBIFY_CONFIG_FILE_NAME
+            );
+            std::process::exit(1);
+        });
+    let follow_links = config.as_ref().and_then(|c| c.follow_links).unwrap_or(true);
+    let include = config.as_ref().and_then(|c| c.include.as_deref());
+    let exclude = config.as_ref().and_then(|c| c.exclude.as_deref());
+
     // Validate input path
     if !path.exists() {
         eprintln!("Error: Path does not exist: {}", path.display());
@@ -33,52 +245,69 @@ pub fn cmd_stubify(path: PathBuf, output: PathBuf) {
         std::process::exit(1);
     }
 
-    // Walk directory and collect .md files
-    let mut stubs: HashMap<String, StubFrontmatter> = HashMap::new();
-    let mut processed = 0;
-    let mut errors = 0;
-
-    for entry in WalkDir::new(&path)
-        .follow_links(true)
+    // Collect candidate .md files first so the read-and-parse pass below
+    // can run in parallel; WalkDir's own iterator isn't `Send`.
+    let md_paths: Vec<PathBuf> = WalkDir::new(&path)
+        .follow_links(follow_links)
         .into_iter()
         .filter_map(|e| e.ok())
-    {
-        let entry_path = entry.path();
+        .map(|e| e.into_path())
+        .filter(|entry_path| {
+            entry_path.is_file() && entry_path.extension().and_then(|e| e.to_str()) == Some("md")
+        })
+        .collect();
 
-        // Only process .md files
-        if !entry_path.is_file() {
-            continue;
-        }
-        if entry_path.extension().and_then(|e| e.to_str()) != Some("md") {
-            continue;
-        }
+    let stubs: Mutex<HashMap<String, StubFrontmatter>> = Mutex::new(HashMap::new());
+    let processed = AtomicUsize::new(0);
+    let errors = AtomicUsize::new(0);
 
+    md_paths.par_iter().for_each(|entry_path| {
         // Get relative path from input directory
         let relative_path = match entry_path.strip_prefix(&path) {
             Ok(p) => p.to_string_lossy().to_string(),
             Err(_) => entry_path.to_string_lossy().to_string(),
         };
 
+        if !passes_glob_filters(&relative_path, include, exclude) {
+            return;
+        }
+
         // Read and parse the file
         match parse_frontmatter(entry_path) {
             Ok(frontmatter) => {
-                stubs.insert(relative_path, frontmatter);
-                processed += 1;
+                stubs.lock().unwrap().insert(relative_path, frontmatter);
+                processed.fetch_add(1, Ordering::Relaxed);
             }
             Err(e) => {
                 eprintln!("Warning: Failed to parse {}: {}", entry_path.display(), e);
-                errors += 1;
+                errors.fetch_add(1, Ordering::Relaxed);
             }
         }
-    }
+    });
+
+    let processed = processed.load(Ordering::Relaxed);
+    let errors = errors.load(Ordering::Relaxed);
 
     if processed == 0 {
         eprintln!("Error: No .md files found in {}", path.display());
         std::process::exit(1);
     }
 
+    // Sort into a BTreeMap so the JSON output (flat or tree) is
+    // deterministic regardless of the order the parallel parse pass
+    // happened to finish in.
+    let stubs: BTreeMap<String, StubFrontmatter> = stubs.into_inner().unwrap().into_iter().collect();
+
     // Write JSON output
-    let json = serde_json::to_string_pretty(&stubs).expect("Failed to serialize JSON");
+    let json = match format {
+        StubOutputFormat::Flat => {
+            serde_json::to_string_pretty(&stubs).expect("Failed to serialize JSON")
+        }
+        StubOutputFormat::Tree => {
+            let tree = build_stub_tree(stubs);
+            serde_json::to_string_pretty(&tree).expect("Failed to serialize JSON")
+        }
+    };
     std::fs::write(&output, &json).expect("Failed to write output file");
 
     println!(
@@ -89,33 +318,270 @@ pub fn cmd_stubify(path: PathBuf, output: PathBuf) {
     );
 }
 
-/// Parse YAML frontmatter from a markdown file.
+/// Execute the destubify command: the inverse of [`cmd_stubify`].
 ///
-/// Expects files in the format:
-/// ```
-/// ---
-/// code-line: 123
-/// code-path: path/to/file.rs
-/// code-name: scip:...
-/// ---
-/// ```
-fn parse_frontmatter(path: &std::path::Path) -> Result<StubFrontmatter, String> {
-    let content =
-        std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+/// Reads the JSON index `cmd_stubify` produces and, for each `path ->
+/// StubFrontmatter` entry, writes a `.md` file at `output_dir/path` whose
+/// header is the serialized YAML frontmatter delimited by `---` lines
+/// (modeled on the Jekyll-style frontmatter block [`parse_frontmatter`]
+/// reads back), followed by the stub's stored `body`, if any.
+pub fn cmd_destubify(json: PathBuf, output_dir: PathBuf) {
+    let content = match std::fs::read_to_string(&json) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Error: Failed to read {}: {}", json.display(), e);
+            std::process::exit(1);
+        }
+    };
+
+    let stubs: HashMap<String, StubFrontmatter> = match serde_json::from_str(&content) {
+        Ok(stubs) => stubs,
+        Err(e) => {
+            eprintln!("Error: Failed to parse {}: {}", json.display(), e);
+            std::process::exit(1);
+        }
+    };
 
-    // Check for frontmatter delimiters
-    if !content.starts_with("---") {
-        return Err("File does not start with YAML frontmatter".to_string());
+    let mut written = 0;
+    let mut errors = 0;
+
+    for (relative_path, frontmatter) in &stubs {
+        match write_stub(&output_dir, relative_path, frontmatter) {
+            Ok(()) => written += 1,
+            Err(e) => {
+                eprintln!("Warning: Failed to write {}: {}", relative_path, e);
+                errors += 1;
+            }
+        }
+    }
+
+    println!(
+        "Wrote {} stubs to {} ({} errors)",
+        written,
+        output_dir.display(),
+        errors
+    );
+}
+
+/// Render and write a single stub's `.md` file at `output_dir/relative_path`.
+fn write_stub(
+    output_dir: &std::path::Path,
+    relative_path: &str,
+    frontmatter: &StubFrontmatter,
+) -> Result<(), String> {
+    // `body` is rendered separately below the closing delimiter, not as a
+    // YAML key, so strip it from the serialized frontmatter block.
+    let mut value = serde_yaml::to_value(frontmatter)
+        .map_err(|e| format!("Failed to serialize YAML: {}", e))?;
+    if let serde_yaml::Value::Mapping(ref mut map) = value {
+        map.remove("body");
+    }
+    let yaml =
+        serde_yaml::to_string(&value).map_err(|e| format!("Failed to serialize YAML: {}", e))?;
+    let body = frontmatter.body.as_deref().unwrap_or("");
+    let rendered = format!("---\n{}---\n{}", yaml, body);
+
+    let out_path = output_dir.join(relative_path);
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    std::fs::write(&out_path, rendered).map_err(|e| format!("Failed to write file: {}", e))
+}
+
+/// Errors [`parse_frontmatter`] can return, distinguishing a missing fence
+/// from a present-but-unparsable one so callers (and tests) can match on
+/// the failure mode rather than grep a message string.
+#[derive(Debug, thiserror::Error)]
+pub enum FrontmatterError {
+    #[error("could not read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("file does not start with a recognized frontmatter fence (---, +++, {{, or ;;;)")]
+    MissingOpenFence,
+    #[error("no matching closing `{fence}` fence found for the opening frontmatter")]
+    MissingCloseFence { fence: &'static str },
+    #[error("failed to parse {format} frontmatter: {message}")]
+    ParseError { format: &'static str, message: String },
+}
+
+/// The recognized opening fences and which format they dispatch to.
+enum FenceKind {
+    /// `---` ... `---`, parsed as YAML.
+    Yaml,
+    /// `+++` ... `+++`, parsed as TOML.
+    Toml,
+    /// `;;;` ... `;;;`, with a JSON object (including its own braces)
+    /// between the fences.
+    JsonDelimited,
+    /// A bare `{` opening line through the matching bare `}` line, the
+    /// JSON object's own braces serving as the fence.
+    JsonBraced,
+}
+
+impl FenceKind {
+    fn from_opening_line(line: &str) -> Option<FenceKind> {
+        match line.trim() {
+            "---" => Some(FenceKind::Yaml),
+            "+++" => Some(FenceKind::Toml),
+            ";;;" => Some(FenceKind::JsonDelimited),
+            "{" => Some(FenceKind::JsonBraced),
+            _ => None,
+        }
+    }
+
+    fn close_marker(&self) -> &'static str {
+        match self {
+            FenceKind::Yaml => "---",
+            FenceKind::Toml => "+++",
+            FenceKind::JsonDelimited => ";;;",
+            FenceKind::JsonBraced => "}",
+        }
+    }
+
+    fn format_name(&self) -> &'static str {
+        match self {
+            FenceKind::Yaml => "YAML",
+            FenceKind::Toml => "TOML",
+            FenceKind::JsonDelimited | FenceKind::JsonBraced => "JSON",
+        }
+    }
+
+    fn parse(&self, body: &str) -> Result<StubFrontmatter, String> {
+        match self {
+            FenceKind::Yaml => serde_yaml::from_str(body).map_err(|e| e.to_string()),
+            FenceKind::Toml => toml::from_str(body).map_err(|e| e.to_string()),
+            FenceKind::JsonDelimited | FenceKind::JsonBraced => {
+                serde_json::from_str(body).map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+/// Find the byte offset of the `}` matching the `{` at `open_byte`,
+/// tracking brace depth and skipping over braces inside JSON string
+/// literals (respecting `\"` escapes) so a nested object like
+/// `{"meta": {"a": 1}}` doesn't close early on its inner `}`.
+fn find_matching_brace(content: &str, open_byte: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, &b) in bytes.iter().enumerate().skip(open_byte) {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
     }
+    None
+}
 
-    // Find the closing delimiter
-    let rest = &content[3..];
-    let end_pos = rest
-        .find("\n---")
-        .ok_or_else(|| "No closing frontmatter delimiter found".to_string())?;
+/// Split `content` into `(line, byte_offset_of_line_start)` pairs, with
+/// the line's own `\n`/`\r\n` terminator stripped -- unlike [`str::lines`],
+/// this keeps each line's byte offset in the original string so a brace
+/// or fence found on a given line can be mapped back to a byte position.
+fn line_starts(content: &str) -> Vec<(&str, usize)> {
+    let mut result = Vec::new();
+    let mut offset = 0;
+    for raw_line in content.split_inclusive('\n') {
+        let line = raw_line
+            .strip_suffix('\n')
+            .and_then(|l| l.strip_suffix('\r').or(Some(l)))
+            .unwrap_or(raw_line);
+        result.push((line, offset));
+        offset += raw_line.len();
+    }
+    result
+}
 
-    // Extract and parse the YAML
-    let yaml_content = &rest[..end_pos].trim();
+/// Parse frontmatter and the trailing body from a markdown file, scanning
+/// line-by-line rather than assuming a bare `content.starts_with("---")`:
+/// a leading UTF-8 BOM is stripped, `\r\n` line endings are handled the
+/// same as `\n`, and the opening fence can be `---` (YAML), `+++` (TOML),
+/// `;;;` (JSON between delimiters), or a bare `{` (JSON, the object's own
+/// braces as the fence) -- matching the heterogeneous conventions real doc
+/// trees use. The closing fence must appear on its own line for the
+/// `---`/`+++`/`;;;` delimiters; for the bare-`{` form the close is instead
+/// the depth-matched `}` found by [`find_matching_brace`], since a plain
+/// "first standalone `}` line" test would close early on any nested object
+/// in the frontmatter. Everything after the close is kept as the body
+/// (mirroring the `Document { metadata, content }` split `yaml-front-matter`
+/// uses), and any frontmatter keys beyond `code-line`/`code-path`/
+/// `code-name` land in [`StubFrontmatter::extra`] instead of failing to
+/// deserialize.
+fn parse_frontmatter(path: &std::path::Path) -> Result<StubFrontmatter, FrontmatterError> {
+    let content =
+        std::fs::read_to_string(path).map_err(|source| FrontmatterError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
+
+    let lines = line_starts(content);
+    let open_idx = lines
+        .iter()
+        .position(|(line, _)| !line.trim().is_empty())
+        .ok_or(FrontmatterError::MissingOpenFence)?;
+    let (open_line, open_line_start) = lines[open_idx];
+    let fence = FenceKind::from_opening_line(open_line).ok_or(FrontmatterError::MissingOpenFence)?;
+
+    let (frontmatter_text, body_start) = if let FenceKind::JsonBraced = fence {
+        let open_byte = open_line_start + open_line.find('{').expect("fence is a bare '{'");
+        let close_byte = find_matching_brace(content, open_byte)
+            .ok_or(FrontmatterError::MissingCloseFence { fence: "}" })?;
+        (content[open_byte..=close_byte].to_string(), close_byte + 1)
+    } else {
+        let close_marker = fence.close_marker();
+        let close_idx = lines[open_idx + 1..]
+            .iter()
+            .position(|(line, _)| line.trim() == close_marker)
+            .map(|offset| open_idx + 1 + offset)
+            .ok_or(FrontmatterError::MissingCloseFence { fence: close_marker })?;
+        let (close_line, close_line_start) = lines[close_idx];
+        let text = lines[open_idx + 1..close_idx]
+            .iter()
+            .map(|(line, _)| *line)
+            .collect::<Vec<_>>()
+            .join("\n");
+        (text, close_line_start + close_line.len())
+    };
+
+    let mut frontmatter: StubFrontmatter =
+        fence
+            .parse(&frontmatter_text)
+            .map_err(|message| FrontmatterError::ParseError {
+                format: fence.format_name(),
+                message,
+            })?;
+
+    let after = &content[body_start.min(content.len())..];
+    let body = after
+        .strip_prefix("\r\n")
+        .or_else(|| after.strip_prefix('\n'))
+        .unwrap_or(after);
+    if !body.is_empty() {
+        frontmatter.body = Some(body.to_string());
+    }
 
-    serde_yaml::from_str(yaml_content).map_err(|e| format!("Failed to parse YAML: {}", e))
+    Ok(frontmatter)
 }
@@ -0,0 +1,118 @@
+//! In-source `//~ TRACKED` annotations for `cmd_tracked_csv --verify-annotations`.
+//!
+//! Analogous to `expect.rs`'s `//~ VERIFY-FAIL` / `//~ ASSUME` function
+//! annotations: a `//~ TRACKED expect-proof` or `//~ TRACKED no-spec`
+//! comment placed above a function asserts what `tracked-csv` should find
+//! when it classifies that function. This gives the crate an in-source
+//! contract for what the dashboard is expected to track, decoupled from
+//! the generated CSV.
+
+use probe_verus::verus_parser::FunctionInfo;
+use std::collections::HashMap;
+
+/// What a `//~ TRACKED` annotation asserts about the function declared
+/// next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackedExpectation {
+    /// The function should be tracked (specified, not external-body) and
+    /// fully proved.
+    ExpectProof,
+    /// The function should not be tracked at all -- no usable spec, or an
+    /// external-body trusted assumption.
+    NoSpec,
+}
+
+/// Where a function's actual tracked-csv state didn't match its `//~
+/// TRACKED` annotation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedMismatch {
+    pub function: String,
+    pub file: String,
+    pub line: usize,
+    pub expected: TrackedExpectation,
+    pub reason: String,
+}
+
+/// Scan `source` for `//~ TRACKED <kind>` lines, returning each
+/// annotation's 1-based source line.
+fn parse_tracked_annotations(source: &str) -> Vec<(usize, TrackedExpectation)> {
+    let mut expectations = Vec::new();
+
+    for (idx, line) in source.lines().enumerate() {
+        let Some(marker_pos) = line.find("//~") else {
+            continue;
+        };
+        let rest = line[marker_pos + 3..].trim_start();
+        let Some(rest) = rest.strip_prefix("TRACKED") else {
+            continue;
+        };
+
+        match rest.trim() {
+            "expect-proof" => expectations.push((idx + 1, TrackedExpectation::ExpectProof)),
+            "no-spec" => expectations.push((idx + 1, TrackedExpectation::NoSpec)),
+            _ => {}
+        }
+    }
+
+    expectations
+}
+
+/// Cross-check `//~ TRACKED` annotations in `source` against `functions`'
+/// actual `specified`/`is_proved()`/`is_external_body` state. Each
+/// annotation attaches to the nearest function declared below it, the same
+/// convention `expect::check_function_expectations` uses for `//~
+/// VERIFY-FAIL` / `//~ ASSUME`.
+pub fn check_tracked_annotations(source: &str, functions: &[&FunctionInfo]) -> Vec<TrackedMismatch> {
+    let annotations = parse_tracked_annotations(source);
+
+    let mut sorted_functions: Vec<&FunctionInfo> = functions.to_vec();
+    sorted_functions.sort_by_key(|f| f.spec_text.lines_start);
+
+    let mut expected_by_line: HashMap<usize, TrackedExpectation> = HashMap::new();
+    for (annotation_line, kind) in annotations {
+        if let Some(func) = sorted_functions
+            .iter()
+            .find(|f| f.spec_text.lines_start > annotation_line)
+        {
+            expected_by_line
+                .entry(func.spec_text.lines_start)
+                .or_insert(kind);
+        }
+    }
+
+    let mut mismatches = Vec::new();
+    for func in functions {
+        let line = func.spec_text.lines_start;
+        let Some(&expected) = expected_by_line.get(&line) else {
+            continue;
+        };
+
+        let reason = match expected {
+            TrackedExpectation::ExpectProof if !func.specified || func.is_external_body => {
+                Some("function is not tracked (no spec, or external body)".to_string())
+            }
+            TrackedExpectation::ExpectProof if !func.is_proved() => {
+                Some("function is tracked but not proved".to_string())
+            }
+            TrackedExpectation::NoSpec if func.specified && !func.is_external_body => {
+                Some("function is tracked but annotated no-spec".to_string())
+            }
+            _ => None,
+        };
+
+        if let Some(reason) = reason {
+            mismatches.push(TrackedMismatch {
+                function: func
+                    .display_name
+                    .clone()
+                    .unwrap_or_else(|| func.name.clone()),
+                file: func.file.clone().unwrap_or_default(),
+                line,
+                expected,
+                reason,
+            });
+        }
+    }
+
+    mismatches
+}
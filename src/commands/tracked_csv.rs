@@ -4,13 +4,83 @@
 //! It outputs a CSV with the same schema consumed by the dashboard scripts:
 //! `function,module,link,has_spec,has_proof`
 
+use super::tracked_annotations::check_tracked_annotations;
+use super::verus_json::{self, FunctionProofStatus};
 use probe_verus::verus_parser::{compute_project_prefix, parse_all_functions_ext};
 use probe_verus::FunctionMode;
-use std::io::Write;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// One function's entry in the `--json-out` structured report -- the same
+/// facts the CSV flattens into five string columns, kept in their native
+/// shape so dashboards don't have to re-parse a lossy CSV.
+#[derive(Debug, Serialize)]
+struct TrackedRecord {
+    function: String,
+    module_path: String,
+    file: String,
+    line: usize,
+    mode: String,
+    specified: bool,
+    is_external_body: bool,
+    is_proved: bool,
+    link: String,
+}
+
+/// Summary counts mirroring the stderr stats, included alongside `records`
+/// in the `--json-out` report.
+#[derive(Debug, Serialize)]
+struct TrackedSummary {
+    total: usize,
+    proved: usize,
+    proved_percent: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct TrackedReport {
+    summary: TrackedSummary,
+    records: Vec<TrackedRecord>,
+}
 
 /// Generate the tracked CSV file.
-pub fn cmd_tracked_csv(src_path: PathBuf, output: PathBuf, github_base_url: Option<String>) {
+///
+/// `check`, instead of writing, compares the freshly generated CSV against
+/// the committed `output` file (after normalizing away the volatile
+/// `#L<number>` line anchor in the `link` column on both sides) and exits
+/// nonzero if anything besides line numbers changed. `bless` regenerates
+/// and overwrites `output` unconditionally -- the same effect as running
+/// with neither flag, but explicit about the intent to accept new output.
+///
+/// `verus_json`, when given, points at the Verus verifier's own JSON
+/// report: each discovered function is joined against it by `file` +
+/// `lines_start` span, so `has_proof` reflects the verifier's ground truth
+/// (verified/failed/timeout) rather than the AST-only `is_proved()`
+/// heuristic. A function with no matching verifier result is reported as
+/// unverified; every function falls back to that heuristic when
+/// `verus_json` isn't supplied at all or can't be loaded.
+///
+/// `verify_annotations`, when set, skips CSV generation entirely and
+/// instead cross-checks every discovered function's in-source `//~
+/// TRACKED` annotation (see [`super::tracked_annotations`]) against its
+/// actual `specified`/`is_proved()`/`is_external_body` state, printing
+/// every mismatch and exiting nonzero if any are found.
+///
+/// `json_out`, when given, additionally writes the tracked set as a
+/// structured [`TrackedReport`] -- one record per function keeping its
+/// native `module_path`/`mode`/span/booleans, plus a summary object --
+/// alongside the flat CSV, for dashboard consumers that want more than
+/// five string columns.
+pub fn cmd_tracked_csv(
+    src_path: PathBuf,
+    output: PathBuf,
+    github_base_url: Option<String>,
+    check: bool,
+    bless: bool,
+    verus_json: Option<PathBuf>,
+    verify_annotations: bool,
+    json_out: Option<PathBuf>,
+) {
     let github_base = github_base_url.unwrap_or_default();
 
     eprintln!("Parsing source files from: {}", src_path.display());
@@ -29,10 +99,27 @@ pub fn cmd_tracked_csv(src_path: PathBuf, output: PathBuf, github_base_url: Opti
         parsed.summary.total_functions, parsed.summary.total_files
     );
 
+    if verify_annotations {
+        run_verify_annotations(&src_path, &parsed.functions);
+        return;
+    }
+
     let project_prefix = compute_project_prefix(&src_path);
 
+    let verus_results = verus_json.as_deref().and_then(|path| {
+        match verus_json::load_verus_json(path) {
+            Ok(results) => Some(results),
+            Err(e) => {
+                eprintln!("Warning: {e}; falling back to the AST heuristic for every function");
+                None
+            }
+        }
+    });
+
     // Collect rows: only exec/proof functions that have specs or external_body
     let mut rows: Vec<(String, String, String, String, String)> = Vec::new();
+    let mut records: Vec<TrackedRecord> = Vec::new();
+    let mut status_counts: HashMap<&'static str, usize> = HashMap::new();
 
     for func in &parsed.functions {
         // Only track exec-mode functions (the actual Rust implementations).
@@ -77,39 +164,58 @@ pub fn cmd_tracked_csv(src_path: PathBuf, output: PathBuf, github_base_url: Opti
         // All functions reaching here have specs (external_body already filtered out)
         let has_spec = "yes".to_string();
 
-        let has_proof = if func.is_proved() {
-            "yes".to_string()
-        } else {
-            String::new()
+        let status = match &verus_results {
+            Some(results) => results
+                .get(&(file.to_string(), line))
+                .copied()
+                .map(FunctionProofStatus::from)
+                .unwrap_or(FunctionProofStatus::Unverified),
+            None if func.is_proved() => FunctionProofStatus::Verified,
+            None => FunctionProofStatus::Unverified,
         };
+        *status_counts.entry(status.label()).or_insert(0) += 1;
+
+        records.push(TrackedRecord {
+            function: function_name.clone(),
+            module_path: module_path.to_string(),
+            file: file.to_string(),
+            line,
+            mode: format!("{:?}", func.mode).to_lowercase(),
+            specified: func.specified,
+            is_external_body: func.is_external_body,
+            is_proved: status.as_has_proof() == "yes",
+            link: link.clone(),
+        });
 
-        rows.push((function_name, module, link, has_spec, has_proof));
+        rows.push((
+            function_name,
+            module,
+            link,
+            has_spec,
+            status.as_has_proof().to_string(),
+        ));
     }
 
     // Sort by function name for deterministic output
     rows.sort_by(|a, b| a.0.cmp(&b.0));
+    records.sort_by(|a, b| a.function.cmp(&b.function));
 
-    // Write CSV
-    if let Some(parent) = output.parent() {
-        std::fs::create_dir_all(parent).ok();
-    }
-    let mut file = std::fs::File::create(&output).expect("Failed to create output file");
-    writeln!(file, "function,module,link,has_spec,has_proof").unwrap();
-    for (function, module, link, has_spec, has_proof) in &rows {
-        writeln!(
-            file,
-            "{},{},{},{},{}",
-            function, module, link, has_spec, has_proof
-        )
-        .unwrap();
+    let csv_content = render_csv(&rows);
+
+    if check {
+        check_against_baseline(&output, &csv_content);
+    } else {
+        write_csv(&output, &csv_content);
+        if bless {
+            eprintln!("Blessed: {}", output.display());
+        }
     }
 
     // Print summary
     let total = rows.len();
     let proof_count = rows.iter().filter(|r| r.4 == "yes").count();
 
-    eprintln!("\nCSV written: {}", output.display());
-    eprintln!("Summary:");
+    eprintln!("\nSummary:");
     eprintln!("  Exec functions with specs: {}", total);
     eprintln!(
         "  With complete proofs: {} ({}%)",
@@ -121,4 +227,233 @@ pub fn cmd_tracked_csv(src_path: PathBuf, output: PathBuf, github_base_url: Opti
         }
     );
     eprintln!("  Without complete proof: {}", total - proof_count);
+
+    if verus_results.is_some() {
+        eprintln!("  By verifier status:");
+        for label in ["verified", "failed", "timeout", "unverified"] {
+            eprintln!(
+                "    {label}: {}",
+                status_counts.get(label).copied().unwrap_or(0)
+            );
+        }
+    }
+
+    if let Some(json_path) = json_out {
+        let report = TrackedReport {
+            summary: TrackedSummary {
+                total,
+                proved: proof_count,
+                proved_percent: if total > 0 {
+                    (proof_count * 100 / total) as u32
+                } else {
+                    0
+                },
+            },
+            records,
+        };
+        let json = serde_json::to_string_pretty(&report).expect("Failed to serialize JSON report");
+        if let Some(parent) = json_path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        std::fs::write(&json_path, json).expect("Failed to write JSON report");
+        eprintln!("JSON report written: {}", json_path.display());
+    }
+}
+
+/// Cross-check every function's `//~ TRACKED` annotation against its
+/// actual tracked-csv state, grouped by source file so each file is only
+/// read once. Prints every mismatch and exits nonzero if any are found.
+fn run_verify_annotations(src_path: &Path, functions: &[probe_verus::verus_parser::FunctionInfo]) {
+    let mut by_file: HashMap<&str, Vec<&probe_verus::verus_parser::FunctionInfo>> = HashMap::new();
+    for func in functions {
+        if let Some(file) = func.file.as_deref() {
+            by_file.entry(file).or_default().push(func);
+        }
+    }
+
+    let mut files: Vec<&str> = by_file.keys().copied().collect();
+    files.sort();
+
+    let mut mismatches = Vec::new();
+    for file in files {
+        let path = src_path.join(file);
+        let source = match std::fs::read_to_string(&path) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Warning: failed to read {} for annotation check: {e}", path.display());
+                continue;
+            }
+        };
+        mismatches.extend(check_tracked_annotations(&source, &by_file[file]));
+    }
+
+    if mismatches.is_empty() {
+        eprintln!("tracked-csv: all //~ TRACKED annotations hold");
+        return;
+    }
+
+    eprintln!("tracked-csv: {} annotation mismatch(es):\n", mismatches.len());
+    for mismatch in &mismatches {
+        eprintln!(
+            "  {}:{}: {} ({:?}): {}",
+            mismatch.file, mismatch.line, mismatch.function, mismatch.expected, mismatch.reason
+        );
+    }
+    std::process::exit(1);
+}
+
+/// Render `rows` as the CSV text written to / compared against `output`.
+fn render_csv(rows: &[(String, String, String, String, String)]) -> String {
+    let mut csv = String::from("function,module,link,has_spec,has_proof\n");
+    for (function, module, link, has_spec, has_proof) in rows {
+        csv.push_str(&format!(
+            "{function},{module},{link},{has_spec},{has_proof}\n"
+        ));
+    }
+    csv
+}
+
+fn write_csv(output: &Path, content: &str) {
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    std::fs::write(output, content).expect("Failed to write output file");
+    eprintln!("CSV written: {}", output.display());
+}
+
+/// Compare `fresh` against the committed `path`, after normalizing away the
+/// volatile `#L<number>` line anchor on both sides (a function merely
+/// shifting down a few lines shouldn't register as a change -- only
+/// added/removed functions or flipped `has_proof` values should). Prints a
+/// unified-diff-style summary with a few lines of context and exits
+/// nonzero if anything remains different.
+fn check_against_baseline(path: &Path, fresh: &str) {
+    let committed = std::fs::read_to_string(path).unwrap_or_default();
+    let normalized_committed = normalize_line_anchors(&committed);
+    let normalized_fresh = normalize_line_anchors(fresh);
+
+    if normalized_committed == normalized_fresh {
+        eprintln!("tracked-csv: {} is up to date", path.display());
+        return;
+    }
+
+    eprintln!("tracked-csv: {} is out of date:\n", path.display());
+    let old_lines: Vec<&str> = normalized_committed.lines().collect();
+    let new_lines: Vec<&str> = normalized_fresh.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+    eprint!("{}", render_diff_with_context(&ops, 3));
+    eprintln!("(re-run with --bless to accept)");
+    std::process::exit(1);
+}
+
+/// Rewrite every `#L<number>` anchor in `csv` to `#L<N>`, so comparisons
+/// ignore line numbers shifting and only see real content changes.
+fn normalize_line_anchors(csv: &str) -> String {
+    let mut out = String::with_capacity(csv.len());
+    let mut rest = csv;
+    while let Some(idx) = rest.find("#L") {
+        out.push_str(&rest[..idx]);
+        out.push_str("#L<N>");
+        rest = &rest[idx + 2..];
+        let digit_end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        rest = &rest[digit_end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// One line of a rendered line-diff: unchanged, or only on one side.
+#[derive(Debug, Clone, Copy)]
+enum LineDiff<'a> {
+    Unchanged(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Longest-common-subsequence line diff between `old` and `new` -- small
+/// and self-contained rather than pulling in an external `diff` crate for
+/// one command. Quadratic in line count, which is fine for a tracked-CSV
+/// the size this command generates.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<LineDiff<'a>> {
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push(LineDiff::Unchanged(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(LineDiff::Removed(old[i]));
+            i += 1;
+        } else {
+            ops.push(LineDiff::Added(new[j]));
+            j += 1;
+        }
+    }
+    while i < n {
+        ops.push(LineDiff::Removed(old[i]));
+        i += 1;
+    }
+    while j < m {
+        ops.push(LineDiff::Added(new[j]));
+        j += 1;
+    }
+    ops
+}
+
+/// Render `ops` unified-diff style, keeping `context` lines of unchanged
+/// text around each run of changes; separate runs further apart than
+/// `2 * context` are shown as separate hunks split by a `...` marker.
+fn render_diff_with_context(ops: &[LineDiff], context: usize) -> String {
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, LineDiff::Unchanged(_)))
+        .map(|(i, _)| i)
+        .collect();
+    let Some(&first) = changed.first() else {
+        return String::new();
+    };
+
+    let mut out = String::new();
+    let mut hunk_start = first.saturating_sub(context);
+    let mut hunk_end = (first + context + 1).min(ops.len());
+
+    for &idx in &changed[1..] {
+        let next_start = idx.saturating_sub(context);
+        if next_start <= hunk_end {
+            hunk_end = (idx + context + 1).min(ops.len());
+        } else {
+            push_diff_hunk(ops, hunk_start, hunk_end, &mut out);
+            out.push_str("...\n");
+            hunk_start = next_start;
+            hunk_end = (idx + context + 1).min(ops.len());
+        }
+    }
+    push_diff_hunk(ops, hunk_start, hunk_end, &mut out);
+    out
+}
+
+fn push_diff_hunk(ops: &[LineDiff], start: usize, end: usize, out: &mut String) {
+    for op in &ops[start..end] {
+        match op {
+            LineDiff::Unchanged(l) => out.push_str(&format!("  {l}\n")),
+            LineDiff::Removed(l) => out.push_str(&format!("- {l}\n")),
+            LineDiff::Added(l) => out.push_str(&format!("+ {l}\n")),
+        }
+    }
 }
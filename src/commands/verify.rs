@@ -1,11 +1,18 @@
 //! Verify command - Run Verus verification and analyze results.
 
-use probe_verus::constants::{DATA_DIR, VERIFICATION_CONFIG_FILE, VERIFICATION_OUTPUT_FILE};
+use super::baseline;
+use probe_verus::constants::{
+    DATA_DIR, VERIFICATION_BASELINE_FILE, VERIFICATION_CONFIG_FILE, VERIFICATION_OUTPUT_FILE,
+};
+use probe_verus::error::{ProbeError, ProbeResult};
 use probe_verus::verification::{
-    enrich_with_code_names, AnalysisResult, AnalysisStatus, VerificationAnalyzer, VerusRunner,
+    enrich_with_code_names, AnalysisResult, AnalysisStatus, CompilationErrorParser,
+    FunctionLocation, VerificationAnalyzer, VerusRunner,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 /// Cached verification configuration.
 #[derive(Serialize, Deserialize)]
@@ -25,10 +32,120 @@ fn cache_config_file() -> String {
     format!("{}/{}", DATA_DIR, VERIFICATION_CONFIG_FILE)
 }
 
+/// Default abbreviation budget for `--verbose` terminal output, distinct
+/// from the (opt-in, user-controlled) `--max-output-bytes` cap applied to
+/// the cached file on disk.
+const DEFAULT_PRINTED_OUTPUT_BYTES: usize = 64 * 1024;
+
+/// Whether [`abbreviate_output`] had to drop anything to stay under budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbbreviationOutcome {
+    Untruncated,
+    Truncated,
+}
+
+/// Abbreviate `text` to about `max_bytes`, ported from the rustc test
+/// harness's `read2_abbreviated` approach: keep the first half and the last
+/// half of that budget, with the middle replaced by a `<N bytes omitted>`
+/// marker (so the result is a few bytes longer than `max_bytes`), so a
+/// caller can see both how the run started and how it ended without paying
+/// for the whole blob.
+///
+/// Unlike [`probe_verus::verification`]'s streaming `AbbreviatedCapture`
+/// (which bounds memory use while a process is still running), this works
+/// on a string already fully in memory -- the cached file and the verbose
+/// terminal dump both abbreviate *after* the full output has been captured
+/// and analyzed.
+///
+/// Truncation points are snapped to UTF-8 char boundaries so a multi-byte
+/// character is never split, and if the text's last line starts with
+/// `verification results::`, that line is always kept whole in the tail
+/// even if it would otherwise fall inside the omitted middle.
+pub fn abbreviate_output(text: &str, max_bytes: usize) -> (String, AbbreviationOutcome) {
+    if text.len() <= max_bytes {
+        return (text.to_string(), AbbreviationOutcome::Untruncated);
+    }
+
+    let results_line_start = text
+        .lines()
+        .last()
+        .filter(|line| line.trim_start().starts_with("verification results::"))
+        .map(|line| text.len() - line.len());
+
+    let head_budget = max_bytes / 2;
+    let tail_budget = max_bytes - head_budget;
+
+    let head_end = floor_char_boundary(text, head_budget);
+    let mut tail_start = ceil_char_boundary(text, text.len().saturating_sub(tail_budget));
+    if let Some(line_start) = results_line_start {
+        tail_start = tail_start.min(line_start);
+    }
+    let tail_start = tail_start.max(head_end);
+
+    let omitted = tail_start - head_end;
+    let abbreviated = format!(
+        "{}\n<{} bytes omitted>\n{}",
+        &text[..head_end],
+        omitted,
+        &text[tail_start..]
+    );
+    (abbreviated, AbbreviationOutcome::Truncated)
+}
+
+/// The largest byte index `<= index` that lies on a UTF-8 char boundary.
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    if index >= text.len() {
+        return text.len();
+    }
+    let mut idx = index;
+    while idx > 0 && !text.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+/// The smallest byte index `>= index` that lies on a UTF-8 char boundary.
+fn ceil_char_boundary(text: &str, index: usize) -> usize {
+    let mut idx = index.min(text.len());
+    while idx < text.len() && !text.is_char_boundary(idx) {
+        idx += 1;
+    }
+    idx
+}
+
 /// Execute the verify command.
 ///
 /// Runs Verus verification on a project and analyzes results.
 /// Supports caching for quick re-analysis.
+///
+/// When `project_path` is given (a fresh run, not `--from-file` or a cached
+/// replay), `verify_only_module`/`verify_function` aren't set, `with_atoms`
+/// resolves to an existing `atoms.json`, and `no_incremental` is false,
+/// verification runs per-function against the content-hash cache in
+/// [`super::cache::VerificationCache`] instead of one monolithic pass,
+/// skipping any function whose hash still matches a cached `Verified`
+/// result. Anything that rules the incremental path out falls back to the
+/// original whole-project run.
+///
+/// Returns the resulting [`AnalysisStatus`] rather than exiting the process
+/// directly, so a thin CLI wrapper can decide the exit code -- via
+/// [`ProbeError::exit_code`] on `Err`, or its own mapping from
+/// `AnalysisStatus` on `Ok`. When `error_format_json` is set, an `Err` is
+/// also printed to stderr as [`ProbeError::to_json`] before being returned,
+/// for `--error-format=json` callers.
+///
+/// `baseline_path` defaults to `data/verification_baseline.json` and is
+/// compared function-by-function against the fresh results: any function
+/// that went verified in the baseline to failed/unverified now is a
+/// regression, reported via [`ProbeError::Verification`] so the run exits
+/// non-zero. When `bless` is set, the baseline comparison is skipped and
+/// the fresh results instead overwrite the baseline file.
+///
+/// When `json_diagnostics` is set, Verus is asked for structured JSON
+/// diagnostics (`--message-format=json` / `--output-json`) instead of
+/// rendered text, giving [`VerificationAnalyzer::analyze_output`] exact
+/// file+line+column on every failure instead of falling back to its text
+/// scraper.
 #[allow(clippy::too_many_arguments)]
 pub fn cmd_verify(
     project_path: Option<PathBuf>,
@@ -39,8 +156,95 @@ pub fn cmd_verify(
     verify_function: Option<String>,
     output: Option<PathBuf>,
     no_cache: bool,
+    max_output_bytes: Option<usize>,
     with_atoms: Option<Option<PathBuf>>,
-) {
+    no_incremental: bool,
+    json_diagnostics: bool,
+    baseline_path: Option<PathBuf>,
+    bless: bool,
+    error_format_json: bool,
+) -> ProbeResult<AnalysisStatus> {
+    let result = run_cmd_verify(
+        project_path,
+        from_file,
+        exit_code_arg,
+        package,
+        verify_only_module,
+        verify_function,
+        output,
+        no_cache,
+        max_output_bytes,
+        with_atoms,
+        no_incremental,
+        json_diagnostics,
+        baseline_path,
+        bless,
+    );
+    if let Err(ref e) = result {
+        if error_format_json {
+            eprintln!("{}", e.to_json());
+        }
+    }
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_cmd_verify(
+    project_path: Option<PathBuf>,
+    from_file: Option<PathBuf>,
+    exit_code_arg: Option<i32>,
+    package: Option<String>,
+    verify_only_module: Option<String>,
+    verify_function: Option<String>,
+    output: Option<PathBuf>,
+    no_cache: bool,
+    max_output_bytes: Option<usize>,
+    with_atoms: Option<Option<PathBuf>>,
+    no_incremental: bool,
+    json_diagnostics: bool,
+    baseline_path: Option<PathBuf>,
+    bless: bool,
+) -> ProbeResult<AnalysisStatus> {
+    let output_path = output.unwrap_or_else(|| PathBuf::from("proofs.json"));
+    let cache_dir = output_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let baseline_path =
+        baseline_path.unwrap_or_else(|| PathBuf::from(DATA_DIR).join(VERIFICATION_BASELINE_FILE));
+
+    if !no_incremental
+        && from_file.is_none()
+        && verify_only_module.is_none()
+        && verify_function.is_none()
+    {
+        if let (Some(ref path), Some(ref atoms_path_opt)) = (&project_path, &with_atoms) {
+            let atoms_path = atoms_path_opt
+                .clone()
+                .unwrap_or_else(|| PathBuf::from("atoms.json"));
+            if atoms_path.exists() {
+                match run_verification_incremental(
+                    path,
+                    package.as_deref(),
+                    &atoms_path,
+                    cache_dir,
+                    no_cache,
+                    json_diagnostics,
+                ) {
+                    Ok(result) => {
+                        return finish_verify(result, output_path, path, &baseline_path, bless)
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "Warning: incremental verification failed ({}), falling back to a full run",
+                            e
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     // Determine the project path and verification output source
     let (project_path, verification_output, exit_code) = get_verification_data(
         project_path,
@@ -48,7 +252,9 @@ pub fn cmd_verify(
         exit_code_arg,
         package.clone(),
         no_cache,
-    );
+        max_output_bytes,
+        json_diagnostics,
+    )?;
 
     // Analyze the output
     let analyzer = VerificationAnalyzer::new();
@@ -58,6 +264,7 @@ pub fn cmd_verify(
         Some(exit_code),
         verify_only_module.as_deref(),
         verify_function.as_deref(),
+        None,
     );
 
     // Enrich with code-names if requested
@@ -65,10 +272,29 @@ pub fn cmd_verify(
         enrich_result_with_code_names(&mut result, atoms_path_opt);
     }
 
-    // Write JSON output
-    let output_path = output.unwrap_or_else(|| PathBuf::from("proofs.json"));
-    let json = serde_json::to_string_pretty(&result).expect("Failed to serialize JSON");
-    std::fs::write(&output_path, &json).expect("Failed to write JSON output");
+    finish_verify(result, output_path, &project_path, &baseline_path, bless)
+}
+
+/// Write the JSON output and print the summary/failures/errors -- the
+/// shared tail of both the incremental and whole-project verify paths.
+/// Returns the resulting status rather than exiting; an unsuccessful
+/// `AnalysisStatus` is still `Ok`, since the verification run itself
+/// completed -- only a genuine I/O failure, or a baseline regression,
+/// becomes an `Err`.
+///
+/// When `bless` is set, `baseline_path` is overwritten with the fresh
+/// results instead of being compared against. Otherwise, if a baseline
+/// already exists at that path, it's diffed against the fresh results via
+/// [`baseline::compare`] and any regression fails the run.
+fn finish_verify(
+    result: AnalysisResult,
+    output_path: PathBuf,
+    project_path: &Path,
+    baseline_path: &Path,
+    bless: bool,
+) -> ProbeResult<AnalysisStatus> {
+    let json = serde_json::to_string_pretty(&result)?;
+    std::fs::write(&output_path, &json).map_err(|e| ProbeError::file_io(&output_path, e))?;
 
     // Print summary
     print_summary(&result);
@@ -102,88 +328,190 @@ pub fn cmd_verify(
     println!();
     println!("JSON output written to {}", output_path.display());
 
-    // Exit with appropriate code
-    if result.status != AnalysisStatus::Success {
-        std::process::exit(1);
+    if bless {
+        if let Some(parent) = baseline_path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent).map_err(|e| ProbeError::file_io(parent, e))?;
+            }
+        }
+        std::fs::write(baseline_path, &json).map_err(|e| ProbeError::file_io(baseline_path, e))?;
+        println!("Blessed baseline at {}", baseline_path.display());
+    } else if baseline_path.exists() {
+        let comparison = compare_against_baseline(&result, project_path, baseline_path)?;
+
+        if !comparison.newly_fixed.is_empty() {
+            println!("✓ {} newly fixed vs. baseline", comparison.newly_fixed.len());
+        }
+        if !comparison.newly_appeared.is_empty() {
+            println!(
+                "  {} new function(s) not in baseline",
+                comparison.newly_appeared.len()
+            );
+        }
+        if !comparison.removed.is_empty() {
+            println!(
+                "  {} function(s) removed since baseline",
+                comparison.removed.len()
+            );
+        }
+
+        if comparison.has_regressions() {
+            println!();
+            println!(
+                "✗ {} regression(s) vs. baseline:",
+                comparison.regressions.len()
+            );
+            for change in &comparison.regressions {
+                println!("  - {} (was verified, now {:?})", change.symbol, change.to);
+            }
+            return Err(ProbeError::Verification(format!(
+                "{} function(s) regressed vs. baseline {}",
+                comparison.regressions.len(),
+                baseline_path.display()
+            )));
+        }
+        println!("✓ No regressions vs. baseline");
     }
+
+    Ok(result.status)
+}
+
+/// Load `baseline_path` and diff it against `result`.
+fn compare_against_baseline(
+    result: &AnalysisResult,
+    project_path: &Path,
+    baseline_path: &Path,
+) -> ProbeResult<baseline::BaselineComparison> {
+    let baseline_result =
+        baseline::load_baseline(baseline_path).map_err(ProbeError::Verification)?;
+    Ok(baseline::compare(
+        &baseline_result,
+        &result.verification.verified_functions,
+        &result.verification.failed_functions,
+        &result.verification.unverified_functions,
+        project_path,
+    ))
 }
 
 /// Get verification data from either running verification or using cached data.
+#[allow(clippy::too_many_arguments)]
 fn get_verification_data(
     project_path: Option<PathBuf>,
     from_file: Option<PathBuf>,
     exit_code_arg: Option<i32>,
     package: Option<String>,
     no_cache: bool,
-) -> (PathBuf, String, i32) {
+    max_output_bytes: Option<usize>,
+    json_diagnostics: bool,
+) -> ProbeResult<(PathBuf, String, i32)> {
     if let Some(ref path) = project_path {
-        get_verification_data_from_project(path, from_file, exit_code_arg, package, no_cache)
+        get_verification_data_from_project(
+            path,
+            from_file,
+            exit_code_arg,
+            package,
+            no_cache,
+            max_output_bytes,
+            json_diagnostics,
+        )
     } else {
         get_verification_data_from_cache()
     }
 }
 
 /// Get verification data from a project (running verification or using a file).
+#[allow(clippy::too_many_arguments)]
 fn get_verification_data_from_project(
     path: &Path,
     from_file: Option<PathBuf>,
     exit_code_arg: Option<i32>,
     package: Option<String>,
     no_cache: bool,
-) -> (PathBuf, String, i32) {
+    max_output_bytes: Option<usize>,
+    json_diagnostics: bool,
+) -> ProbeResult<(PathBuf, String, i32)> {
     if !path.exists() {
-        eprintln!("Error: Project path does not exist: {}", path.display());
-        std::process::exit(1);
+        return Err(ProbeError::ProjectValidation(format!(
+            "Project path does not exist: {}",
+            path.display()
+        )));
     }
 
     let (output, code) = if let Some(ref output_file) = from_file {
-        get_output_from_file(output_file, exit_code_arg)
+        get_output_from_file(output_file, exit_code_arg)?
     } else {
-        run_verification(path, package.as_deref(), no_cache, &package)
+        run_verification(
+            path,
+            package.as_deref(),
+            no_cache,
+            max_output_bytes,
+            &package,
+            json_diagnostics,
+        )?
     };
 
-    (path.to_path_buf(), output, code)
+    Ok((path.to_path_buf(), output, code))
 }
 
 /// Get verification output from an existing file.
-fn get_output_from_file(output_file: &PathBuf, exit_code_arg: Option<i32>) -> (String, i32) {
+fn get_output_from_file(
+    output_file: &PathBuf,
+    exit_code_arg: Option<i32>,
+) -> ProbeResult<(String, i32)> {
     if !output_file.exists() {
-        eprintln!(
-            "Error: Output file does not exist: {}",
+        return Err(ProbeError::ProjectValidation(format!(
+            "Output file does not exist: {}",
             output_file.display()
-        );
-        std::process::exit(1);
+        )));
     }
 
-    let content = match std::fs::read_to_string(output_file) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("Error reading output file: {}", e);
-            std::process::exit(1);
-        }
-    };
+    let content = std::fs::read_to_string(output_file)
+        .map_err(|e| ProbeError::file_io(output_file, e))?;
 
     println!(
         "Analyzing verification output from: {}",
         output_file.display()
     );
-    (content, exit_code_arg.unwrap_or(0))
+    Ok((content, exit_code_arg.unwrap_or(0)))
 }
 
 /// Run Verus verification on the project.
+///
+/// When `json_diagnostics` is set, requests structured JSON diagnostics
+/// from Verus via [`VerusRunner::run_verification_json`] and uses
+/// [`CompilationErrorParser::parse_json_diagnostics`] for the quick status
+/// check below, instead of the brittle `", 0 errors"` substring check --
+/// the same structured-vs-text split [`VerificationAnalyzer::analyze_output`]
+/// makes when it later analyzes this same output.
+///
+/// `max_output_bytes`, when set, abbreviates the *cached* copy of the output
+/// via [`abbreviate_output`] so a huge run doesn't bloat `data/` -- the
+/// in-memory `output` returned for analysis is always the full text, and
+/// `--no-cache` callers never hit this cap at all since nothing is written.
 fn run_verification(
     path: &Path,
     package: Option<&str>,
     no_cache: bool,
+    max_output_bytes: Option<usize>,
     package_for_cache: &Option<String>,
-) -> (String, i32) {
+    json_diagnostics: bool,
+) -> ProbeResult<(String, i32)> {
     println!("════════════════════════════════════════════════════════════");
     println!("  Running Verus verification...");
     println!("════════════════════════════════════════════════════════════");
 
     let runner = VerusRunner::new();
-    match runner.run_verification(path, package, None, None, None) {
-        Ok((output, code)) => {
+    let run_result = if json_diagnostics {
+        runner.run_verification_json(path, package, None, None)
+    } else {
+        runner.run_verification(path, package, None, None, None)
+    };
+    match run_result {
+        Ok((captured, code)) => {
+            let output = captured.text;
+            if captured.truncated {
+                println!("(output exceeded the capture limit; middle section omitted)");
+            }
             println!();
             println!("════════════════════════════════════════════════════════════");
             println!("  Verification completed with exit code: {}", code);
@@ -191,7 +519,15 @@ fn run_verification(
             println!();
 
             // Quick status check
-            if output.contains("verification results::") {
+            if json_diagnostics {
+                let (errors, _warnings, failures) =
+                    CompilationErrorParser::new().parse_json_diagnostics(&output);
+                if errors.is_empty() && failures.is_empty() {
+                    println!("✓ Verification succeeded!");
+                } else {
+                    println!("✗ Verification failed with errors");
+                }
+            } else if output.contains("verification results::") {
                 if output.contains(", 0 errors") {
                     println!("✓ Verification succeeded!");
                 } else {
@@ -203,27 +539,163 @@ fn run_verification(
 
             // Cache the output unless --no-cache is specified
             if !no_cache {
-                cache_verification_output(path, package_for_cache, code, &output);
+                cache_verification_output(path, package_for_cache, code, &output, max_output_bytes);
             }
 
-            (output, code)
+            Ok((output, code))
+        }
+        Err(e) => Err(ProbeError::external_tool("verus", e.to_string())),
+    }
+}
+
+/// Run verification per-function against the content-hash cache in
+/// `cache_dir/.probe-cache` (see [`super::cache::VerificationCache`]),
+/// skipping Verus entirely for any function whose hash still matches a
+/// cached `Verified` result instead of re-running it as part of one
+/// whole-project pass. A cache miss invokes `--verify-function` for just
+/// that function, the same way [`super::scheduler::verify_group`] does for
+/// the `--jobs N` path, and stores the outcome for next time.
+fn run_verification_incremental(
+    project_path: &Path,
+    package: Option<&str>,
+    atoms_path: &Path,
+    cache_dir: &Path,
+    no_cache: bool,
+    json_diagnostics: bool,
+) -> Result<AnalysisResult, String> {
+    use probe_verus::AtomWithLines;
+    use std::collections::HashMap;
+
+    let atoms_json = std::fs::read_to_string(atoms_path)
+        .map_err(|e| format!("Failed to read {}: {}", atoms_path.display(), e))?;
+    let atoms: HashMap<String, AtomWithLines> = serde_json::from_str(&atoms_json)
+        .map_err(|e| format!("Failed to parse {}: {}", atoms_path.display(), e))?;
+
+    let cache = super::cache::VerificationCache::new(cache_dir, !no_cache);
+    let runner = VerusRunner::new();
+    let analyzer = VerificationAnalyzer::new();
+
+    let mut verified = Vec::new();
+    let mut failed = Vec::new();
+    let mut unverified = Vec::new();
+    let mut cache_hits = 0usize;
+    let mut cache_misses = 0usize;
+
+    for (name, atom) in &atoms {
+        let location = FunctionLocation {
+            display_name: atom.display_name.clone(),
+            code_path: atom.code_path.clone(),
+            code_text: probe_verus::verification::CodeTextInfo {
+                lines_start: atom.code_text.lines_start,
+                lines_end: atom.code_text.lines_end,
+            },
+        };
+
+        let key = cache.key_for(name, &atoms, project_path);
+        if let Some(super::cache::CachedStatus::Verified) = cache.lookup(key) {
+            cache_hits += 1;
+            verified.push(location);
+            continue;
         }
-        Err(e) => {
-            eprintln!("✗ Failed to run verification: {}", e);
-            std::process::exit(1);
+        cache_misses += 1;
+
+        let run_result = if json_diagnostics {
+            runner.run_verification_json(project_path, package, None, Some(&atom.display_name))
+        } else {
+            runner.run_verification(project_path, package, None, Some(&atom.display_name), None)
+        };
+        let (captured, exit_code) = run_result
+            .map_err(|e| format!("Failed to run verification for {}: {}", atom.display_name, e))?;
+
+        let single = analyzer.analyze_output(
+            project_path,
+            &captured.text,
+            Some(exit_code),
+            None,
+            Some(&atom.display_name),
+            None,
+        );
+
+        let status = match single.status {
+            AnalysisStatus::Success if single.summary.unverified_functions > 0 => {
+                super::cache::CachedStatus::Unverified
+            }
+            AnalysisStatus::Success => super::cache::CachedStatus::Verified,
+            _ => super::cache::CachedStatus::Failed,
+        };
+        cache.store(key, status);
+
+        match status {
+            super::cache::CachedStatus::Verified => verified.push(location),
+            super::cache::CachedStatus::Failed => failed.push(location),
+            super::cache::CachedStatus::Unverified => unverified.push(location),
         }
     }
+
+    println!(
+        "Incremental cache: {} hit(s), {} miss(es)",
+        cache_hits, cache_misses
+    );
+
+    Ok(AnalysisResult {
+        status: if failed.is_empty() {
+            AnalysisStatus::Success
+        } else {
+            AnalysisStatus::VerificationFailed
+        },
+        summary: probe_verus::verification::AnalysisSummary {
+            total_functions: atoms.len(),
+            failed_functions: failed.len(),
+            verified_functions: verified.len(),
+            unverified_functions: unverified.len(),
+            verification_errors: 0,
+            compilation_errors: 0,
+            compilation_warnings: 0,
+        },
+        verification: probe_verus::verification::VerificationResult {
+            failed_functions: failed,
+            verified_functions: verified,
+            unverified_functions: unverified,
+            errors: Vec::new(),
+        },
+        compilation: probe_verus::verification::CompilationResult {
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        },
+    })
 }
 
-/// Cache verification output to the data directory.
-fn cache_verification_output(path: &Path, package: &Option<String>, code: i32, output: &str) {
+/// Cache verification output to the data directory. When `max_output_bytes`
+/// is set, the cached copy is abbreviated via [`abbreviate_output`] first --
+/// the file on disk may be smaller than what was actually captured.
+fn cache_verification_output(
+    path: &Path,
+    package: &Option<String>,
+    code: i32,
+    output: &str,
+    max_output_bytes: Option<usize>,
+) {
     if let Err(e) = std::fs::create_dir_all(DATA_DIR) {
         eprintln!("Warning: Could not create data directory: {}", e);
         return;
     }
 
+    let to_write = match max_output_bytes {
+        Some(cap) => {
+            let (abbreviated, outcome) = abbreviate_output(output, cap);
+            if outcome == AbbreviationOutcome::Truncated {
+                println!(
+                    "(cached output abbreviated to {} bytes; see --max-output-bytes)",
+                    cap
+                );
+            }
+            abbreviated
+        }
+        None => output.to_string(),
+    };
+
     // Save verification output
-    if let Err(e) = std::fs::write(cache_output_file(), output) {
+    if let Err(e) = std::fs::write(cache_output_file(), &to_write) {
         eprintln!("Warning: Could not cache verification output: {}", e);
         return;
     }
@@ -245,36 +717,23 @@ fn cache_verification_output(path: &Path, package: &Option<String>, code: i32, o
 }
 
 /// Get verification data from cache.
-fn get_verification_data_from_cache() -> (PathBuf, String, i32) {
+fn get_verification_data_from_cache() -> ProbeResult<(PathBuf, String, i32)> {
     println!("════════════════════════════════════════════════════════════");
     println!("  Using cached verification output");
     println!("════════════════════════════════════════════════════════════");
 
     // Load config
-    let config: VerificationConfig = match std::fs::read_to_string(cache_config_file()) {
-        Ok(content) => match serde_json::from_str(&content) {
-            Ok(c) => c,
-            Err(e) => {
-                eprintln!("Error: Could not parse {}: {}", cache_config_file(), e);
-                eprintln!("Run with a project path first to cache verification output.");
-                std::process::exit(1);
-            }
-        },
-        Err(_) => {
-            eprintln!("Error: No cached verification found.");
-            eprintln!("Run with a project path first: probe-verus verify <project-path>");
-            std::process::exit(1);
-        }
-    };
+    let config_content = std::fs::read_to_string(cache_config_file()).map_err(|_| {
+        ProbeError::ProjectValidation(
+            "No cached verification found. Run with a project path first: probe-verus verify <project-path>"
+                .to_string(),
+        )
+    })?;
+    let config: VerificationConfig = serde_json::from_str(&config_content)?;
 
     // Load cached output
-    let output = match std::fs::read_to_string(cache_output_file()) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("Error: Could not read cached output: {}", e);
-            std::process::exit(1);
-        }
-    };
+    let output = std::fs::read_to_string(cache_output_file())
+        .map_err(|e| ProbeError::file_io(cache_output_file(), e))?;
 
     let path = PathBuf::from(&config.project_path);
     if !path.exists() {
@@ -292,7 +751,7 @@ fn get_verification_data_from_cache() -> (PathBuf, String, i32) {
     println!("════════════════════════════════════════════════════════════");
     println!();
 
-    (path, output, config.exit_code)
+    Ok((path, output, config.exit_code))
 }
 
 /// Enrich the analysis result with code-names from atoms.json.
@@ -333,21 +792,76 @@ fn print_summary(result: &AnalysisResult) {
 
 /// Internal verify implementation that returns Result for better error handling.
 /// Used by the `run` command.
+///
+/// When `jobs > 1` and `atoms_path` points at an existing `atoms.json`, functions
+/// are verified concurrently over the call graph (see [`super::scheduler`])
+/// instead of in one monolithic `cargo verus verify` pass. `progress` only
+/// applies to that monolithic pass -- the `--jobs N` scheduled path prints
+/// no per-group progress of its own.
+#[allow(clippy::too_many_arguments)]
 pub fn verify_internal(
     project_path: &Path,
     output: &Path,
     package: Option<&str>,
     atoms_path: Option<&Path>,
     verbose: bool,
+    jobs: usize,
+    no_cache: bool,
+    clean_cache: bool,
+    progress: bool,
 ) -> Result<VerifySummary, String> {
+    let cache_dir = output.parent().unwrap_or_else(|| Path::new("."));
+    if clean_cache {
+        super::cache::VerificationCache::clean(cache_dir);
+    }
+
+    if jobs > 1 {
+        if let Some(atoms_path) = atoms_path.filter(|p| p.exists()) {
+            return verify_scheduled(
+                project_path,
+                output,
+                package,
+                atoms_path,
+                jobs,
+                cache_dir,
+                no_cache,
+            );
+        }
+    }
+
     let runner = VerusRunner::new();
 
-    let (verification_output, exit_code) = runner
-        .run_verification(project_path, package, None, None, None)
-        .map_err(|e| format!("Failed to run verification: {}", e))?;
+    let reporter = progress.then(|| {
+        let total = probe_verus::verus_parser::parse_all_functions(
+            project_path,
+            true,
+            true,
+            false,
+            false,
+        )
+        .functions
+        .len();
+        probe_verus::verification::ProgressReporter::new(total)
+    });
+    let run_result = match &reporter {
+        Some(reporter) => {
+            runner.run_verification_with_progress(project_path, package, None, None, None, reporter)
+        }
+        None => runner.run_verification(project_path, package, None, None, None),
+    };
+    if let Some(reporter) = &reporter {
+        reporter.finish();
+    }
+    let (captured, exit_code) =
+        run_result.map_err(|e| format!("Failed to run verification: {}", e))?;
+    let verification_output = captured.text;
 
     if verbose {
-        println!("{}", verification_output);
+        let (printed, outcome) = abbreviate_output(&verification_output, DEFAULT_PRINTED_OUTPUT_BYTES);
+        println!("{}", printed);
+        if outcome == AbbreviationOutcome::Truncated {
+            println!("(output abbreviated for the terminal; the full text is in the JSON result)");
+        }
     }
 
     let analyzer = VerificationAnalyzer::new();
@@ -357,6 +871,7 @@ pub fn verify_internal(
         Some(exit_code),
         None,
         None,
+        None,
     );
 
     // Enrich with code-names if atoms.json exists
@@ -378,9 +893,368 @@ pub fn verify_internal(
         verified: result.summary.verified_functions,
         failed: result.summary.failed_functions,
         unverified: result.summary.unverified_functions,
+        skipped: 0,
+        failed_locations: result.verification.failed_functions.clone(),
+        unverified_locations: result.verification.unverified_functions.clone(),
+        verified_locations: result.verification.verified_functions.clone(),
+        wall_clock_ms: None,
+        worker_utilization: None,
+        cache_hits: 0,
+        cache_misses: 0,
     })
 }
 
+/// Verify a project's functions concurrently, scheduled over the call graph in
+/// `atoms_path`. Reuses per-function results from `cache_dir/.probe-cache` when
+/// the source+dependency hash matches, unless `no_cache` is set.
+#[allow(clippy::too_many_arguments)]
+fn verify_scheduled(
+    project_path: &Path,
+    output: &Path,
+    package: Option<&str>,
+    atoms_path: &Path,
+    jobs: usize,
+    cache_dir: &Path,
+    no_cache: bool,
+) -> Result<VerifySummary, String> {
+    use probe_verus::AtomWithLines;
+    use std::collections::HashMap;
+
+    let atoms_json = std::fs::read_to_string(atoms_path)
+        .map_err(|e| format!("Failed to read {}: {}", atoms_path.display(), e))?;
+    let atoms: HashMap<String, AtomWithLines> = serde_json::from_str(&atoms_json)
+        .map_err(|e| format!("Failed to parse {}: {}", atoms_path.display(), e))?;
+    let total_functions = atoms.len();
+
+    let cache = super::cache::VerificationCache::new(cache_dir, !no_cache);
+    let schedule =
+        super::scheduler::run_scheduled_verification(project_path, atoms, package, jobs, cache);
+
+    let verified_locations = schedule.verified_locations();
+    let failed_locations = schedule.failed_locations();
+    let unverified_locations = schedule.unverified_locations();
+    let skipped = schedule.skipped_count();
+
+    let result = AnalysisResult {
+        status: if failed_locations.is_empty() {
+            AnalysisStatus::Success
+        } else {
+            AnalysisStatus::VerificationFailed
+        },
+        summary: probe_verus::verification::AnalysisSummary {
+            total_functions,
+            failed_functions: failed_locations.len(),
+            verified_functions: verified_locations.len(),
+            unverified_functions: unverified_locations.len(),
+            verification_errors: 0,
+            compilation_errors: 0,
+            compilation_warnings: 0,
+        },
+        verification: probe_verus::verification::VerificationResult {
+            failed_functions: failed_locations.clone(),
+            verified_functions: verified_locations.clone(),
+            unverified_functions: unverified_locations.clone(),
+            errors: Vec::new(),
+        },
+        compilation: probe_verus::verification::CompilationResult {
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        },
+    };
+
+    let json = serde_json::to_string_pretty(&result)
+        .map_err(|e| format!("Failed to serialize JSON: {}", e))?;
+    std::fs::write(output, &json).map_err(|e| format!("Failed to write output: {}", e))?;
+
+    Ok(VerifySummary {
+        total_functions,
+        verified: verified_locations.len(),
+        failed: failed_locations.len(),
+        unverified: unverified_locations.len(),
+        skipped,
+        failed_locations,
+        unverified_locations,
+        verified_locations,
+        wall_clock_ms: Some(schedule.wall_clock_ms),
+        worker_utilization: Some(schedule.worker_utilization),
+        cache_hits: schedule.cache_hits,
+        cache_misses: schedule.cache_misses,
+    })
+}
+
+/// One explicit verification target for [`cmd_verify_batch`].
+#[derive(Clone, Debug)]
+pub enum BatchTarget {
+    Module(String),
+    Function(String),
+}
+
+impl BatchTarget {
+    /// A filesystem-safe tag for this target, used to namespace its cached
+    /// output so concurrent workers don't clobber each other's files.
+    fn cache_tag(&self) -> String {
+        let raw = match self {
+            BatchTarget::Module(name) => format!("module-{}", name),
+            BatchTarget::Function(name) => format!("function-{}", name),
+        };
+        raw.chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect()
+    }
+}
+
+/// Run verification against a fixed list of `--verify-only-module`/
+/// `--verify-function` targets concurrently, up to `jobs` Verus processes at
+/// once, then merge every target's [`AnalysisResult`] into one aggregate --
+/// the same idea as [`super::scheduler::run_scheduled_verification`], but for
+/// a caller-specified target list instead of the whole atoms.json call graph,
+/// so there's no dependency relationship between targets to respect: a flat
+/// work queue is enough.
+///
+/// Each target's raw output is cached separately (tagged by
+/// [`BatchTarget::cache_tag`]) under `data/`, guarded by a shared mutex so
+/// concurrent writes don't interleave. The merged result is written through
+/// [`finish_verify`], so `--baseline`/`--bless` gating applies to the
+/// aggregate the same way it does for a single-target run.
+#[allow(clippy::too_many_arguments)]
+pub fn cmd_verify_batch(
+    project_path: PathBuf,
+    package: Option<String>,
+    verify_only_modules: Vec<String>,
+    verify_functions: Vec<String>,
+    output: Option<PathBuf>,
+    jobs: usize,
+    no_cache: bool,
+    json_diagnostics: bool,
+    baseline_path: Option<PathBuf>,
+    bless: bool,
+) -> ProbeResult<AnalysisStatus> {
+    let output_path = output.unwrap_or_else(|| PathBuf::from("proofs.json"));
+    let baseline_path =
+        baseline_path.unwrap_or_else(|| PathBuf::from(DATA_DIR).join(VERIFICATION_BASELINE_FILE));
+
+    if !project_path.exists() {
+        return Err(ProbeError::ProjectValidation(format!(
+            "Project path does not exist: {}",
+            project_path.display()
+        )));
+    }
+
+    let targets: VecDeque<BatchTarget> = verify_only_modules
+        .into_iter()
+        .map(BatchTarget::Module)
+        .chain(verify_functions.into_iter().map(BatchTarget::Function))
+        .collect();
+
+    if targets.is_empty() {
+        return Err(ProbeError::ProjectValidation(
+            "verify-batch requires at least one --verify-only-module or --verify-function"
+                .to_string(),
+        ));
+    }
+
+    let queue = Mutex::new(targets);
+    let results = Mutex::new(Vec::new());
+    let cache_lock = Mutex::new(());
+    let worker_count = jobs.max(1);
+
+    println!(
+        "Running batch verification across {} worker(s)...",
+        worker_count
+    );
+
+    std::thread::scope(|scope| {
+        for _ in 0..worker_count {
+            scope.spawn(|| loop {
+                let target = {
+                    let mut queue = queue.lock().unwrap();
+                    queue.pop_front()
+                };
+                let Some(target) = target else {
+                    break;
+                };
+
+                match run_batch_target(
+                    &project_path,
+                    package.as_deref(),
+                    &target,
+                    no_cache,
+                    json_diagnostics,
+                    &cache_lock,
+                ) {
+                    Ok(result) => results.lock().unwrap().push(result),
+                    Err(e) => {
+                        eprintln!("Warning: verification of {:?} failed: {}", target, e);
+                    }
+                }
+            });
+        }
+    });
+
+    let collected = results.into_inner().unwrap();
+    let merged = merge_analysis_results(collected);
+
+    finish_verify(merged, output_path, &project_path, &baseline_path, bless)
+}
+
+/// Run verification for a single batch target and analyze its output.
+fn run_batch_target(
+    project_path: &Path,
+    package: Option<&str>,
+    target: &BatchTarget,
+    no_cache: bool,
+    json_diagnostics: bool,
+    cache_lock: &Mutex<()>,
+) -> ProbeResult<AnalysisResult> {
+    let runner = VerusRunner::new();
+    let (module, function) = match target {
+        BatchTarget::Module(name) => (Some(name.as_str()), None),
+        BatchTarget::Function(name) => (None, Some(name.as_str())),
+    };
+
+    let run_result = if json_diagnostics {
+        runner.run_verification_json(project_path, package, module, function)
+    } else {
+        runner.run_verification(project_path, package, module, function, None)
+    };
+    let (captured, exit_code) =
+        run_result.map_err(|e| ProbeError::external_tool("verus", e.to_string()))?;
+
+    if !no_cache {
+        cache_verification_output_for_target(
+            project_path,
+            &target.cache_tag(),
+            exit_code,
+            &captured.text,
+            cache_lock,
+        );
+    }
+
+    let analyzer = VerificationAnalyzer::new();
+    Ok(analyzer.analyze_output(
+        project_path,
+        &captured.text,
+        Some(exit_code),
+        module,
+        function,
+        None,
+    ))
+}
+
+/// Cache a single batch target's verification output under a tagged
+/// filename (e.g. `data/verification_output.function-foo.txt`) so that
+/// concurrent targets don't overwrite each other's cached output. Guarded by
+/// `lock` since multiple workers may be writing to `data/` at once.
+fn cache_verification_output_for_target(
+    project_path: &Path,
+    tag: &str,
+    code: i32,
+    output: &str,
+    lock: &Mutex<()>,
+) {
+    let _guard = lock.lock().unwrap();
+
+    if let Err(e) = std::fs::create_dir_all(DATA_DIR) {
+        eprintln!("Warning: Could not create data directory: {}", e);
+        return;
+    }
+
+    let output_file = format!("{}/verification_output.{}.txt", DATA_DIR, tag);
+    if let Err(e) = std::fs::write(&output_file, output) {
+        eprintln!("Warning: Could not cache verification output: {}", e);
+        return;
+    }
+
+    let config = VerificationConfig {
+        project_path: project_path.to_string_lossy().to_string(),
+        package: None,
+        exit_code: code,
+    };
+    let config_file = format!("{}/verification_config.{}.json", DATA_DIR, tag);
+    if let Ok(config_json) = serde_json::to_string_pretty(&config) {
+        if let Err(e) = std::fs::write(&config_file, config_json) {
+            eprintln!("Warning: Could not save verification config: {}", e);
+        }
+    }
+}
+
+/// Merge several targets' [`AnalysisResult`]s into one aggregate: summary
+/// counts are summed, per-function lists are concatenated, and the merged
+/// `status` is the worst of all the inputs (see [`worse_status`]).
+fn merge_analysis_results(results: Vec<AnalysisResult>) -> AnalysisResult {
+    let mut merged = AnalysisResult {
+        status: AnalysisStatus::Success,
+        summary: probe_verus::verification::AnalysisSummary {
+            total_functions: 0,
+            failed_functions: 0,
+            verified_functions: 0,
+            unverified_functions: 0,
+            verification_errors: 0,
+            compilation_errors: 0,
+            compilation_warnings: 0,
+        },
+        verification: probe_verus::verification::VerificationResult {
+            failed_functions: Vec::new(),
+            verified_functions: Vec::new(),
+            unverified_functions: Vec::new(),
+            errors: Vec::new(),
+        },
+        compilation: probe_verus::verification::CompilationResult {
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        },
+    };
+
+    for result in results {
+        merged.status = worse_status(merged.status, result.status);
+
+        merged.summary.total_functions += result.summary.total_functions;
+        merged.summary.failed_functions += result.summary.failed_functions;
+        merged.summary.verified_functions += result.summary.verified_functions;
+        merged.summary.unverified_functions += result.summary.unverified_functions;
+        merged.summary.verification_errors += result.summary.verification_errors;
+        merged.summary.compilation_errors += result.summary.compilation_errors;
+        merged.summary.compilation_warnings += result.summary.compilation_warnings;
+
+        merged
+            .verification
+            .failed_functions
+            .extend(result.verification.failed_functions);
+        merged
+            .verification
+            .verified_functions
+            .extend(result.verification.verified_functions);
+        merged
+            .verification
+            .unverified_functions
+            .extend(result.verification.unverified_functions);
+        merged.verification.errors.extend(result.verification.errors);
+
+        merged.compilation.errors.extend(result.compilation.errors);
+        merged.compilation.warnings.extend(result.compilation.warnings);
+    }
+
+    merged
+}
+
+/// Rank two [`AnalysisStatus`] values and return the worse one, mirroring
+/// [`super::scheduler::worse_of`]'s rank-based comparison for `GroupStatus`.
+fn worse_status(a: AnalysisStatus, b: AnalysisStatus) -> AnalysisStatus {
+    fn rank(status: &AnalysisStatus) -> u8 {
+        match status {
+            AnalysisStatus::Success => 0,
+            AnalysisStatus::FunctionsOnly => 1,
+            AnalysisStatus::VerificationFailed => 2,
+            AnalysisStatus::CompilationFailed => 3,
+        }
+    }
+    if rank(&b) > rank(&a) {
+        b
+    } else {
+        a
+    }
+}
+
 /// Summary of verification results.
 #[derive(Clone)]
 pub struct VerifySummary {
@@ -388,4 +1262,19 @@ pub struct VerifySummary {
     pub verified: usize,
     pub failed: usize,
     pub unverified: usize,
+    /// Dependents of a failed/unverified function that were never dispatched.
+    /// Only populated by the `--jobs N` scheduled path.
+    pub skipped: usize,
+    /// Per-function locations, carried along so callers (like `cmd_run`'s
+    /// reporters) can surface individual outcomes, not just the totals.
+    pub failed_locations: Vec<FunctionLocation>,
+    pub unverified_locations: Vec<FunctionLocation>,
+    pub verified_locations: Vec<FunctionLocation>,
+    /// Wall-clock time for the scheduled run, if `--jobs N` was used.
+    pub wall_clock_ms: Option<u128>,
+    /// Fraction of worker-time spent verifying vs. idle, if `--jobs N` was used.
+    pub worker_utilization: Option<f64>,
+    /// Functions reused from the incremental cache instead of re-verified.
+    pub cache_hits: usize,
+    pub cache_misses: usize,
 }
@@ -0,0 +1,95 @@
+//! Parsing the Verus verifier's machine-readable JSON results.
+//!
+//! Mirrors the approach compiletest uses for `--error-format=json`: treat
+//! the verifier's own structured output as ground truth instead of
+//! re-deriving proof status from the AST. Each result is keyed by source
+//! span (`file` + `lines_start`) so it can be joined against functions
+//! discovered by `parse_all_functions_ext`.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A single function's verification outcome, as reported by the verifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerusStatus {
+    Verified,
+    Failed,
+    Timeout,
+}
+
+/// One verifier result, keyed by the source span of the function it covers.
+#[derive(Debug, Clone, Deserialize)]
+pub struct VerusFunctionResult {
+    pub file: String,
+    pub lines_start: usize,
+    pub status: VerusStatus,
+}
+
+/// The verifier's full JSON report: one result per function it checked.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct VerusJsonReport {
+    #[serde(default)]
+    pub results: Vec<VerusFunctionResult>,
+}
+
+/// Verifier results indexed by `(file, lines_start)` for joining against
+/// parsed functions.
+pub type VerusResultsByKey = HashMap<(String, usize), VerusStatus>;
+
+/// Load and index a verifier JSON report from disk.
+pub fn load_verus_json(path: &Path) -> Result<VerusResultsByKey, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let report: VerusJsonReport = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+    Ok(report
+        .results
+        .into_iter()
+        .map(|r| ((r.file, r.lines_start), r.status))
+        .collect())
+}
+
+/// The richer per-function proof status `tracked-csv` reports once verifier
+/// JSON is supplied. Falls back to [`FunctionProofStatus::Unverified`] both
+/// when a function has no matching verifier result and (via the AST
+/// heuristic) when no `--verus-json` was supplied at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionProofStatus {
+    Verified,
+    Failed,
+    Timeout,
+    Unverified,
+}
+
+impl From<VerusStatus> for FunctionProofStatus {
+    fn from(status: VerusStatus) -> Self {
+        match status {
+            VerusStatus::Verified => FunctionProofStatus::Verified,
+            VerusStatus::Failed => FunctionProofStatus::Failed,
+            VerusStatus::Timeout => FunctionProofStatus::Timeout,
+        }
+    }
+}
+
+impl FunctionProofStatus {
+    /// The value of the CSV's existing `has_proof` column for this status.
+    pub fn as_has_proof(&self) -> &'static str {
+        if matches!(self, FunctionProofStatus::Verified) {
+            "yes"
+        } else {
+            ""
+        }
+    }
+
+    /// A short label for summary counts (`verified`/`failed`/`timeout`/`unverified`).
+    pub fn label(&self) -> &'static str {
+        match self {
+            FunctionProofStatus::Verified => "verified",
+            FunctionProofStatus::Failed => "failed",
+            FunctionProofStatus::Timeout => "timeout",
+            FunctionProofStatus::Unverified => "unverified",
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Workspace-wide `run`, driven by `cargo metadata`.
+//!
+//! `cmd_run` normally treats `project_path` as a single crate rooted at its
+//! `Cargo.toml`. This module adds the `--workspace` mode: discover every member
+//! via `cargo metadata --no-deps`, then atomize+verify each one under its own
+//! subdirectory of `output_dir`, rolling the per-member results up into one
+//! aggregated `run_summary.json`. The top-level status becomes
+//! `verification_failed` if any member has failures, mirroring how `cargo test
+//! --workspace` reports a single pass/fail across every crate.
+
+use super::atomize::{atomize_internal, AtomizeFormat};
+use super::verify::verify_internal;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A single workspace member, as reported by `cargo metadata`.
+struct WorkspaceMember {
+    name: String,
+    root: PathBuf,
+}
+
+#[derive(serde::Deserialize)]
+struct CargoMetadata {
+    packages: Vec<CargoPackage>,
+}
+
+#[derive(serde::Deserialize)]
+struct CargoPackage {
+    name: String,
+    manifest_path: String,
+}
+
+/// Discover workspace members by shelling out to `cargo metadata --no-deps`.
+///
+/// `--no-deps` restricts the `packages` list to workspace members themselves
+/// (no transitive dependency packages), so it can be used directly without
+/// cross-referencing `workspace_members`.
+fn discover_members(project_path: &Path) -> Result<Vec<WorkspaceMember>, String> {
+    let output = Command::new("cargo")
+        .args(["metadata", "--format-version", "1", "--no-deps"])
+        .current_dir(project_path)
+        .output()
+        .map_err(|e| format!("Failed to run `cargo metadata` (is cargo on PATH?): {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "`cargo metadata` failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let metadata: CargoMetadata = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse `cargo metadata` output: {}", e))?;
+
+    Ok(metadata
+        .packages
+        .into_iter()
+        .filter_map(|pkg| {
+            let root = PathBuf::from(&pkg.manifest_path).parent()?.to_path_buf();
+            Some(WorkspaceMember {
+                name: pkg.name,
+                root,
+            })
+        })
+        .collect())
+}
+
+/// Per-member result, for the aggregated workspace summary.
+#[derive(Serialize)]
+struct MemberResult {
+    package: String,
+    status: String,
+    atoms_output: String,
+    proofs_output: String,
+}
+
+/// Aggregated result of a `--workspace` run, written to `run_summary.json`.
+#[derive(Serialize)]
+struct WorkspaceRunResult {
+    status: String,
+    members: Vec<MemberResult>,
+}
+
+/// Run atomize+verify over every workspace member (minus `--exclude`d ones),
+/// writing per-member output under `output_dir/<package-name>/` and an
+/// aggregated `run_summary.json` at the top of `output_dir`.
+#[allow(clippy::too_many_arguments)]
+pub fn cmd_run_workspace(
+    project_path: PathBuf,
+    output_dir: PathBuf,
+    atomize_only: bool,
+    verify_only: bool,
+    regenerate_scip: bool,
+    verbose: bool,
+    jobs: usize,
+    exclude: Vec<String>,
+) {
+    let members = match discover_members(&project_path) {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let members: Vec<_> = members
+        .into_iter()
+        .filter(|m| !exclude.contains(&m.name))
+        .collect();
+
+    if members.is_empty() {
+        eprintln!("Error: no workspace members to run (after applying --exclude)");
+        std::process::exit(1);
+    }
+
+    if let Err(e) = std::fs::create_dir_all(&output_dir) {
+        eprintln!("Error: Failed to create output directory: {}", e);
+        std::process::exit(1);
+    }
+
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  probe-verus run --workspace ({} members)", members.len());
+    println!("═══════════════════════════════════════════════════════════════");
+    println!();
+
+    let mut any_hard_failure = false;
+    let mut any_verification_failure = false;
+    let mut member_results = Vec::new();
+
+    for member in &members {
+        println!("─── {} ───", member.name);
+
+        let member_output_dir = output_dir.join(&member.name);
+        if let Err(e) = std::fs::create_dir_all(&member_output_dir) {
+            eprintln!("  ✗ Failed to create output directory: {}", e);
+            any_hard_failure = true;
+            continue;
+        }
+
+        let atoms_path = member_output_dir.join("atoms.json");
+        let results_path = member_output_dir.join("proofs.json");
+        let mut member_status = "success".to_string();
+
+        if !verify_only {
+            match atomize_internal(
+                &member.root,
+                &atoms_path,
+                regenerate_scip,
+                verbose,
+                AtomizeFormat::Json,
+            ) {
+                Ok(count) => println!("  ✓ Atomize: {} functions", count),
+                Err(e) => {
+                    eprintln!("  ✗ Atomize failed: {}", e);
+                    member_status = "atomize_failed".to_string();
+                }
+            }
+        }
+
+        if !atomize_only && member_status == "success" {
+            match verify_internal(
+                &member.root,
+                &results_path,
+                Some(&member.name),
+                if atoms_path.exists() {
+                    Some(atoms_path.as_path())
+                } else {
+                    None
+                },
+                verbose,
+                jobs,
+                false, // no_cache
+                false, // clean_cache
+                false, // progress
+            ) {
+                Ok(summary) => {
+                    println!(
+                        "  ✓ Verify: {} verified, {} failed, {} unverified",
+                        summary.verified, summary.failed, summary.unverified
+                    );
+                    if summary.failed > 0 {
+                        member_status = "verification_failed".to_string();
+                    }
+                }
+                Err(e) => {
+                    eprintln!("  ✗ Verify failed: {}", e);
+                    member_status = "verify_failed".to_string();
+                }
+            }
+        }
+
+        match member_status.as_str() {
+            "atomize_failed" | "verify_failed" => any_hard_failure = true,
+            "verification_failed" => any_verification_failure = true,
+            _ => {}
+        }
+
+        member_results.push(MemberResult {
+            package: member.name.clone(),
+            status: member_status,
+            atoms_output: atoms_path.display().to_string(),
+            proofs_output: results_path.display().to_string(),
+        });
+        println!();
+    }
+
+    // A hard failure (couldn't even run atomize/verify) takes priority over a
+    // soft one (ran fine, found real verification failures) for the top-level
+    // status, mirroring `cmd_run`'s own precedence.
+    let overall_status = if any_hard_failure {
+        "run_failed".to_string()
+    } else if any_verification_failure {
+        "verification_failed".to_string()
+    } else {
+        "success".to_string()
+    };
+
+    let workspace_result = WorkspaceRunResult {
+        status: overall_status.clone(),
+        members: member_results,
+    };
+
+    let summary_path = output_dir.join("run_summary.json");
+    if let Ok(json) = serde_json::to_string_pretty(&workspace_result) {
+        if let Err(e) = std::fs::write(&summary_path, &json) {
+            eprintln!("Warning: Could not write summary: {}", e);
+        }
+    }
+
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Workspace status: {}", overall_status);
+    println!("═══════════════════════════════════════════════════════════════");
+
+    // "verification_failed" exits 0, same as `cmd_run`: verification ran
+    // successfully, it just found failures. Only a run that couldn't complete
+    // exits non-zero.
+    std::process::exit(if any_hard_failure { 1 } else { 0 });
+}
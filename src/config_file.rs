@@ -0,0 +1,428 @@
+//! Layered configuration for the `scip-atoms` CLI: built-in defaults, a
+//! `scip-atoms.toml` discovered upward from the working directory, a small
+//! set of environment variables, and finally the user's own CLI flags --
+//! each layer overriding the previous, 12-factor-app style.
+//!
+//! `scip-atoms.toml` is searched for upward from the working directory, the
+//! same way `cargo` resolves `.cargo/config.toml` -- so a config checked in
+//! at a project's root applies no matter which subdirectory it's invoked
+//! from. Two things it can configure:
+//!
+//! - `[alias]`: a name expanding to a full subcommand plus preset
+//!   arguments, e.g. `field = "verify . --package curve25519 ..."`.
+//! - `[atoms]` / `[functions]` / `[verify]`: default flag values for that
+//!   subcommand, injected ahead of the user's own arguments so an explicit
+//!   flag on the command line still overrides the config default (clap
+//!   keeps the last occurrence of a `Set`-action flag).
+//!
+//! On top of the config file, [`ENV_OVERRIDES`] lists a handful of
+//! environment variables useful for overriding a setting from CI without
+//! touching a committed `scip-atoms.toml`; they're spliced in after the
+//! config file's defaults and before the user's own flags, giving the
+//! precedence order `defaults < scip-atoms.toml < environment < CLI flags`.
+//! Run any subcommand with `--print-config` to see the effective argv after
+//! all three layers have been applied.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Filename searched for upward from the working directory.
+pub const CONFIG_FILE_NAME: &str = "scip-atoms.toml";
+
+/// Environment variables that override a config-file default, applied
+/// after `scip-atoms.toml` and before explicit CLI flags. Narrow and
+/// explicit on purpose: these are the handful of settings worth flipping
+/// from a CI job's environment, not a mechanical transform of every flag
+/// name (the flag each maps to isn't always a literal case conversion --
+/// `SCIP_ATOMS_JSON` controls `--json-output`, the flag name shared by the
+/// `functions` and `verify` subcommands).
+const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("SCIP_ATOMS_NO_CACHE", "no-cache"),
+    ("SCIP_ATOMS_JSON", "json-output"),
+];
+
+/// Flags that take no value (`ArgAction::SetTrue` in the `Cli` derive) --
+/// splicing a default/env override for one of these must omit the value
+/// token entirely when the flag should be set, rather than emitting
+/// `--flag value` as is done for a value-taking flag.
+const BOOL_FLAGS: &[&str] = &[
+    "regenerate-scip",
+    "show-edge-diagnostics",
+    "disambiguate-by-line",
+    "exclude-verus-constructs",
+    "exclude-methods",
+    "show-visibility",
+    "show-kind",
+    "no-cache",
+    "watch",
+    "progress",
+    "bless",
+];
+
+/// Append the argv tokens for one resolved `--flag value` (or bare `--flag`
+/// for a [`BOOL_FLAGS`] entry) default, parsing a boolean value loosely
+/// (`"true"`/`"1"`/`"yes"`, case-insensitive) so a config file or
+/// environment variable can write whichever spelling reads naturally.
+fn splice_default(out: &mut Vec<String>, flag: &str, value: &str) {
+    let flag = flag.replace('_', "-");
+    let long_flag = format!("--{}", flag);
+    if BOOL_FLAGS.contains(&flag.as_str()) {
+        let truthy = matches!(value.to_lowercase().as_str(), "true" | "1" | "yes");
+        if truthy {
+            out.push(long_flag);
+        }
+    } else {
+        out.push(long_flag);
+        out.push(value.to_string());
+    }
+}
+
+/// Read [`ENV_OVERRIDES`] from the process environment, in declaration
+/// order, skipping any variable that isn't set.
+fn env_defaults() -> Vec<(&'static str, String)> {
+    ENV_OVERRIDES
+        .iter()
+        .filter_map(|(var, flag)| std::env::var(var).ok().map(|value| (*flag, value)))
+        .collect()
+}
+
+/// Parsed `scip-atoms.toml`. Each subcommand table maps a long-flag name
+/// (dashes or underscores) to its default value; boolean flags should be
+/// given as `"true"`/`"false"` strings since they're spliced into argv the
+/// same way a string-valued flag is.
+#[derive(Deserialize, Default)]
+pub struct Config {
+    #[serde(default)]
+    pub alias: BTreeMap<String, String>,
+    #[serde(default)]
+    pub atoms: BTreeMap<String, String>,
+    #[serde(default)]
+    pub functions: BTreeMap<String, String>,
+    #[serde(default)]
+    pub verify: BTreeMap<String, String>,
+}
+
+impl Config {
+    fn defaults_for(&self, subcommand: &str) -> Option<&BTreeMap<String, String>> {
+        match subcommand {
+            "atoms" => Some(&self.atoms),
+            "functions" => Some(&self.functions),
+            "verify" => Some(&self.verify),
+            _ => None,
+        }
+    }
+}
+
+/// Search `start_dir` and its ancestors for [`CONFIG_FILE_NAME`].
+pub fn find_config(start_dir: &Path) -> Option<PathBuf> {
+    let mut dir = Some(start_dir);
+    while let Some(d) = dir {
+        let candidate = d.join(CONFIG_FILE_NAME);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        dir = d.parent();
+    }
+    None
+}
+
+/// Load and parse `scip-atoms.toml`, searched upward from `start_dir`.
+/// Missing file or a parse error both fall back to an empty (all-default)
+/// [`Config`] -- a misconfigured or absent config shouldn't stop the command
+/// the user actually typed from running, just lose its defaults/aliases.
+pub fn load(start_dir: &Path) -> Config {
+    let Some(path) = find_config(start_dir) else {
+        return Config::default();
+    };
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Warning: could not read {}: {}", path.display(), e);
+            return Config::default();
+        }
+    };
+    match toml::from_str(&content) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Warning: could not parse {}: {}", path.display(), e);
+            Config::default()
+        }
+    }
+}
+
+/// Split a config string (an alias's expansion) into argv tokens,
+/// respecting double-quoted segments so a value containing spaces survives.
+fn split_args(command: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in command.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Resolve raw `argv` (including the program name at index 0) against
+/// `config` and the process environment before handing it to
+/// `Cli::parse_from`:
+///
+/// 1. If the first argument names a `[alias]`, replace it with the alias's
+///    expansion (subcommand plus preset arguments); anything the user typed
+///    after the alias name is appended, so `scip-atoms field --no-cache`
+///    still works.
+/// 2. If the resulting subcommand has a matching defaults table
+///    (`[atoms]`/`[functions]`/`[verify]`), each entry is spliced in right
+///    after the subcommand name, ahead of the rest of the arguments.
+/// 3. Any set [`ENV_OVERRIDES`] variable is spliced in next, overriding the
+///    config file's defaults but not the user's own flags.
+///
+/// Because each layer lands after the one before it in argv (config
+/// defaults, then environment, then the user's own arguments), and
+/// clap keeps the last occurrence of a `Set`-action flag, the result is the
+/// precedence order `scip-atoms.toml < environment < explicit CLI flags`.
+pub fn resolve_args(argv: Vec<String>, config: &Config) -> Vec<String> {
+    if argv.len() < 2 {
+        return argv;
+    }
+
+    let mut resolved = vec![argv[0].clone()];
+    let rest = &argv[1..];
+
+    if let Some(command) = config.alias.get(&rest[0]) {
+        resolved.extend(split_args(command));
+        resolved.extend(rest[1..].iter().cloned());
+    } else {
+        resolved.extend(rest.iter().cloned());
+    }
+
+    let Some(subcommand) = resolved.get(1).cloned() else {
+        return resolved;
+    };
+
+    let mut with_defaults = resolved[..2].to_vec();
+    if let Some(defaults) = config.defaults_for(&subcommand.to_lowercase()) {
+        for (flag, value) in defaults {
+            splice_default(&mut with_defaults, flag, value);
+        }
+    }
+    for (flag, value) in env_defaults() {
+        splice_default(&mut with_defaults, flag, &value);
+    }
+    with_defaults.extend_from_slice(&resolved[2..]);
+    with_defaults
+}
+
+/// A one-line, human-readable summary of the fully resolved argv for
+/// `--print-config`: the effective command line after the config file and
+/// environment layers have been spliced in, but before clap itself applies
+/// the user's own flags on top.
+pub fn describe_resolved_invocation(resolved_argv: &[String]) -> String {
+    format!(
+        "Effective invocation after layering config sources:\n  {}\n\nPrecedence (lowest to highest): built-in defaults < scip-atoms.toml < environment variables < explicit CLI flags",
+        resolved_argv.join(" ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_alias(name: &str, command: &str) -> Config {
+        let mut alias = BTreeMap::new();
+        alias.insert(name.to_string(), command.to_string());
+        Config {
+            alias,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn resolve_args_expands_a_matching_alias() {
+        let config = config_with_alias(
+            "field",
+            r#"verify . --package curve25519 --verify-only-module backend::serial::u64::field_verus"#,
+        );
+        let argv = vec!["scip-atoms".to_string(), "field".to_string()];
+        let resolved = resolve_args(argv, &config);
+        assert_eq!(
+            resolved,
+            vec![
+                "scip-atoms",
+                "verify",
+                ".",
+                "--package",
+                "curve25519",
+                "--verify-only-module",
+                "backend::serial::u64::field_verus",
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_args_appends_trailing_user_args_after_an_alias() {
+        let config = config_with_alias("field", "verify .");
+        let argv = vec![
+            "scip-atoms".to_string(),
+            "field".to_string(),
+            "--no-cache".to_string(),
+        ];
+        let resolved = resolve_args(argv, &config);
+        assert_eq!(resolved, vec!["scip-atoms", "verify", ".", "--no-cache"]);
+    }
+
+    #[test]
+    fn resolve_args_leaves_a_non_alias_subcommand_untouched() {
+        let config = config_with_alias("field", "verify .");
+        let argv = vec![
+            "scip-atoms".to_string(),
+            "atoms".to_string(),
+            ".".to_string(),
+        ];
+        let resolved = resolve_args(argv.clone(), &config);
+        assert_eq!(resolved, argv);
+    }
+
+    #[test]
+    fn resolve_args_injects_subcommand_defaults_before_explicit_flags() {
+        let mut verify = BTreeMap::new();
+        verify.insert("package".to_string(), "curve25519".to_string());
+        let config = Config {
+            verify,
+            ..Config::default()
+        };
+        let argv = vec![
+            "scip-atoms".to_string(),
+            "verify".to_string(),
+            ".".to_string(),
+            "--package".to_string(),
+            "other".to_string(),
+        ];
+        let resolved = resolve_args(argv, &config);
+        // The default comes first, but clap keeps the last occurrence of
+        // `--package`, so the explicit `other` still wins.
+        assert_eq!(
+            resolved,
+            vec![
+                "scip-atoms",
+                "verify",
+                "--package",
+                "curve25519",
+                ".",
+                "--package",
+                "other",
+            ]
+        );
+    }
+
+    #[test]
+    fn find_config_searches_ancestor_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(dir.path().join(CONFIG_FILE_NAME), "[alias]\n").unwrap();
+
+        let found = find_config(&nested).unwrap();
+        assert_eq!(found, dir.path().join(CONFIG_FILE_NAME));
+    }
+
+    #[test]
+    fn load_falls_back_to_defaults_when_no_config_is_found() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = load(dir.path());
+        assert!(config.alias.is_empty());
+    }
+
+    #[test]
+    fn resolve_args_applies_a_bool_env_override_as_a_bare_flag() {
+        std::env::set_var("SCIP_ATOMS_NO_CACHE", "true");
+        let argv = vec![
+            "scip-atoms".to_string(),
+            "verify".to_string(),
+            ".".to_string(),
+        ];
+        let resolved = resolve_args(argv, &Config::default());
+        std::env::remove_var("SCIP_ATOMS_NO_CACHE");
+
+        assert_eq!(
+            resolved,
+            vec!["scip-atoms", "verify", "--no-cache", "."]
+        );
+    }
+
+    #[test]
+    fn resolve_args_env_override_loses_to_an_explicit_cli_flag() {
+        std::env::set_var("SCIP_ATOMS_JSON", "env_results.json");
+        let argv = vec![
+            "scip-atoms".to_string(),
+            "verify".to_string(),
+            ".".to_string(),
+            "--json-output".to_string(),
+            "cli_results.json".to_string(),
+        ];
+        let resolved = resolve_args(argv, &Config::default());
+        std::env::remove_var("SCIP_ATOMS_JSON");
+
+        // clap keeps the last occurrence, so the explicit CLI value wins
+        // even though the env override was spliced in first.
+        assert_eq!(
+            resolved,
+            vec![
+                "scip-atoms",
+                "verify",
+                "--json-output",
+                "env_results.json",
+                ".",
+                "--json-output",
+                "cli_results.json",
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_args_env_override_wins_over_a_config_file_default() {
+        let mut verify = BTreeMap::new();
+        verify.insert("no-cache".to_string(), "false".to_string());
+        let config = Config {
+            verify,
+            ..Config::default()
+        };
+        std::env::set_var("SCIP_ATOMS_NO_CACHE", "true");
+        let argv = vec![
+            "scip-atoms".to_string(),
+            "verify".to_string(),
+            ".".to_string(),
+        ];
+        let resolved = resolve_args(argv, &config);
+        std::env::remove_var("SCIP_ATOMS_NO_CACHE");
+
+        // The config default is "false" (spliced as no flag at all), then
+        // the env override splices a bare `--no-cache` after it.
+        assert_eq!(
+            resolved,
+            vec!["scip-atoms", "verify", "--no-cache", "."]
+        );
+    }
+
+    #[test]
+    fn describe_resolved_invocation_includes_the_precedence_order() {
+        let description = describe_resolved_invocation(&[
+            "scip-atoms".to_string(),
+            "verify".to_string(),
+            ".".to_string(),
+        ]);
+        assert!(description.contains("scip-atoms verify ."));
+        assert!(description.contains("scip-atoms.toml < environment variables < explicit CLI flags"));
+    }
+}
@@ -2,6 +2,11 @@
 //!
 //! This module centralizes magic numbers and configuration values
 //! to improve readability and maintainability.
+//!
+//! These are the built-in defaults a project's `probe_config.json`
+//! (`probe_config::ProbeConfig`) can override -- different indexer
+//! versions emit different `kind` numbers and symbol prefixes, so a
+//! project that needs different values isn't stuck recompiling.
 
 // =============================================================================
 // SCIP Symbol Kinds
@@ -47,17 +52,6 @@ pub fn is_definition(symbol_roles: Option<i32>) -> bool {
     symbol_roles.unwrap_or(0) & SYMBOL_ROLE_DEFINITION != 0
 }
 
-// =============================================================================
-// Matching Tolerances
-// =============================================================================
-
-/// Line number tolerance for matching functions between different tools.
-///
-/// verus-analyzer and verus_syn may report slightly different start lines
-/// due to differences in how they handle attributes and doc comments.
-/// This tolerance allows fuzzy matching within a reasonable range.
-pub const LINE_TOLERANCE: usize = 5;
-
 /// Number of lines to look back from a definition for type context.
 ///
 /// Used when collecting nearby type references to help disambiguate
@@ -77,6 +71,10 @@ pub const VERIFICATION_OUTPUT_FILE: &str = "verification_output.txt";
 /// Filename for cached verification configuration
 pub const VERIFICATION_CONFIG_FILE: &str = "verification_config.json";
 
+/// Filename for the last-accepted verification results snapshot, compared
+/// against on each `verify` run to gate on regressions.
+pub const VERIFICATION_BASELINE_FILE: &str = "verification_baseline.json";
+
 /// Filename for the SCIP index binary
 pub const SCIP_INDEX_FILE: &str = "index.scip";
 
@@ -0,0 +1,396 @@
+//! A first-class coverage-report subsystem: match a list of expected
+//! `(method, module, impl_block)` entries -- from a CSV file, an
+//! in-memory list, or anything else a caller assembles -- against a
+//! project's [`AtomWithLines`] output, producing a structured
+//! [`CoverageReport`] instead of only printed text.
+//!
+//! Matching is tiered, from strictest to most permissive:
+//!
+//! 1. [`MatchTier::Exact`]: `(display_name, module)` names exactly one
+//!    atom.
+//! 2. [`MatchTier::ImplAware`]: `(display_name, module)` names several
+//!    atoms (impls that share a method name), narrowed to one by
+//!    comparing the tracked entry's `impl_block` (e.g. `"Mul<&'b Scalar>
+//!    for RistrettoPoint"`) against each candidate's disambiguated
+//!    `scip_name`, which embeds the same Self type and trait generic args
+//!    -- so `Mul<&Scalar> for &RistrettoPoint` is told apart from
+//!    `Mul<&Scalar> for &RistrettoBasepointTable`.
+//! 3. [`MatchTier::Fuzzy`]: `display_name` matches and the module name
+//!    appears anywhere in `scip_name` -- the weakest, last-resort match.
+//!
+//! When more than one candidate survives every tier, the entry is
+//! [`AmbiguousEntry`] rather than silently resolved to the first
+//! candidate.
+
+use crate::AtomWithLines;
+use std::collections::HashMap;
+
+/// One expected function from a tracking source -- a CSV row, a hardcoded
+/// list, whatever a caller assembles.
+#[derive(Debug, Clone)]
+pub struct TrackedEntry {
+    /// Full function signature, e.g. `"Scalar::hash_from_bytes(&[u8])"`.
+    pub method: String,
+    /// Module path, e.g. `"curve25519_dalek::scalar"`.
+    pub module: String,
+    /// Impl block, e.g. `"Mul<&'b Scalar> for Scalar"`, empty if unknown.
+    pub impl_block: String,
+}
+
+impl TrackedEntry {
+    pub fn new(method: impl Into<String>, module: impl Into<String>, impl_block: impl Into<String>) -> Self {
+        TrackedEntry {
+            method: method.into(),
+            module: module.into(),
+            impl_block: impl_block.into(),
+        }
+    }
+
+    /// Just the method/function name, stripped of any `Type::` prefix and
+    /// parameter list, e.g. `"Scalar::hash_from_bytes(&[u8])"` ->
+    /// `"hash_from_bytes"`.
+    pub fn method_name(&self) -> &str {
+        let without_params = self.method.split('(').next().unwrap_or(&self.method);
+        match without_params.rfind("::") {
+            Some(pos) => &without_params[pos + 2..],
+            None => without_params,
+        }
+    }
+
+    /// The last component of the module path, e.g.
+    /// `"curve25519_dalek::scalar"` -> `"scalar"`.
+    pub fn module_name(&self) -> &str {
+        self.module.split("::").last().unwrap_or(&self.module)
+    }
+}
+
+/// Which tier resolved a [`TrackedEntry`] to a [`FoundEntry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchTier {
+    Exact,
+    ImplAware,
+    Fuzzy,
+}
+
+/// A tracked entry that resolved to exactly one atom, and which tier
+/// resolved it.
+#[derive(Debug, Clone)]
+pub struct FoundEntry {
+    pub tracked: TrackedEntry,
+    pub scip_name: String,
+    pub tier: MatchTier,
+}
+
+/// A tracked entry that matched more than one atom and couldn't be
+/// narrowed down any further.
+#[derive(Debug, Clone)]
+pub struct AmbiguousEntry {
+    pub tracked: TrackedEntry,
+    pub candidates: Vec<String>,
+}
+
+/// The structured result of checking a list of [`TrackedEntry`] against a
+/// project's atoms.
+#[derive(Debug, Clone)]
+pub struct CoverageReport {
+    pub found: Vec<FoundEntry>,
+    pub missing: Vec<TrackedEntry>,
+    pub ambiguous: Vec<AmbiguousEntry>,
+    pub coverage_pct: f64,
+}
+
+/// Reported by [`check_coverage_with_threshold`] when `coverage_pct` falls
+/// below the caller's required minimum.
+#[derive(Debug, Clone)]
+pub struct CoverageBelowThreshold {
+    pub report: CoverageReport,
+    pub min_coverage: f64,
+}
+
+impl std::fmt::Display for CoverageBelowThreshold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "coverage {:.1}% is below the required {:.1}%",
+            self.report.coverage_pct, self.min_coverage
+        )
+    }
+}
+
+impl std::error::Error for CoverageBelowThreshold {}
+
+/// Index atoms by `(display_name, module)` for the `Exact`/`ImplAware`
+/// tiers, keyed the same way [`TrackedEntry::method_name`]/
+/// [`TrackedEntry::module_name`] name them.
+fn index_atoms(atoms: &[AtomWithLines]) -> HashMap<(String, String), Vec<&AtomWithLines>> {
+    let mut index: HashMap<(String, String), Vec<&AtomWithLines>> = HashMap::new();
+    for atom in atoms {
+        if let Some(module) = module_from_scip_name(&atom.scip_name) {
+            index
+                .entry((atom.display_name.clone(), module))
+                .or_default()
+                .push(atom);
+        }
+    }
+    index
+}
+
+/// The last path component before the `#`-delimited type/method
+/// descriptors in a `scip_name`, e.g.
+/// `"curve25519-dalek 4.1.3 scalar/Scalar#hash_from_bytes()"` -> `"scalar"`.
+fn module_from_scip_name(scip_name: &str) -> Option<String> {
+    let path = scip_name.splitn(3, ' ').nth(2)?;
+    let path_parts: Vec<&str> = path.split('/').collect();
+    if let Some(last_dir) = path_parts.iter().rev().find(|p| !p.contains('#')) {
+        return Some(last_dir.to_string());
+    }
+    for part in &path_parts {
+        if let Some(pos) = part.find('#') {
+            return Some(part[..pos].to_string());
+        }
+    }
+    None
+}
+
+/// Normalize for substring comparison between a tracked entry's free-text
+/// `impl_block` and a `scip_name`'s descriptor syntax: lowercase, and drop
+/// everything that isn't alphanumeric (`&`, `'`, whitespace, punctuation),
+/// since `"RistrettoPoint"` and `"...#RistrettoPoint#Mul<&Scalar>..."`
+/// agree on content, not formatting.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// The Self type out of a free-text `impl_block` like `"Mul<&'b Scalar>
+/// for RistrettoBasepointTable"` -- the part after `" for "`, or the whole
+/// string for an inherent impl block with no trait (e.g. just
+/// `"Scalar"`). This is the discriminating piece: two impls of the same
+/// trait method almost always differ in Self, not in the trait's own
+/// generic arguments.
+fn self_type_of(impl_block: &str) -> &str {
+    match impl_block.rsplit_once(" for ") {
+        Some((_, self_part)) => self_part.trim(),
+        None => impl_block.trim(),
+    }
+}
+
+/// Narrow `candidates` to the one whose `scip_name` contains `impl_block`'s
+/// Self type, for impls that tie on `(display_name, module)` alone (e.g.
+/// several `Mul` impls for the same method name).
+fn narrow_by_impl_block<'a>(impl_block: &str, candidates: &[&'a AtomWithLines]) -> Vec<&'a AtomWithLines> {
+    let self_type = self_type_of(impl_block);
+    if self_type.is_empty() {
+        return Vec::new();
+    }
+    let needle = normalize(self_type);
+    candidates
+        .iter()
+        .copied()
+        .filter(|atom| normalize(&atom.scip_name).contains(&needle))
+        .collect()
+}
+
+/// Check `tracked` against `atoms`, tiered from strictest to most
+/// permissive, and record each entry as found (with its matching tier),
+/// ambiguous, or missing.
+pub fn check_coverage(tracked: &[TrackedEntry], atoms: &[AtomWithLines]) -> CoverageReport {
+    let index = index_atoms(atoms);
+
+    let mut found = Vec::new();
+    let mut missing = Vec::new();
+    let mut ambiguous = Vec::new();
+
+    for entry in tracked {
+        let key = (entry.method_name().to_string(), entry.module_name().to_string());
+        let empty: Vec<&AtomWithLines> = Vec::new();
+        let candidates = index.get(&key).unwrap_or(&empty);
+
+        match candidates.len() {
+            0 => {
+                let fuzzy: Vec<&AtomWithLines> = atoms
+                    .iter()
+                    .filter(|a| {
+                        a.display_name == entry.method_name() && a.scip_name.contains(entry.module_name())
+                    })
+                    .collect();
+                match fuzzy.len() {
+                    0 => missing.push(entry.clone()),
+                    1 => found.push(FoundEntry {
+                        tracked: entry.clone(),
+                        scip_name: fuzzy[0].scip_name.clone(),
+                        tier: MatchTier::Fuzzy,
+                    }),
+                    _ => ambiguous.push(AmbiguousEntry {
+                        tracked: entry.clone(),
+                        candidates: fuzzy.iter().map(|a| a.scip_name.clone()).collect(),
+                    }),
+                }
+            }
+            1 => {
+                found.push(FoundEntry {
+                    tracked: entry.clone(),
+                    scip_name: candidates[0].scip_name.clone(),
+                    tier: MatchTier::Exact,
+                });
+            }
+            _ => {
+                let narrowed = narrow_by_impl_block(&entry.impl_block, candidates);
+                if narrowed.len() == 1 {
+                    found.push(FoundEntry {
+                        tracked: entry.clone(),
+                        scip_name: narrowed[0].scip_name.clone(),
+                        tier: MatchTier::ImplAware,
+                    });
+                } else {
+                    ambiguous.push(AmbiguousEntry {
+                        tracked: entry.clone(),
+                        candidates: candidates.iter().map(|a| a.scip_name.clone()).collect(),
+                    });
+                }
+            }
+        }
+    }
+
+    let coverage_pct = if tracked.is_empty() {
+        100.0
+    } else {
+        100.0 * found.len() as f64 / tracked.len() as f64
+    };
+
+    CoverageReport {
+        found,
+        missing,
+        ambiguous,
+        coverage_pct,
+    }
+}
+
+/// [`check_coverage`], failing with [`CoverageBelowThreshold`] when the
+/// resulting `coverage_pct` is below `min_coverage` -- so CI can fail a
+/// build on a coverage regression instead of only reporting it.
+pub fn check_coverage_with_threshold(
+    tracked: &[TrackedEntry],
+    atoms: &[AtomWithLines],
+    min_coverage: f64,
+) -> Result<CoverageReport, CoverageBelowThreshold> {
+    let report = check_coverage(tracked, atoms);
+    if report.coverage_pct < min_coverage {
+        Err(CoverageBelowThreshold {
+            report,
+            min_coverage,
+        })
+    } else {
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CodeTextInfo;
+    use std::collections::HashSet;
+
+    fn atom(scip_name: &str, display_name: &str) -> AtomWithLines {
+        AtomWithLines {
+            display_name: display_name.to_string(),
+            scip_name: scip_name.to_string(),
+            dependencies: HashSet::new(),
+            ambiguous_dependencies: HashSet::new(),
+            code_path: "src/lib.rs".to_string(),
+            code_text: CodeTextInfo {
+                lines_start: 1,
+                lines_end: 2,
+            },
+        }
+    }
+
+    #[test]
+    fn exact_match_when_only_one_atom_shares_the_method_and_module() {
+        let atoms = vec![atom("curve25519-dalek 4.1.3 scalar/Scalar#hash_from_bytes()", "hash_from_bytes")];
+        let tracked = vec![TrackedEntry::new(
+            "Scalar::hash_from_bytes(&[u8])",
+            "curve25519_dalek::scalar",
+            "Scalar",
+        )];
+        let report = check_coverage(&tracked, &atoms);
+        assert_eq!(report.found.len(), 1);
+        assert_eq!(report.found[0].tier, MatchTier::Exact);
+        assert_eq!(report.coverage_pct, 100.0);
+    }
+
+    #[test]
+    fn impl_aware_match_distinguishes_impls_sharing_a_method_name() {
+        let atoms = vec![
+            atom(
+                "curve25519-dalek 4.1.3 ristretto/RistrettoPoint#Mul<&Scalar>#mul()",
+                "mul",
+            ),
+            atom(
+                "curve25519-dalek 4.1.3 ristretto/RistrettoBasepointTable#Mul<&Scalar>#mul()",
+                "mul",
+            ),
+        ];
+        let tracked = vec![TrackedEntry::new(
+            "mul(&Scalar)",
+            "curve25519_dalek::ristretto",
+            "Mul<&'b Scalar> for RistrettoBasepointTable",
+        )];
+        let report = check_coverage(&tracked, &atoms);
+        assert_eq!(report.found.len(), 1);
+        assert_eq!(report.found[0].tier, MatchTier::ImplAware);
+        assert!(report.found[0].scip_name.contains("RistrettoBasepointTable"));
+    }
+
+    #[test]
+    fn ambiguous_when_impl_block_does_not_narrow_to_one_candidate() {
+        let atoms = vec![
+            atom("curve25519-dalek 4.1.3 a/A#Mul<&Scalar>#mul()", "mul"),
+            atom("curve25519-dalek 4.1.3 a/B#Mul<&Scalar>#mul()", "mul"),
+        ];
+        let tracked = vec![TrackedEntry::new("mul(&Scalar)", "curve25519_dalek::a", "")];
+        let report = check_coverage(&tracked, &atoms);
+        assert!(report.found.is_empty());
+        assert_eq!(report.ambiguous.len(), 1);
+        assert_eq!(report.ambiguous[0].candidates.len(), 2);
+    }
+
+    #[test]
+    fn fuzzy_match_when_no_exact_index_entry_exists() {
+        let atoms = vec![atom("curve25519-dalek 4.1.3 montgomery/MontgomeryPoint#mul()", "mul")];
+        // "gomery" doesn't equal the atom's module segment ("montgomery"),
+        // so the exact index misses, but it's a substring of scip_name, so
+        // the fuzzy fallback still finds it.
+        let tracked = vec![TrackedEntry::new("mul(&Scalar)", "curve25519_dalek::gomery", "")];
+        let report = check_coverage(&tracked, &atoms);
+        assert_eq!(report.found.len(), 1);
+        assert_eq!(report.found[0].tier, MatchTier::Fuzzy);
+    }
+
+    #[test]
+    fn missing_when_nothing_matches() {
+        let atoms = vec![atom("curve25519-dalek 4.1.3 scalar/Scalar#invert()", "invert")];
+        let tracked = vec![TrackedEntry::new("nonexistent()", "curve25519_dalek::scalar", "")];
+        let report = check_coverage(&tracked, &atoms);
+        assert_eq!(report.missing.len(), 1);
+        assert_eq!(report.coverage_pct, 0.0);
+    }
+
+    #[test]
+    fn threshold_check_fails_below_the_minimum() {
+        let atoms: Vec<AtomWithLines> = Vec::new();
+        let tracked = vec![TrackedEntry::new("missing()", "m", "")];
+        let result = check_coverage_with_threshold(&tracked, &atoms, 50.0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn threshold_check_passes_at_or_above_the_minimum() {
+        let atoms = vec![atom("curve25519-dalek 4.1.3 scalar/Scalar#invert()", "invert")];
+        let tracked = vec![TrackedEntry::new("invert()", "curve25519_dalek::scalar", "Scalar")];
+        let result = check_coverage_with_threshold(&tracked, &atoms, 100.0);
+        assert!(result.is_ok());
+    }
+}
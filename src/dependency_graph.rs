@@ -0,0 +1,396 @@
+//! Directed dependency graph over `AtomWithLines::scip_name`s.
+//!
+//! `AtomWithLines::dependencies` records each atom's outgoing edges, but
+//! nothing assembles them into a graph a caller can actually query.
+//! [`DependencyGraph::build`] does that, and offers strongly-connected-
+//! component detection (via Tarjan's algorithm, surfacing recursive and
+//! mutually-recursive cycles), transitive-closure reachability, and a
+//! topological ordering of the acyclic condensation (the graph of SCCs).
+//! A dependency pointing outside the atom set (an external function, the
+//! `"unknown"` branch `symbol_to_scip_name` falls back to when a callee
+//! isn't a project definition) is kept as a leaf node rather than dropped,
+//! so external dependencies still show up in reachability and SCC output.
+//! [`reverse_dependency_index`] answers the opposite question -- "who
+//! calls this" -- by inverting the same `dependencies` sets.
+
+use crate::AtomWithLines;
+use std::collections::{HashMap, HashSet};
+
+/// Invert `AtomWithLines::dependencies` into a reverse index: for every
+/// `scip_name` a dependency names, the set of `scip_name`s that depend on
+/// it -- "who calls this function", rather than "what does this function
+/// call". An external callee (a dependency that isn't any atom's own
+/// `scip_name`) still gets an entry, since the index is keyed by whatever
+/// string `dependencies` names regardless of whether an `AtomWithLines`
+/// defines it. Keyed by the same (already disambiguated) `scip_name`s the
+/// forward `dependencies` sets use, so the two views agree on identity.
+pub fn reverse_dependency_index(atoms: &[AtomWithLines]) -> HashMap<String, HashSet<String>> {
+    let mut callers: HashMap<String, HashSet<String>> = HashMap::new();
+    for atom in atoms {
+        for dep in &atom.dependencies {
+            callers
+                .entry(dep.clone())
+                .or_default()
+                .insert(atom.scip_name.clone());
+        }
+    }
+    callers
+}
+
+/// Fan-in (distinct callers) and fan-out (distinct callees) for one
+/// `scip_name`, computed by [`call_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CallStats {
+    pub fan_in: usize,
+    pub fan_out: usize,
+}
+
+/// Fan-in/fan-out for every `scip_name` named in `atoms`, including
+/// external dependencies that are only ever a callee (fan-out stays 0 for
+/// those since they have no recorded `dependencies` of their own). One
+/// pass over `atoms` builds both the forward and reverse (via
+/// [`reverse_dependency_index`]) views, so fan-in and fan-out are each
+/// just a set length, not a re-scan.
+///
+/// Found by a real incident where a heavy helper was unknowingly called
+/// from inside a loop across many call sites and silently ballooned
+/// verification cost -- a fan-in report surfaces that function immediately
+/// as a high-impact target, before anyone has to notice the slowdown.
+pub fn call_stats(atoms: &[AtomWithLines]) -> HashMap<String, CallStats> {
+    let callers = reverse_dependency_index(atoms);
+    let mut stats: HashMap<String, CallStats> = HashMap::new();
+
+    for atom in atoms {
+        stats.entry(atom.scip_name.clone()).or_default().fan_out = atom.dependencies.len();
+    }
+    for (callee, its_callers) in &callers {
+        stats.entry(callee.clone()).or_default().fan_in = its_callers.len();
+    }
+
+    stats
+}
+
+/// Rank `stats` by fan-in, highest first, for surfacing the widely-called
+/// helpers that dominate a codebase's call graph.
+pub fn rank_by_fan_in(stats: &HashMap<String, CallStats>) -> Vec<(String, CallStats)> {
+    let mut ranked: Vec<(String, CallStats)> =
+        stats.iter().map(|(name, s)| (name.clone(), *s)).collect();
+    ranked.sort_by(|a, b| b.1.fan_in.cmp(&a.1.fan_in).then_with(|| a.0.cmp(&b.0)));
+    ranked
+}
+
+/// Look up `name`'s node index, inserting it as a new node first if this
+/// is the first time it's been seen.
+fn node_index(name: &str, index: &mut HashMap<String, usize>, nodes: &mut Vec<String>) -> usize {
+    if let Some(&idx) = index.get(name) {
+        return idx;
+    }
+    let idx = nodes.len();
+    nodes.push(name.to_string());
+    index.insert(name.to_string(), idx);
+    idx
+}
+
+/// A directed graph over `scip_name`s, built from every atom's recorded
+/// `dependencies`. Nodes named only as a dependency target (never an
+/// atom's own `scip_name`) are kept as edge-free leaves.
+pub struct DependencyGraph {
+    nodes: Vec<String>,
+    index: HashMap<String, usize>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl DependencyGraph {
+    /// Assemble the graph from a set of atoms: every atom is a node, every
+    /// entry in its `dependencies` is an edge to (possibly first
+    /// introducing) another node.
+    pub fn build(atoms: &[AtomWithLines]) -> Self {
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut nodes: Vec<String> = Vec::new();
+
+        for atom in atoms {
+            node_index(&atom.scip_name, &mut index, &mut nodes);
+        }
+        for atom in atoms {
+            for dep in &atom.dependencies {
+                node_index(dep, &mut index, &mut nodes);
+            }
+        }
+
+        let mut edges: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        for atom in atoms {
+            let from = index[&atom.scip_name];
+            for dep in &atom.dependencies {
+                edges[from].push(index[dep]);
+            }
+        }
+
+        DependencyGraph {
+            nodes,
+            index,
+            edges,
+        }
+    }
+
+    /// Every `scip_name` transitively reachable from `start` (not including
+    /// `start` itself), following dependency edges forward. Returns `None`
+    /// if `start` isn't a node in the graph.
+    pub fn reachable_from(&self, start: &str) -> Option<HashSet<String>> {
+        let start_idx = *self.index.get(start)?;
+        let mut visited: HashSet<usize> = HashSet::new();
+        let mut stack = vec![start_idx];
+        while let Some(idx) = stack.pop() {
+            for &next in &self.edges[idx] {
+                if visited.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        visited.remove(&start_idx);
+        Some(visited.into_iter().map(|idx| self.nodes[idx].clone()).collect())
+    }
+
+    /// Every strongly-connected component with more than one member, or a
+    /// single self-referential node -- i.e. every genuine cycle, recursive
+    /// or mutually-recursive -- found via Tarjan's algorithm.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        self.strongly_connected_components()
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || {
+                    let idx = self.index[&component[0]];
+                    self.edges[idx].contains(&idx)
+                }
+            })
+            .collect()
+    }
+
+    /// All strongly-connected components, each rendered as a list of
+    /// `scip_name`s, via Tarjan's algorithm. Includes singleton components
+    /// for every node not part of a larger cycle.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<String>> {
+        let mut tarjan = Tarjan::new(self.nodes.len());
+        for idx in 0..self.nodes.len() {
+            if tarjan.index[idx].is_none() {
+                tarjan.strong_connect(idx, &self.edges);
+            }
+        }
+        tarjan
+            .components
+            .into_iter()
+            .map(|component| component.into_iter().map(|idx| self.nodes[idx].clone()).collect())
+            .collect()
+    }
+
+    /// A topological ordering of the acyclic condensation: each entry is
+    /// one strongly-connected component (a single node for any acyclic
+    /// part of the graph), ordered so that every component appears before
+    /// any component that depends on it -- the order you'd want to process
+    /// dependencies in, leaves first.
+    pub fn topological_order(&self) -> Vec<Vec<String>> {
+        // Tarjan's algorithm only finishes (and records) a node's
+        // component once every component reachable from it has already
+        // finished, so the components it already emits, in emission
+        // order, are dependencies-first -- no reordering needed.
+        self.strongly_connected_components()
+    }
+}
+
+/// Mutable working state for Tarjan's strongly-connected-components
+/// algorithm, kept separate from [`DependencyGraph`] so the graph itself
+/// stays immutable during the walk.
+struct Tarjan {
+    index: Vec<Option<usize>>,
+    lowlink: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next_index: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan {
+    fn new(node_count: usize) -> Self {
+        Tarjan {
+            index: vec![None; node_count],
+            lowlink: vec![0; node_count],
+            on_stack: vec![false; node_count],
+            stack: Vec::new(),
+            next_index: 0,
+            components: Vec::new(),
+        }
+    }
+
+    /// Iterative Tarjan's algorithm rooted at `start`, explicit-stack to
+    /// avoid recursion depth limits on a deep dependency chain.
+    fn strong_connect(&mut self, start: usize, edges: &[Vec<usize>]) {
+        // Each explicit-stack frame tracks which outgoing edge to resume
+        // from, since an iterative DFS can't rely on the call stack to
+        // remember it.
+        let mut call_stack: Vec<(usize, usize)> = vec![(start, 0)];
+        self.visit(start);
+
+        while let Some(&(node, edge_pos)) = call_stack.last() {
+            if edge_pos < edges[node].len() {
+                let next = edges[node][edge_pos];
+                call_stack.last_mut().unwrap().1 += 1;
+                if self.index[next].is_none() {
+                    self.visit(next);
+                    call_stack.push((next, 0));
+                } else if self.on_stack[next] {
+                    self.lowlink[node] = self.lowlink[node].min(self.index[next].unwrap());
+                }
+                continue;
+            }
+
+            call_stack.pop();
+            if let Some(&(parent, _)) = call_stack.last() {
+                self.lowlink[parent] = self.lowlink[parent].min(self.lowlink[node]);
+            }
+
+            if self.lowlink[node] == self.index[node].unwrap() {
+                let mut component = Vec::new();
+                loop {
+                    let member = self.stack.pop().unwrap();
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    fn visit(&mut self, node: usize) {
+        self.index[node] = Some(self.next_index);
+        self.lowlink[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CodeTextInfo;
+
+    fn atom(scip_name: &str, deps: &[&str]) -> AtomWithLines {
+        AtomWithLines {
+            display_name: scip_name.to_string(),
+            scip_name: scip_name.to_string(),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            ambiguous_dependencies: HashSet::new(),
+            code_path: "src/lib.rs".to_string(),
+            code_text: CodeTextInfo {
+                lines_start: 1,
+                lines_end: 2,
+            },
+        }
+    }
+
+    #[test]
+    fn reverse_index_answers_who_calls_this() {
+        let atoms = vec![
+            atom("a", &["c"]),
+            atom("b", &["c"]),
+            atom("c", &["external::unknown"]),
+        ];
+        let callers = reverse_dependency_index(&atoms);
+        assert_eq!(
+            callers.get("c").cloned().unwrap_or_default(),
+            ["a", "b"].into_iter().map(String::from).collect()
+        );
+        assert_eq!(
+            callers.get("external::unknown").cloned().unwrap_or_default(),
+            ["c"].into_iter().map(String::from).collect()
+        );
+        assert!(callers.get("a").is_none());
+    }
+
+    #[test]
+    fn external_dependencies_are_kept_as_leaf_nodes() {
+        let atoms = vec![atom("a", &["external::unknown"])];
+        let graph = DependencyGraph::build(&atoms);
+        assert_eq!(graph.nodes.len(), 2);
+        let reachable = graph.reachable_from("a").unwrap();
+        assert!(reachable.contains("external::unknown"));
+    }
+
+    #[test]
+    fn reachable_from_follows_transitive_edges() {
+        let atoms = vec![atom("a", &["b"]), atom("b", &["c"]), atom("c", &[])];
+        let graph = DependencyGraph::build(&atoms);
+        let reachable = graph.reachable_from("a").unwrap();
+        assert_eq!(reachable, ["b", "c"].into_iter().map(String::from).collect());
+        assert!(graph.reachable_from("c").unwrap().is_empty());
+        assert!(graph.reachable_from("missing").is_none());
+    }
+
+    #[test]
+    fn detects_a_direct_cycle() {
+        let atoms = vec![atom("a", &["b"]), atom("b", &["a"])];
+        let graph = DependencyGraph::build(&atoms);
+        let cycles = graph.cycles();
+        assert_eq!(cycles.len(), 1);
+        let mut members = cycles[0].clone();
+        members.sort();
+        assert_eq!(members, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn detects_direct_self_recursion() {
+        let atoms = vec![atom("a", &["a"])];
+        let graph = DependencyGraph::build(&atoms);
+        let cycles = graph.cycles();
+        assert_eq!(cycles, vec![vec!["a".to_string()]]);
+    }
+
+    #[test]
+    fn acyclic_graph_has_no_cycles() {
+        let atoms = vec![atom("a", &["b"]), atom("b", &[])];
+        let graph = DependencyGraph::build(&atoms);
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn call_stats_counts_distinct_callers_and_callees() {
+        let atoms = vec![
+            atom("helper", &[]),
+            atom("a", &["helper"]),
+            atom("b", &["helper"]),
+            atom("c", &["helper", "a"]),
+        ];
+        let stats = call_stats(&atoms);
+        assert_eq!(stats["helper"].fan_in, 3);
+        assert_eq!(stats["helper"].fan_out, 0);
+        assert_eq!(stats["c"].fan_out, 2);
+        assert_eq!(stats["c"].fan_in, 0);
+    }
+
+    #[test]
+    fn rank_by_fan_in_puts_the_most_called_function_first() {
+        let atoms = vec![
+            atom("helper", &[]),
+            atom("a", &["helper"]),
+            atom("b", &["helper"]),
+            atom("c", &["helper", "a"]),
+        ];
+        let stats = call_stats(&atoms);
+        let ranked = rank_by_fan_in(&stats);
+        assert_eq!(ranked[0].0, "helper");
+        assert_eq!(ranked[0].1.fan_in, 3);
+    }
+
+    #[test]
+    fn topological_order_puts_dependencies_before_dependents() {
+        let atoms = vec![atom("a", &["b"]), atom("b", &["c"]), atom("c", &[])];
+        let graph = DependencyGraph::build(&atoms);
+        let order = graph.topological_order();
+        let flat: Vec<&String> = order.iter().flatten().collect();
+        let position = |name: &str| flat.iter().position(|n| n.as_str() == name).unwrap();
+        assert!(position("c") < position("b"));
+        assert!(position("b") < position("a"));
+    }
+}
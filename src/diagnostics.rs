@@ -0,0 +1,160 @@
+//! Env-var-gated diagnostic tracing for call-graph construction and
+//! symbol disambiguation (`build_call_graph`, `convert_to_atoms_with_lines`).
+//!
+//! Three independent flags, each read once into [`Diagnostics::global`]:
+//!
+//! - `SCIP_ATOMS_TRACE_DISAMBIG` -- a line-number suffix was appended to
+//!   break a `scip_name` tie that a discriminating type couldn't resolve.
+//! - `SCIP_ATOMS_TRACE_SELFTYPE` -- two entries share a raw symbol but are
+//!   kept distinct by `self_type` or a type parameter.
+//! - `SCIP_ATOMS_TRACE_DUPES` -- `find_duplicate_scip_names` reports a
+//!   collision.
+//!
+//! Each enabled event is one JSON object on stderr, so two runs' traces
+//! can be diffed when upstream SCIP data changes -- the same pattern as a
+//! compiler's `-Z`-style debug flags that print unification/specialization
+//! steps on demand.
+
+use serde::Serialize;
+use std::sync::OnceLock;
+
+const ENV_TRACE_DISAMBIG: &str = "SCIP_ATOMS_TRACE_DISAMBIG";
+const ENV_TRACE_SELFTYPE: &str = "SCIP_ATOMS_TRACE_SELFTYPE";
+const ENV_TRACE_DUPES: &str = "SCIP_ATOMS_TRACE_DUPES";
+
+/// Which trace flags are enabled, read once from the environment.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Diagnostics {
+    pub trace_disambig: bool,
+    pub trace_selftype: bool,
+    pub trace_dupes: bool,
+}
+
+impl Diagnostics {
+    fn from_env() -> Self {
+        Diagnostics {
+            trace_disambig: env_flag_set(ENV_TRACE_DISAMBIG),
+            trace_selftype: env_flag_set(ENV_TRACE_SELFTYPE),
+            trace_dupes: env_flag_set(ENV_TRACE_DUPES),
+        }
+    }
+
+    /// The process-wide flags, read from the environment on first use and
+    /// cached for the rest of the run.
+    pub fn global() -> &'static Diagnostics {
+        static DIAGNOSTICS: OnceLock<Diagnostics> = OnceLock::new();
+        DIAGNOSTICS.get_or_init(Diagnostics::from_env)
+    }
+}
+
+fn env_flag_set(var: &str) -> bool {
+    std::env::var(var)
+        .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// One traced event: a symbol that went through disambiguation, a
+/// duplicate collision, or a self-type tie-break. Fields that don't apply
+/// to a given event are omitted rather than emitted as `null`.
+#[derive(Serialize)]
+struct TraceEvent<'a> {
+    event: &'a str,
+    symbol: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    self_type: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature_text: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scip_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+}
+
+fn emit(
+    event: &str,
+    symbol: &str,
+    self_type: Option<&str>,
+    signature_text: Option<&str>,
+    scip_name: Option<&str>,
+    line: Option<usize>,
+) {
+    let record = TraceEvent {
+        event,
+        symbol,
+        self_type,
+        signature_text,
+        scip_name,
+        line,
+    };
+    if let Ok(json) = serde_json::to_string(&record) {
+        eprintln!("{json}");
+    }
+}
+
+/// Trace a `scip_name` tie broken by appending a source line number,
+/// gated on `SCIP_ATOMS_TRACE_DISAMBIG`.
+pub fn trace_disambig(symbol: &str, self_type: Option<&str>, signature_text: &str, scip_name: &str, line: usize) {
+    if !Diagnostics::global().trace_disambig {
+        return;
+    }
+    emit(
+        "line-disambig",
+        symbol,
+        self_type,
+        Some(signature_text),
+        Some(scip_name),
+        Some(line),
+    );
+}
+
+/// Trace two entries sharing a raw symbol that were kept distinct by
+/// `self_type` or a type parameter, gated on `SCIP_ATOMS_TRACE_SELFTYPE`.
+pub fn trace_selftype(symbol: &str, self_type: Option<&str>, signature_text: &str, scip_name: &str) {
+    if !Diagnostics::global().trace_selftype {
+        return;
+    }
+    emit("selftype-disambig", symbol, self_type, Some(signature_text), Some(scip_name), None);
+}
+
+/// Trace a `scip_name` collision `find_duplicate_scip_names` would report,
+/// gated on `SCIP_ATOMS_TRACE_DUPES`.
+pub fn trace_dupe(symbol: &str, scip_name: &str) {
+    if !Diagnostics::global().trace_dupes {
+        return;
+    }
+    emit("dupe", symbol, None, None, Some(scip_name), None);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_flag_set_accepts_common_truthy_spellings() {
+        std::env::set_var("SCIP_ATOMS_TRACE_TEST_TRUTHY", "TRUE");
+        assert!(env_flag_set("SCIP_ATOMS_TRACE_TEST_TRUTHY"));
+        std::env::remove_var("SCIP_ATOMS_TRACE_TEST_TRUTHY");
+    }
+
+    #[test]
+    fn env_flag_unset_is_false() {
+        std::env::remove_var("SCIP_ATOMS_TRACE_TEST_UNSET");
+        assert!(!env_flag_set("SCIP_ATOMS_TRACE_TEST_UNSET"));
+    }
+
+    #[test]
+    fn trace_event_omits_absent_fields() {
+        let event = TraceEvent {
+            event: "dupe",
+            symbol: "foo#",
+            self_type: None,
+            signature_text: None,
+            scip_name: Some("foo#"),
+            line: None,
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(!json.contains("self_type"));
+        assert!(!json.contains("line"));
+        assert!(json.contains("\"scip_name\":\"foo#\""));
+    }
+}
@@ -0,0 +1,204 @@
+//! Structured diff between two verification runs.
+//!
+//! Comparing a stored "golden" transcript against a fresh run as raw text
+//! only answers pass/fail. [`VerificationDiff::compute`] instead compares the
+//! already-parsed [`VerificationFailure`] lists and reports exactly what
+//! changed, keyed by `(file, line, error_type)` so CI can gate on *new*
+//! failures (regressions) while tolerating pre-existing ones whose message
+//! text shifted.
+
+use crate::verification::VerificationFailure;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Key identifying the same logical failure across two runs.
+type FailureKey = (Option<String>, Option<i32>, String);
+
+fn key_for(failure: &VerificationFailure) -> FailureKey {
+    (
+        failure.file.clone(),
+        failure.line,
+        failure.error_type.clone(),
+    )
+}
+
+/// A failure present in both runs under the same key, but with a different
+/// message (e.g. the assertion text shifted even though it's the same site).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedFailure {
+    pub before: VerificationFailure,
+    pub after: VerificationFailure,
+}
+
+/// The delta between a "before" and "after" set of verification failures.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct VerificationDiff {
+    /// Failures present in `after` but not `before`.
+    pub added: Vec<VerificationFailure>,
+    /// Failures present in `before` but not `after`.
+    pub removed: Vec<VerificationFailure>,
+    /// Failures present in both, with a different message.
+    pub changed: Vec<ChangedFailure>,
+}
+
+impl VerificationDiff {
+    /// Compute the diff between a golden (`before`) and fresh (`after`) run.
+    pub fn compute(before: &[VerificationFailure], after: &[VerificationFailure]) -> Self {
+        let before_by_key: BTreeMap<FailureKey, &VerificationFailure> =
+            before.iter().map(|f| (key_for(f), f)).collect();
+        let after_by_key: BTreeMap<FailureKey, &VerificationFailure> =
+            after.iter().map(|f| (key_for(f), f)).collect();
+
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+        for (key, after_failure) in &after_by_key {
+            match before_by_key.get(key) {
+                None => added.push((*after_failure).clone()),
+                Some(before_failure) => {
+                    if before_failure.message != after_failure.message {
+                        changed.push(ChangedFailure {
+                            before: (*before_failure).clone(),
+                            after: (*after_failure).clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        let removed = before_by_key
+            .iter()
+            .filter(|(key, _)| !after_by_key.contains_key(*key))
+            .map(|(_, f)| (*f).clone())
+            .collect();
+
+        Self {
+            added,
+            removed,
+            changed,
+        }
+    }
+
+    /// Whether any *new* failures appeared. This is the signal CI regression
+    /// gating cares about -- pre-existing failures that merely moved or
+    /// reworded don't count.
+    pub fn has_regressions(&self) -> bool {
+        !self.added.is_empty()
+    }
+
+    /// Render a unified-diff-style summary grouped by file, with `-`/`+`/`~`
+    /// markers colored red/green/yellow via raw ANSI escapes.
+    pub fn render_colored(&self) -> String {
+        const RED: &str = "\x1b[31m";
+        const GREEN: &str = "\x1b[32m";
+        const YELLOW: &str = "\x1b[33m";
+        const BOLD: &str = "\x1b[1m";
+        const RESET: &str = "\x1b[0m";
+
+        let mut by_file: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for failure in &self.removed {
+            by_file
+                .entry(file_label(failure.file.as_deref()))
+                .or_default()
+                .push(format!(
+                    "{RED}-{RESET} line {}: {} [{}]",
+                    line_label(failure.line),
+                    failure.message,
+                    failure.error_type
+                ));
+        }
+        for failure in &self.added {
+            by_file
+                .entry(file_label(failure.file.as_deref()))
+                .or_default()
+                .push(format!(
+                    "{GREEN}+{RESET} line {}: {} [{}]",
+                    line_label(failure.line),
+                    failure.message,
+                    failure.error_type
+                ));
+        }
+        for change in &self.changed {
+            by_file
+                .entry(file_label(change.after.file.as_deref()))
+                .or_default()
+                .push(format!(
+                    "{YELLOW}~{RESET} line {}: {} -> {} [{}]",
+                    line_label(change.after.line),
+                    change.before.message,
+                    change.after.message,
+                    change.after.error_type
+                ));
+        }
+
+        let mut out = String::new();
+        for (file, lines) in &by_file {
+            out.push_str(&format!("{BOLD}{file}{RESET}\n"));
+            for line in lines {
+                out.push_str("  ");
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+fn file_label(file: Option<&str>) -> String {
+    file.unwrap_or("<unknown file>").to_string()
+}
+
+fn line_label(line: Option<i32>) -> String {
+    line.map(|l| l.to_string()).unwrap_or_else(|| "?".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(file: &str, line: i32, error_type: &str, message: &str) -> VerificationFailure {
+        VerificationFailure {
+            error_type: error_type.to_string(),
+            file: Some(file.to_string()),
+            line: Some(line),
+            column: None,
+            message: message.to_string(),
+            assertion_details: Vec::new(),
+            full_error_text: String::new(),
+        }
+    }
+
+    #[test]
+    fn detects_added_and_removed_failures() {
+        let before = vec![failure("a.rs", 1, "assertion failed", "x")];
+        let after = vec![failure("b.rs", 2, "assertion failed", "y")];
+
+        let diff = VerificationDiff::compute(&before, &after);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.removed.len(), 1);
+        assert!(diff.changed.is_empty());
+        assert!(diff.has_regressions());
+    }
+
+    #[test]
+    fn detects_changed_message_at_same_site() {
+        let before = vec![failure("a.rs", 1, "assertion failed", "old message")];
+        let after = vec![failure("a.rs", 1, "assertion failed", "new message")];
+
+        let diff = VerificationDiff::compute(&before, &after);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        assert!(!diff.has_regressions());
+    }
+
+    #[test]
+    fn identical_runs_produce_empty_diff() {
+        let failures = vec![failure("a.rs", 1, "assertion failed", "x")];
+        let diff = VerificationDiff::compute(&failures, &failures);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert!(diff.changed.is_empty());
+        assert!(!diff.has_regressions());
+    }
+}
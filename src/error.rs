@@ -25,6 +25,13 @@ pub enum ProbeError {
         source: std::io::Error,
     },
 
+    /// I/O error with no path context available, e.g. a `?` conversion from
+    /// a generic `std::io::Error`. Prefer [`ProbeError::file_io`] wherever a
+    /// path is available; this exists so library callers aren't forced to
+    /// invent one.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// JSON serialization/deserialization error
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
@@ -82,6 +89,65 @@ impl ProbeError {
             message: message.into(),
         }
     }
+
+    /// The process exit code a CLI wrapper should use for this error, so the
+    /// mapping from error variant to exit status lives in one place instead
+    /// of being re-derived at every `std::process::exit` call site.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ProbeError::ScipParse(_) | ProbeError::Json(_) | ProbeError::SourceParse { .. } => 2,
+            ProbeError::InvalidSymbol { .. } => 3,
+            ProbeError::FileIo { .. } | ProbeError::Io(_) => 4,
+            ProbeError::ProjectValidation(_) => 5,
+            ProbeError::DuplicateCodeNames { .. } => 6,
+            ProbeError::ExternalTool { .. } => 7,
+            ProbeError::Verification(_) => 8,
+        }
+    }
+
+    /// This variant's tag, for [`ProbeError::to_json`] and anywhere else
+    /// that needs a stable machine-readable name rather than a Debug dump.
+    fn tag(&self) -> &'static str {
+        match self {
+            ProbeError::ScipParse(_) => "scip_parse",
+            ProbeError::InvalidSymbol { .. } => "invalid_symbol",
+            ProbeError::FileIo { .. } => "file_io",
+            ProbeError::Io(_) => "io",
+            ProbeError::Json(_) => "json",
+            ProbeError::SourceParse { .. } => "source_parse",
+            ProbeError::ProjectValidation(_) => "project_validation",
+            ProbeError::DuplicateCodeNames { .. } => "duplicate_code_names",
+            ProbeError::ExternalTool { .. } => "external_tool",
+            ProbeError::Verification(_) => "verification",
+        }
+    }
+
+    /// Serialize this error for `--error-format=json`: a variant tag, the
+    /// `Display` message, and whichever path/symbol context fields the
+    /// variant already carries.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut fields = serde_json::Map::new();
+        fields.insert("error".to_string(), self.tag().into());
+        fields.insert("message".to_string(), self.to_string().into());
+
+        match self {
+            ProbeError::InvalidSymbol { symbol, .. } => {
+                fields.insert("symbol".to_string(), symbol.as_str().into());
+            }
+            ProbeError::FileIo { path, .. } | ProbeError::SourceParse { path, .. } => {
+                fields.insert("path".to_string(), path.display().to_string().into());
+            }
+            ProbeError::DuplicateCodeNames { names, .. } => {
+                fields.insert("names".to_string(), names.clone().into());
+            }
+            ProbeError::ExternalTool { tool, .. } => {
+                fields.insert("tool".to_string(), tool.as_str().into());
+            }
+            _ => {}
+        }
+
+        serde_json::Value::Object(fields)
+    }
 }
 
 /// Result type alias for probe-verus operations.
@@ -110,4 +176,20 @@ mod tests {
         let probe_err: ProbeError = json_err.unwrap_err().into();
         assert!(matches!(probe_err, ProbeError::Json(_)));
     }
+
+    #[test]
+    fn test_exit_code_distinguishes_variants() {
+        let io = ProbeError::file_io("atoms.json", std::io::Error::other("boom"));
+        let validation = ProbeError::ProjectValidation("Cargo.toml not found".to_string());
+        assert_ne!(io.exit_code(), validation.exit_code());
+    }
+
+    #[test]
+    fn test_to_json_carries_variant_tag_and_context() {
+        let err = ProbeError::invalid_symbol("missing prefix", "bad_symbol");
+        let json = err.to_json();
+        assert_eq!(json["error"], "invalid_symbol");
+        assert_eq!(json["symbol"], "bad_symbol");
+        assert!(json["message"].as_str().unwrap().contains("missing prefix"));
+    }
 }
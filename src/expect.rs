@@ -0,0 +1,484 @@
+//! Inline expected-verification-failure annotations, ui_test style.
+//!
+//! A source file can assert which verification failures it's expected to
+//! produce with a trailing comment on the offending line:
+//! ```text
+//! let x = a / b; //~ VERIFY-FAIL postcondition not satisfied
+//! ```
+//! When the failure attributes to a line above the annotation (e.g. inside a
+//! macro expansion), `//~^` points one line up, `//~^^` two lines up, and so
+//! on -- one `^` per line. [`check_file`] reconciles these annotations
+//! against [`VerificationFailure`]s from [`crate::verification::VerificationParser`]:
+//! every annotation must match a failure at its resolved line whose message
+//! contains the given substring, and every failure must be covered by some
+//! annotation. Anything left over on either side is reported as a [`Mismatch`].
+
+use crate::verification::{
+    CodeTextInfo, FunctionCategory, FunctionLocation, VerificationFailure,
+    VERIFICATION_ERROR_TYPES,
+};
+use crate::verus_parser::FunctionInfo;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// An expected failure parsed from a `//~` annotation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedFailure {
+    /// 1-based source line the annotation resolves to, after applying `^` shifts.
+    pub line: usize,
+    pub error_type_substring: String,
+}
+
+/// A single reconciliation problem between expectations and actual failures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// An annotation whose resolved line/substring matched no actual failure.
+    MissingFailure {
+        line: usize,
+        error_type_substring: String,
+    },
+    /// An actual failure not covered by any `//~` annotation.
+    UnexpectedFailure { line: Option<i32>, message: String },
+}
+
+/// How to handle a mismatch between expectations and actual verification output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputConflictHandling {
+    /// Report mismatches back to the caller (the default, for CI).
+    #[default]
+    Error,
+    /// Rewrite the `//~` annotations in place to match the actual failures,
+    /// so expectations can be regenerated after an intentional change.
+    Bless,
+}
+
+/// Scan `source` for `//~`/`//~^...` annotations.
+pub fn parse_annotations(source: &str) -> Vec<ExpectedFailure> {
+    let mut expectations = Vec::new();
+
+    for (idx, line) in source.lines().enumerate() {
+        let Some(marker_pos) = line.find("//~") else {
+            continue;
+        };
+        let rest = &line[marker_pos + 3..];
+
+        let carets = rest.chars().take_while(|&c| c == '^').count();
+        let rest = rest[carets..].trim_start();
+
+        let Some(substring) = rest.strip_prefix("VERIFY-FAIL") else {
+            continue;
+        };
+
+        // Annotation lines are 1-based; each leading `^` shifts the target up
+        // by one line from the annotation's own line.
+        let annotation_line = idx + 1;
+        let target_line = annotation_line.saturating_sub(carets);
+
+        expectations.push(ExpectedFailure {
+            line: target_line,
+            error_type_substring: substring.trim().to_string(),
+        });
+    }
+
+    expectations
+}
+
+/// Reconcile `expected` annotations against `actual` verification failures.
+pub fn reconcile(expected: &[ExpectedFailure], actual: &[VerificationFailure]) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    let mut matched = vec![false; actual.len()];
+
+    for exp in expected {
+        let hit = actual
+            .iter()
+            .enumerate()
+            .find(|(i, f)| {
+                !matched[*i]
+                    && f.line == Some(exp.line as i32)
+                    && f.message.contains(&exp.error_type_substring)
+            })
+            .map(|(i, _)| i);
+
+        match hit {
+            Some(i) => matched[i] = true,
+            None => mismatches.push(Mismatch::MissingFailure {
+                line: exp.line,
+                error_type_substring: exp.error_type_substring.clone(),
+            }),
+        }
+    }
+
+    for (i, failure) in actual.iter().enumerate() {
+        if !matched[i] {
+            mismatches.push(Mismatch::UnexpectedFailure {
+                line: failure.line,
+                message: failure.message.clone(),
+            });
+        }
+    }
+
+    mismatches
+}
+
+/// Read `path`, reconcile its `//~` annotations against `actual`, and apply
+/// `handling`. Under [`OutputConflictHandling::Bless`], mismatches are never
+/// returned: the file is rewritten to match `actual` instead.
+pub fn check_file(
+    path: &Path,
+    actual: &[VerificationFailure],
+    handling: OutputConflictHandling,
+) -> std::io::Result<Vec<Mismatch>> {
+    let source = std::fs::read_to_string(path)?;
+    let expected = parse_annotations(&source);
+    let mismatches = reconcile(&expected, actual);
+
+    if handling == OutputConflictHandling::Bless && !mismatches.is_empty() {
+        bless(path, &source, actual)?;
+        return Ok(Vec::new());
+    }
+
+    Ok(mismatches)
+}
+
+/// Strip every existing `//~`/`//~^...` annotation from `source`, then append
+/// a fresh one to each line `actual` reports a failure on.
+fn bless(path: &Path, source: &str, actual: &[VerificationFailure]) -> std::io::Result<()> {
+    let mut lines: Vec<String> = source
+        .lines()
+        .map(|line| match line.find("//~") {
+            Some(pos) => line[..pos].trim_end().to_string(),
+            None => line.to_string(),
+        })
+        .collect();
+
+    for failure in actual {
+        let Some(line_num) = failure.line else {
+            continue;
+        };
+        let Some(idx) = (line_num as usize).checked_sub(1) else {
+            continue;
+        };
+        let Some(line) = lines.get_mut(idx) else {
+            continue;
+        };
+
+        let error_type = VERIFICATION_ERROR_TYPES
+            .iter()
+            .find(|&&t| failure.message.contains(t))
+            .copied()
+            .unwrap_or(failure.error_type.as_str());
+        line.push_str(" //~ VERIFY-FAIL ");
+        line.push_str(error_type);
+    }
+
+    std::fs::write(path, lines.join("\n") + "\n")
+}
+
+/// A `//~ VERIFY-FAIL` / `//~ ASSUME` annotation placed above a function
+/// signature, asserting that function should end up in the
+/// `failed` / `unverified` category respectively. Unlike [`ExpectedFailure`],
+/// this attaches to whichever function is declared next, not to a specific
+/// line -- so there's no `^` caret shifting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FunctionExpectationKind {
+    VerifyFail,
+    Assume,
+}
+
+/// Where a function's actual verification category didn't match its
+/// annotation (or the implicit "expected to verify" when there is none).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionExpectationMismatch {
+    pub function: FunctionLocation,
+    pub expected: FunctionCategory,
+    pub actual: FunctionCategory,
+}
+
+/// Scan `source` for `//~ VERIFY-FAIL` / `//~ ASSUME` lines, returning each
+/// annotation's 1-based source line.
+fn parse_function_expectations(source: &str) -> Vec<(usize, FunctionExpectationKind)> {
+    let mut expectations = Vec::new();
+
+    for (idx, line) in source.lines().enumerate() {
+        let Some(marker_pos) = line.find("//~") else {
+            continue;
+        };
+        let rest = line[marker_pos + 3..].trim_start();
+
+        if rest.starts_with("VERIFY-FAIL") {
+            expectations.push((idx + 1, FunctionExpectationKind::VerifyFail));
+        } else if rest.starts_with("ASSUME") {
+            expectations.push((idx + 1, FunctionExpectationKind::Assume));
+        }
+    }
+
+    expectations
+}
+
+/// Cross-check `//~ VERIFY-FAIL` / `//~ ASSUME` annotations in `source`
+/// against `functions`' actual verification category (as reported by
+/// [`crate::verification::VerificationAnalyzer::analyze_output`], supplied
+/// via the `actual` lookup). A function with no annotation above it is
+/// expected to verify cleanly.
+pub fn check_function_expectations(
+    source: &str,
+    functions: &[FunctionInfo],
+    actual: impl Fn(&FunctionInfo) -> FunctionCategory,
+) -> Vec<FunctionExpectationMismatch> {
+    let annotations = parse_function_expectations(source);
+
+    let mut sorted_functions: Vec<&FunctionInfo> = functions.iter().collect();
+    sorted_functions.sort_by_key(|f| f.start_line);
+
+    // Each annotation attaches to the nearest function declared below it.
+    let mut expected_by_start_line: std::collections::HashMap<usize, FunctionExpectationKind> =
+        std::collections::HashMap::new();
+    for (annotation_line, kind) in annotations {
+        if let Some(func) = sorted_functions
+            .iter()
+            .find(|f| f.start_line > annotation_line)
+        {
+            expected_by_start_line.entry(func.start_line).or_insert(kind);
+        }
+    }
+
+    let mut mismatches = Vec::new();
+    for func in functions {
+        let expected = match expected_by_start_line.get(&func.start_line) {
+            Some(FunctionExpectationKind::VerifyFail) => FunctionCategory::Failed,
+            Some(FunctionExpectationKind::Assume) => FunctionCategory::Unverified,
+            None => FunctionCategory::Verified,
+        };
+        let actual_category = actual(func);
+
+        if actual_category != expected {
+            mismatches.push(FunctionExpectationMismatch {
+                function: FunctionLocation {
+                    display_name: func.name.clone(),
+                    code_path: func.file.clone().unwrap_or_default(),
+                    code_text: CodeTextInfo {
+                        lines_start: func.start_line,
+                        lines_end: func.end_line,
+                    },
+                },
+                expected,
+                actual: actual_category,
+            });
+        }
+    }
+
+    mismatches
+}
+
+/// Rewrite every function's `//~ VERIFY-FAIL` / `//~ ASSUME` annotation in
+/// `source` to match its actual category -- the function-granularity
+/// analogue of [`bless`]. A `Failed` function gets `//~ VERIFY-FAIL` placed
+/// directly above it, `Unverified` gets `//~ ASSUME`, and `Verified` gets no
+/// annotation at all; any annotation already there is replaced rather than
+/// duplicated. Functions are edited bottom-to-top so an earlier edit's line
+/// shift never invalidates a later one's target line.
+pub fn bless_function_expectations(
+    path: &Path,
+    source: &str,
+    functions: &[FunctionInfo],
+    actual: impl Fn(&FunctionInfo) -> FunctionCategory,
+) -> std::io::Result<()> {
+    let mut lines: Vec<String> = source.lines().map(str::to_string).collect();
+
+    let mut sorted_functions: Vec<&FunctionInfo> = functions.iter().collect();
+    sorted_functions.sort_by_key(|f| f.start_line);
+
+    // Each existing annotation attaches to the nearest function declared
+    // below it, same rule `parse_function_expectations`'s caller uses.
+    let mut existing_annotation_line: HashMap<usize, usize> = HashMap::new();
+    for (annotation_line, _) in parse_function_expectations(source) {
+        if let Some(func) = sorted_functions
+            .iter()
+            .find(|f| f.start_line > annotation_line)
+        {
+            existing_annotation_line
+                .entry(func.start_line)
+                .or_insert(annotation_line);
+        }
+    }
+
+    let mut edits: Vec<(usize, FunctionCategory)> =
+        functions.iter().map(|f| (f.start_line, actual(f))).collect();
+    edits.sort_by_key(|(start_line, _)| std::cmp::Reverse(*start_line));
+
+    for (start_line, category) in edits {
+        let mut insert_at = start_line.saturating_sub(1);
+        if let Some(annotation_line) = existing_annotation_line.get(&start_line) {
+            lines.remove(annotation_line - 1);
+            insert_at = insert_at.saturating_sub(1);
+        }
+        if let Some(text) = function_annotation_text(category) {
+            lines.insert(insert_at.min(lines.len()), text.to_string());
+        }
+    }
+
+    std::fs::write(path, lines.join("\n") + "\n")
+}
+
+/// The `//~` annotation a bless pass writes for a given category, or `None`
+/// for `Verified` -- verifying cleanly is the implicit default, so it gets
+/// no annotation at all.
+fn function_annotation_text(category: FunctionCategory) -> Option<&'static str> {
+    match category {
+        FunctionCategory::Verified => None,
+        FunctionCategory::Failed => Some("//~ VERIFY-FAIL"),
+        FunctionCategory::Unverified => Some("//~ ASSUME"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(line: i32, message: &str) -> VerificationFailure {
+        VerificationFailure {
+            error_type: "postcondition not satisfied".to_string(),
+            file: Some("src/lib.rs".to_string()),
+            line: Some(line),
+            column: None,
+            message: message.to_string(),
+            assertion_details: Vec::new(),
+            full_error_text: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_caret_shifted_annotation() {
+        let source = "fn f() {\n    bar(); //~^ VERIFY-FAIL postcondition not satisfied\n}\n";
+        let expected = parse_annotations(source);
+        assert_eq!(
+            expected,
+            vec![ExpectedFailure {
+                line: 1,
+                error_type_substring: "postcondition not satisfied".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reconcile_matches_expected_and_actual() {
+        let expected = vec![ExpectedFailure {
+            line: 2,
+            error_type_substring: "postcondition not satisfied".to_string(),
+        }];
+        let actual = vec![failure(2, "postcondition not satisfied for function `f`")];
+        assert!(reconcile(&expected, &actual).is_empty());
+    }
+
+    #[test]
+    fn reconcile_reports_unmatched_on_both_sides() {
+        let expected = vec![ExpectedFailure {
+            line: 2,
+            error_type_substring: "precondition not satisfied".to_string(),
+        }];
+        let actual = vec![failure(3, "postcondition not satisfied for function `f`")];
+
+        let mismatches = reconcile(&expected, &actual);
+        assert_eq!(
+            mismatches,
+            vec![
+                Mismatch::MissingFailure {
+                    line: 2,
+                    error_type_substring: "precondition not satisfied".to_string(),
+                },
+                Mismatch::UnexpectedFailure {
+                    line: Some(3),
+                    message: "postcondition not satisfied for function `f`".to_string(),
+                },
+            ]
+        );
+    }
+
+    fn function_info(name: &str, start_line: usize) -> FunctionInfo {
+        FunctionInfo {
+            name: name.to_string(),
+            file: Some("src/lib.rs".to_string()),
+            start_line,
+            end_line: start_line + 2,
+            kind: None,
+            visibility: None,
+            context: None,
+            has_requires: true,
+            has_ensures: true,
+            has_trusted_assumption: false,
+            trusted_assumption_kind: None,
+            callees: Vec::new(),
+            doc: None,
+            attributes: Vec::new(),
+            is_test: false,
+            in_cfg_test_module: false,
+        }
+    }
+
+    #[test]
+    fn annotated_function_matching_its_actual_category_has_no_mismatch() {
+        let source = "//~ VERIFY-FAIL\nfn f() {}\n";
+        let functions = vec![function_info("f", 2)];
+        let mismatches =
+            check_function_expectations(source, &functions, |_| FunctionCategory::Failed);
+        assert!(mismatches.is_empty());
+    }
+
+    #[test]
+    fn unannotated_function_expected_to_verify_flags_drift() {
+        let source = "fn f() {}\n";
+        let functions = vec![function_info("f", 1)];
+        let mismatches =
+            check_function_expectations(source, &functions, |_| FunctionCategory::Failed);
+        assert_eq!(
+            mismatches,
+            vec![FunctionExpectationMismatch {
+                function: FunctionLocation {
+                    display_name: "f".to_string(),
+                    code_path: "src/lib.rs".to_string(),
+                    code_text: CodeTextInfo {
+                        lines_start: 1,
+                        lines_end: 3,
+                    },
+                },
+                expected: FunctionCategory::Verified,
+                actual: FunctionCategory::Failed,
+            }]
+        );
+    }
+
+    #[test]
+    fn bless_adds_annotation_for_a_newly_failing_function() {
+        let source = "fn f() {}\n";
+        let functions = vec![function_info("f", 1)];
+        let file = tempfile::NamedTempFile::new().unwrap();
+        bless_function_expectations(file.path(), source, &functions, |_| FunctionCategory::Failed)
+            .unwrap();
+        let blessed = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!(blessed, "//~ VERIFY-FAIL\nfn f() {}\n");
+    }
+
+    #[test]
+    fn bless_removes_annotation_for_a_now_verifying_function() {
+        let source = "//~ VERIFY-FAIL\nfn f() {}\n";
+        let functions = vec![function_info("f", 2)];
+        let file = tempfile::NamedTempFile::new().unwrap();
+        bless_function_expectations(file.path(), source, &functions, |_| {
+            FunctionCategory::Verified
+        })
+        .unwrap();
+        let blessed = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!(blessed, "fn f() {}\n");
+    }
+
+    #[test]
+    fn bless_replaces_a_stale_annotation_kind() {
+        let source = "//~ ASSUME\nfn f() {}\n";
+        let functions = vec![function_info("f", 2)];
+        let file = tempfile::NamedTempFile::new().unwrap();
+        bless_function_expectations(file.path(), source, &functions, |_| FunctionCategory::Failed)
+            .unwrap();
+        let blessed = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!(blessed, "//~ VERIFY-FAIL\nfn f() {}\n");
+    }
+}
@@ -0,0 +1,173 @@
+//! Tree-derived type context for definitions, as a precise alternative to
+//! the line-proximity heuristic in [`crate::build_call_graph`].
+//!
+//! `build_call_graph`'s `definition_type_contexts` pre-pass approximates a
+//! definition's impl context by scanning a fixed window of lines above it
+//! for `#`-terminated occurrences, which misses multi-line `impl` headers
+//! and over-collects in dense code. When the source file is available,
+//! [`parse_impl_contexts`] parses it with verus_syn (the same parser
+//! [`crate::verus_parser`] uses for function spans) and walks the AST to
+//! find the `impl` block enclosing each method definition, reading its
+//! trait path, `Self` type and generic arguments directly from the syntax
+//! tree instead of guessing from nearby lines.
+
+use crate::verus_parser::expand_macro_items;
+use std::collections::HashMap;
+use verus_syn::spanned::Spanned;
+use verus_syn::visit::Visit;
+use verus_syn::{GenericArgument, ImplItem, ItemImpl, Path, PathArguments, Type};
+
+/// Precise type context for one function definition, read from its
+/// enclosing `impl` block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImplTypeContext {
+    /// The resolved `Self` type, e.g. `"RistrettoPoint"` or `"&Scalar"`.
+    pub self_type: Option<String>,
+    /// Every type name mentioned in the `Self` type and the trait path,
+    /// including generic arguments -- e.g. `impl Mul<Scalar> for
+    /// RistrettoPoint` yields `["RistrettoPoint", "Mul", "Scalar"]`.
+    pub type_context: Vec<String>,
+}
+
+/// Parse `content` and return, for every method defined inside an `impl`
+/// block, its precise type context keyed by `(method_name,
+/// definition_line)` where `definition_line` is the 1-indexed line of the
+/// method's name (matching how SCIP reports definition occurrences).
+/// Returns an empty map if `content` doesn't parse as Rust/Verus source.
+pub fn parse_impl_contexts(content: &str) -> HashMap<(String, usize), ImplTypeContext> {
+    let Ok(syntax_tree) = verus_syn::parse_file(content) else {
+        return HashMap::new();
+    };
+
+    let mut visitor = ImplContextVisitor {
+        contexts: HashMap::new(),
+    };
+    visitor.visit_file(&syntax_tree);
+    visitor.contexts
+}
+
+struct ImplContextVisitor {
+    contexts: HashMap<(String, usize), ImplTypeContext>,
+}
+
+impl<'ast> Visit<'ast> for ImplContextVisitor {
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let self_type = type_name(&node.self_ty);
+
+        let mut type_context = Vec::new();
+        collect_type_names(&node.self_ty, &mut type_context);
+        if let Some((_, trait_path, _)) = &node.trait_ {
+            collect_path_names(trait_path, &mut type_context);
+        }
+        type_context.dedup();
+
+        let context = ImplTypeContext {
+            self_type,
+            type_context,
+        };
+
+        for item in &node.items {
+            if let ImplItem::Fn(method) = item {
+                let line = method.sig.ident.span().start().line;
+                self.contexts
+                    .insert((method.sig.ident.to_string(), line), context.clone());
+            }
+        }
+
+        verus_syn::visit::visit_item_impl(self, node);
+    }
+
+    fn visit_item_macro(&mut self, node: &'ast verus_syn::ItemMacro) {
+        for item in expand_macro_items(node) {
+            self.visit_item(&item);
+        }
+        verus_syn::visit::visit_item_macro(self, node);
+    }
+}
+
+/// The bare name of a type's last path segment, e.g. `"RistrettoPoint"` for
+/// both `RistrettoPoint` and `curve25519_dalek::RistrettoPoint`, preserving
+/// a leading `&` for reference types.
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Reference(r) => type_name(&r.elem).map(|name| format!("&{name}")),
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|seg| seg.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Collect every type name mentioned in `ty`, including nested generic
+/// arguments, in source order.
+fn collect_type_names(ty: &Type, out: &mut Vec<String>) {
+    match ty {
+        Type::Reference(r) => collect_type_names(&r.elem, out),
+        Type::Path(type_path) => collect_path_names(&type_path.path, out),
+        _ => {}
+    }
+}
+
+/// Collect every type name in a path, including the path's own last
+/// segment and any generic arguments it carries.
+fn collect_path_names(path: &Path, out: &mut Vec<String>) {
+    let Some(segment) = path.segments.last() else {
+        return;
+    };
+    out.push(segment.ident.to_string());
+    if let PathArguments::AngleBracketed(args) = &segment.arguments {
+        for arg in &args.args {
+            if let GenericArgument::Type(ty) = arg {
+                collect_type_names(ty, out);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_self_type_and_trait_generics() {
+        let source = r#"
+impl Mul<Scalar> for RistrettoPoint {
+    fn mul(self, rhs: Scalar) -> RistrettoPoint {
+        unimplemented!()
+    }
+}
+"#;
+        let contexts = parse_impl_contexts(source);
+        let context = contexts
+            .get(&("mul".to_string(), 3))
+            .expect("should find mul's context");
+        assert_eq!(context.self_type.as_deref(), Some("RistrettoPoint"));
+        assert!(context.type_context.contains(&"RistrettoPoint".to_string()));
+        assert!(context.type_context.contains(&"Mul".to_string()));
+        assert!(context.type_context.contains(&"Scalar".to_string()));
+    }
+
+    #[test]
+    fn handles_inherent_impl_with_no_trait() {
+        let source = r#"
+impl RistrettoPoint {
+    fn identity() -> RistrettoPoint {
+        unimplemented!()
+    }
+}
+"#;
+        let contexts = parse_impl_contexts(source);
+        let context = contexts
+            .get(&("identity".to_string(), 3))
+            .expect("should find identity's context");
+        assert_eq!(context.self_type.as_deref(), Some("RistrettoPoint"));
+        assert_eq!(context.type_context, vec!["RistrettoPoint".to_string()]);
+    }
+
+    #[test]
+    fn unparsable_source_yields_empty_map() {
+        assert!(parse_impl_contexts("not valid rust {{{").is_empty());
+    }
+}
@@ -2,6 +2,27 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+pub mod annotations;
+pub mod atom_cache;
+pub mod canonical;
+pub mod config_file;
+pub mod coverage;
+pub mod dependency_graph;
+pub mod diagnostics;
+pub mod diff;
+pub mod edge_diagnostics;
+pub mod error;
+pub mod expect;
+pub mod impl_context;
+pub mod line_index;
+pub mod narrow;
+pub mod probe_config;
+pub mod reachability;
+pub mod scip_protobuf;
+pub mod scip_symbol;
+pub mod symbol_interner;
+pub mod symbol_table;
+pub mod type_normalize;
 pub mod verification;
 pub mod verus_parser;
 
@@ -62,14 +83,38 @@ pub struct SignatureDocumentation {
     pub position_encoding: i32,
 }
 
+/// A generic type reference captured at a call site, with its nested type
+/// arguments in source order -- e.g. the turbofish `::<LinearMap<K, V>>()`
+/// yields `TypeHint { name: "LinearMap", args: ["K", "V"] }` rather than the
+/// flat, order-losing `["LinearMap", "K", "V"]` a bag of names would give.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeHint {
+    pub name: String,
+    pub args: Vec<TypeHint>,
+}
+
+impl TypeHint {
+    /// This hint's name followed by every argument's name, depth-first --
+    /// the flat view callers that only care about "was this type mentioned
+    /// at all" can use.
+    pub fn flatten(&self) -> Vec<&str> {
+        let mut out = vec![self.name.as_str()];
+        for arg in &self.args {
+            out.extend(arg.flatten());
+        }
+        out
+    }
+}
+
 /// A call from one function to another, with optional type context for disambiguation
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CalleeInfo {
     /// The raw SCIP symbol of the callee
     pub symbol: String,
-    /// Type hints found on the same line as the call (e.g., turbofish type parameters)
-    /// Used to disambiguate calls to generic trait implementations
-    pub type_hints: Vec<String>,
+    /// Generic type references found on the same line as the call (e.g.
+    /// turbofish type arguments), used to disambiguate calls to generic
+    /// trait implementations.
+    pub type_hints: Vec<TypeHint>,
 }
 
 /// Function node in the call graph
@@ -93,12 +138,28 @@ pub struct FunctionNode {
 
 /// Output format: Atom with line numbers
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "rkyv-impl",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct AtomWithLines {
     #[serde(rename = "display-name")]
     pub display_name: String,
     #[serde(rename = "scip-name")]
     pub scip_name: String,
     pub dependencies: HashSet<String>,
+    /// The subset of `dependencies` that didn't resolve to a single
+    /// candidate -- the raw callee symbol matched more than one project
+    /// definition, and type-hint disambiguation couldn't narrow it down to
+    /// one, so every remaining candidate is included as a (possibly
+    /// spurious) edge. Empty, and omitted, when every edge resolved
+    /// uniquely.
+    #[serde(
+        rename = "ambiguous-dependencies",
+        default,
+        skip_serializing_if = "HashSet::is_empty"
+    )]
+    pub ambiguous_dependencies: HashSet<String>,
     #[serde(rename = "code-path")]
     pub code_path: String,
     #[serde(rename = "code-text")]
@@ -106,6 +167,10 @@ pub struct AtomWithLines {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "rkyv-impl",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct CodeTextInfo {
     #[serde(rename = "lines-start")]
     pub lines_start: usize,
@@ -120,37 +185,181 @@ pub fn parse_scip_json(file_path: &str) -> Result<ScipIndex, Box<dyn std::error:
     Ok(index)
 }
 
-/// Check if a symbol kind represents a function-like entity
+/// Check if a symbol kind represents a function-like entity, consulting
+/// [`probe_config::ProbeConfig::global`] so a project can widen or narrow
+/// this beyond the default Method/Function/Constructor/Macro set.
 fn is_function_like(kind: i32) -> bool {
-    matches!(kind, 6 | 17 | 26 | 80) // Method, Function, etc.
+    probe_config::ProbeConfig::global().is_function_like(kind)
+}
+
+/// Check if `symbol_roles` marks a definition occurrence, consulting
+/// [`probe_config::ProbeConfig::global`] for consistency with
+/// [`is_function_like`], though no indexer observed so far varies this bit.
+fn is_definition(symbol_roles: Option<i32>) -> bool {
+    probe_config::ProbeConfig::global().is_definition(symbol_roles)
+}
+
+/// The kind of thing a [`ScopeSegment`] names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScopeSegmentKind {
+    Function,
+    Type,
+    Term,
+}
+
+/// One segment of a [`FullyQualifiedSymbol`]'s scope chain.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScopeSegment {
+    pub name: String,
+    pub kind: ScopeSegmentKind,
 }
 
-/// Create a unique key for a function by combining symbol, signature, self_type, and line number.
+/// A structured, `Hash + Eq` key disambiguating a function definition,
+/// replacing the old `symbol|signature|self_type@line` string concatenation.
 ///
-/// This handles multiple levels of potential collisions:
-/// 1. Same symbol, different signature → distinguished by signature
-/// 2. Same symbol & signature, different Self type → distinguished by self_type
-/// 3. Same symbol, signature & self_type, different line → distinguished by line (fallback)
+/// `segments` is the SCIP descriptor chain (namespaces become `Term`
+/// segments, types `Type` segments, the method name itself a `Function`
+/// segment) plus synthetic
+/// segments for the signature and the resolved `Self` type -- so two
+/// `impl Marker<A> for X` / `impl Marker<B> for X` blocks with identical
+/// method signatures are distinguished by their differing `Type` segments
+/// (the trait's type argument) rather than by where they happen to sit in
+/// the source. `line` is kept only as a last-resort tiebreaker for the rare
+/// case where two definitions produce genuinely identical segment chains.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FullyQualifiedSymbol {
+    pub segments: Vec<ScopeSegment>,
+    pub line: Option<i32>,
+}
+
+/// Map a parsed SCIP descriptor to the scope-segment kind it represents.
+/// Namespace descriptors (module path components) are folded into `Term`
+/// segments; parameters and meta descriptors don't identify a named scope
+/// and are dropped.
+fn scope_segment_from_descriptor(descriptor: scip_symbol::Descriptor) -> Option<ScopeSegment> {
+    use scip_symbol::DescriptorKind;
+    let kind = match descriptor.kind {
+        DescriptorKind::Type => ScopeSegmentKind::Type,
+        DescriptorKind::Method | DescriptorKind::Term => ScopeSegmentKind::Function,
+        DescriptorKind::Namespace | DescriptorKind::Meta => ScopeSegmentKind::Term,
+        DescriptorKind::TypeParameter | DescriptorKind::Parameter => return None,
+    };
+    Some(ScopeSegment {
+        name: descriptor.name,
+        kind,
+    })
+}
+
+/// Build the segment chain for a function definition: its parsed SCIP
+/// descriptor chain, then a `Function` segment for the signature (so
+/// overloads sharing a descriptor chain are still distinguished), then a
+/// `Type` segment for the resolved `Self` type, if any.
+fn scope_segments(symbol: &str, signature: &str, self_type: Option<&str>) -> Vec<ScopeSegment> {
+    let mut segments: Vec<ScopeSegment> = scip_symbol::parse_symbol(symbol)
+        .map(|parsed| {
+            parsed
+                .descriptors
+                .into_iter()
+                .filter_map(scope_segment_from_descriptor)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    segments.push(ScopeSegment {
+        name: signature.to_string(),
+        kind: ScopeSegmentKind::Function,
+    });
+
+    if let Some(self_type) = self_type {
+        segments.push(ScopeSegment {
+            name: self_type.to_string(),
+            kind: ScopeSegmentKind::Type,
+        });
+    }
+
+    segments
+}
+
+/// Create a unique key for a function from its symbol, signature and
+/// resolved `Self` type, falling back to `line` only when another
+/// definition already produced the same segment chain.
 ///
-/// The line number fallback handles edge cases like:
-/// ```text
-/// impl<T> Marker<A> for X { fn mark(self) {} }  // line 10
-/// impl<T> Marker<B> for X { fn mark(self) {} }  // line 20
-/// ```
-/// Where the trait type parameter doesn't appear in the method signature.
+/// `seen_segments` tracks every segment chain handed out so far (without a
+/// line tiebreaker) so the fallback only kicks in on a genuine collision.
 fn make_unique_key(
     symbol: &str,
     signature: &str,
     self_type: Option<&str>,
     line: Option<i32>,
-) -> String {
-    let base = match self_type {
-        Some(st) => format!("{}|{}|{}", symbol, signature, st),
-        None => format!("{}|{}", symbol, signature),
-    };
-    match line {
-        Some(l) => format!("{}@{}", base, l),
-        None => base,
+    seen_segments: &mut HashSet<Vec<ScopeSegment>>,
+) -> FullyQualifiedSymbol {
+    let segments = scope_segments(symbol, signature, self_type);
+
+    if seen_segments.contains(&segments) {
+        return FullyQualifiedSymbol { segments, line };
+    }
+
+    seen_segments.insert(segments.clone());
+    FullyQualifiedSymbol {
+        segments,
+        line: None,
+    }
+}
+
+/// Group the type references found on a single line into a forest of
+/// [`TypeHint`]s by their column ranges: a span fully enclosed by another
+/// is that enclosing span's type argument. `spans` must be sorted by `(start asc, end
+/// desc)` so an enclosing span is always visited before the spans it
+/// encloses.
+fn build_type_hint_forest(spans: Vec<(i32, i32, String)>) -> Vec<TypeHint> {
+    let mut stack: Vec<(i32, i32, TypeHint)> = Vec::new();
+    let mut roots = Vec::new();
+
+    for (start, end, name) in spans {
+        while let Some(top) = stack.last() {
+            if top.1 < start {
+                let (_, _, hint) = stack.pop().unwrap();
+                attach_type_hint(&mut stack, &mut roots, hint);
+            } else {
+                break;
+            }
+        }
+        stack.push((start, end, TypeHint { name, args: Vec::new() }));
+    }
+
+    while let Some((_, _, hint)) = stack.pop() {
+        attach_type_hint(&mut stack, &mut roots, hint);
+    }
+
+    roots
+}
+
+/// Attach a finished [`TypeHint`] to its enclosing span on the stack, or to
+/// the top-level forest if none remains.
+fn attach_type_hint(
+    stack: &mut [(i32, i32, TypeHint)],
+    roots: &mut Vec<TypeHint>,
+    hint: TypeHint,
+) {
+    if let Some(parent) = stack.last_mut() {
+        parent.2.args.push(hint);
+    } else {
+        roots.push(hint);
+    }
+}
+
+/// The `(start_column, end_column)` of an occurrence's range, if it's a
+/// single-line range -- SCIP ranges are `[line, start_col, end_col]` for a
+/// single line or `[start_line, start_col, end_line, end_col]` when they
+/// span multiple lines, and only single-line spans can nest meaningfully
+/// within a type-hint forest.
+fn single_line_col_range(range: &[i32]) -> Option<(i32, i32)> {
+    match range {
+        [_, start_col, end_col] => Some((*start_col, *end_col)),
+        [start_line, start_col, end_line, end_col] if start_line == end_line => {
+            Some((*start_col, *end_col))
+        }
+        _ => None,
     }
 }
 
@@ -159,13 +368,23 @@ fn make_unique_key(
 ///
 /// Note: Multiple trait implementations (e.g., `impl Mul<A> for B` and `impl Mul<B> for A`)
 /// can have the same SCIP symbol string. We use signature_documentation.text to distinguish them.
+///
+/// `project_root`, if given, lets definition type context be read directly
+/// from the syntax tree (see [`crate::impl_context`]) instead of only the
+/// line-proximity heuristic -- pass `None` to use the heuristic everywhere.
 pub fn build_call_graph(
     scip_data: &ScipIndex,
-) -> (HashMap<String, FunctionNode>, HashMap<String, String>) {
-    let mut call_graph: HashMap<String, FunctionNode> = HashMap::new();
-    let mut project_function_keys: HashSet<String> = HashSet::new();
+    project_root: Option<&Path>,
+) -> (
+    HashMap<FullyQualifiedSymbol, FunctionNode>,
+    HashMap<String, String>,
+    HashSet<String>,
+) {
+    let mut call_graph: HashMap<FullyQualifiedSymbol, FunctionNode> = HashMap::new();
+    let mut project_function_keys: HashSet<FullyQualifiedSymbol> = HashSet::new();
     let mut all_function_symbols: HashSet<String> = HashSet::new();
     let mut symbol_to_display_name: HashMap<String, String> = HashMap::new();
+    let mut seen_segments: HashSet<Vec<ScopeSegment>> = HashSet::new();
 
     // Pre-pass: Find where each symbol is DEFINED (symbol_roles == 1)
     // Collect ALL definition occurrences per symbol (there may be multiple for trait impls)
@@ -174,7 +393,7 @@ pub fn build_call_graph(
     for doc in &scip_data.documents {
         let rel_path = doc.relative_path.trim_start_matches('/').to_string();
         for occurrence in &doc.occurrences {
-            let is_definition = occurrence.symbol_roles.unwrap_or(0) & 1 == 1;
+            let is_definition = is_definition(occurrence.symbol_roles);
             if is_definition && !occurrence.range.is_empty() {
                 let line = occurrence.range[0];
                 symbol_to_definitions
@@ -190,6 +409,23 @@ pub fn build_call_graph(
         defs.sort_by_key(|(_, line)| *line);
     }
 
+    // Pre-pass: Read exact impl/trait type context from the syntax tree,
+    // when the source file is available, instead of guessing from nearby
+    // lines. Maps (file_path, line) -> the impl block's precise context.
+    let mut definition_tree_contexts: HashMap<(String, i32), impl_context::ImplTypeContext> =
+        HashMap::new();
+    if let Some(root) = project_root {
+        for doc in &scip_data.documents {
+            let rel_path = doc.relative_path.trim_start_matches('/').to_string();
+            if let Ok(content) = line_index::read_source_file(&root.join(&rel_path)) {
+                for ((_, line), context) in impl_context::parse_impl_contexts(&content) {
+                    // verus_syn reports 1-indexed lines; SCIP ranges are 0-indexed.
+                    definition_tree_contexts.insert((rel_path.clone(), line as i32 - 1), context);
+                }
+            }
+        }
+    }
+
     // Pre-pass: Collect type context for definitions (types near each definition line)
     // This helps disambiguate trait impls like `impl From<T> for Container<X>` vs `Container<Y>`
     // Maps (file_path, line) -> Vec<type_name>
@@ -200,24 +436,46 @@ pub fn build_call_graph(
         // Collect all type references in this document
         let mut type_refs_by_line: HashMap<i32, Vec<String>> = HashMap::new();
         for occ in &doc.occurrences {
-            let is_definition = occ.symbol_roles.unwrap_or(0) & 1 == 1;
-            if !is_definition && !occ.range.is_empty() && occ.symbol.ends_with('#') {
-                let line = occ.range[0];
-                if let Some(type_name) = extract_type_name_from_symbol(&occ.symbol) {
-                    type_refs_by_line.entry(line).or_default().push(type_name);
+            let is_definition = is_definition(occ.symbol_roles);
+            if !is_definition && !occ.range.is_empty() {
+                if let Some(type_name) = crate::scip_symbol::parse_symbol(&occ.symbol)
+                    .as_ref()
+                    .and_then(|parsed| parsed.type_name())
+                {
+                    let line = occ.range[0];
+                    type_refs_by_line
+                        .entry(line)
+                        .or_default()
+                        .push(type_name.to_string());
                 }
             }
         }
 
-        // For each definition line, collect types from nearby lines (within 5 lines before)
+        // For each definition line, prefer the exact context read from the
+        // syntax tree; fall back to collecting types from nearby lines
+        // (within 5 lines before) when no source file was available.
         for occ in &doc.occurrences {
-            let is_definition = occ.symbol_roles.unwrap_or(0) & 1 == 1;
+            let is_definition = is_definition(occ.symbol_roles);
             if is_definition && !occ.range.is_empty() {
                 let def_line = occ.range[0];
+
+                if let Some(tree_context) =
+                    definition_tree_contexts.get(&(rel_path.clone(), def_line))
+                {
+                    if !tree_context.type_context.is_empty() {
+                        definition_type_contexts.insert(
+                            (rel_path.clone(), def_line),
+                            tree_context.type_context.clone(),
+                        );
+                        continue;
+                    }
+                }
+
                 let mut nearby_types = Vec::new();
 
-                // Look at lines from def_line-5 to def_line
-                for offset in 0..=5 {
+                // Look back `type_context_lookback_lines` lines from def_line.
+                let lookback = probe_config::ProbeConfig::global().type_context_lookback_lines;
+                for offset in 0..=lookback {
                     let check_line = def_line - offset;
                     if check_line >= 0 {
                         if let Some(types) = type_refs_by_line.get(&check_line) {
@@ -306,16 +564,22 @@ pub fn build_call_graph(
                 // Only add to call_graph if DEFINED in this project
                 if let Some(defs) = symbol_to_definitions.get(&symbol.symbol) {
                     if let Some((rel_path, line)) = defs.get(def_index) {
-                        // Create unique key using signature, self_type, AND line number
-                        // This handles all collision cases:
-                        // - Same symbol, different signature → distinguished by signature
-                        // - Same symbol & signature, different Self type → distinguished by self_type
-                        // - Same symbol, signature & self_type → distinguished by line (fallback)
+                        // Prefer the `Self` type read from the syntax tree
+                        // over the `method().(self)` symbol heuristic.
+                        let self_type = definition_tree_contexts
+                            .get(&(rel_path.clone(), *line))
+                            .and_then(|ctx| ctx.self_type.clone())
+                            .or(self_type);
+
+                        // Create a unique key from the symbol's descriptor
+                        // chain, signature and self_type, falling back to
+                        // the line number only on a genuine collision.
                         let unique_key = make_unique_key(
                             &symbol.symbol,
                             signature,
                             self_type.as_deref(),
                             Some(*line),
+                            &mut seen_segments,
                         );
 
                         project_function_keys.insert(unique_key.clone());
@@ -346,7 +610,7 @@ pub fn build_call_graph(
     }
 
     // Build a map from (symbol, line) -> unique_key for occurrence processing
-    let mut symbol_line_to_key: HashMap<(String, i32), String> = HashMap::new();
+    let mut symbol_line_to_key: HashMap<(String, i32), FullyQualifiedSymbol> = HashMap::new();
     for (key, node) in &call_graph {
         if let Some(defs) = symbol_to_definitions.get(&node.symbol) {
             // Find the definition line that matches this node's signature
@@ -368,6 +632,9 @@ pub fn build_call_graph(
     symbol_line_to_key.clear();
     let mut symbol_seen_for_lines: HashMap<String, usize> = HashMap::new();
     let mut symbol_self_type_idx_for_lines: HashMap<String, usize> = HashMap::new();
+    // Mirrors the first pass's traversal exactly, so it makes the same
+    // collision/no-collision decisions and reproduces the same keys.
+    let mut seen_segments_for_lines: HashSet<Vec<ScopeSegment>> = HashSet::new();
     for doc in &scip_data.documents {
         for symbol in &doc.symbols {
             if is_function_like(symbol.kind) {
@@ -397,12 +664,20 @@ pub fn build_call_graph(
 
                 // Get line number from definitions
                 if let Some(defs) = symbol_to_definitions.get(&symbol.symbol) {
-                    if let Some((_, line)) = defs.get(def_index) {
+                    if let Some((rel_path, line)) = defs.get(def_index) {
+                        // Must match pass 1's override exactly, so the two
+                        // passes make the same collision decisions.
+                        let self_type = definition_tree_contexts
+                            .get(&(rel_path.clone(), *line))
+                            .and_then(|ctx| ctx.self_type.clone())
+                            .or(self_type);
+
                         let unique_key = make_unique_key(
                             &symbol.symbol,
                             signature,
                             self_type.as_deref(),
                             Some(*line),
+                            &mut seen_segments_for_lines,
                         );
 
                         if call_graph.contains_key(&unique_key) {
@@ -417,7 +692,7 @@ pub fn build_call_graph(
     // Second pass: build call relationships and extract ranges
     // Also collect type hints (symbols ending with #) for disambiguation
     for doc in &scip_data.documents {
-        let mut current_function_key: Option<String> = None;
+        let mut current_function_key: Option<FullyQualifiedSymbol> = None;
 
         let mut ordered_occurrences = doc.occurrences.clone();
         ordered_occurrences.sort_by(|a, b| {
@@ -428,25 +703,41 @@ pub fn build_call_graph(
 
         // Pre-collect type symbols per line for disambiguation
         // Type symbols are those ending with # (struct/type references)
-        let mut line_to_type_hints: HashMap<i32, Vec<String>> = HashMap::new();
+        let mut line_to_type_spans: HashMap<i32, Vec<(i32, i32, String)>> = HashMap::new();
         for occ in &ordered_occurrences {
-            let is_definition = occ.symbol_roles.unwrap_or(0) & 1 == 1;
+            let is_definition = is_definition(occ.symbol_roles);
             if !is_definition && !occ.range.is_empty() {
                 let line = occ.range[0];
-                // Check if this is a type reference (symbol ends with #)
-                if occ.symbol.ends_with('#') {
-                    // Extract just the type name from the symbol
-                    // e.g., "rust-analyzer cargo ... curve_models/serial/backend/ProjectiveNielsPoint#"
-                    // → "ProjectiveNielsPoint"
-                    if let Some(type_name) = extract_type_name_from_symbol(&occ.symbol) {
-                        line_to_type_hints.entry(line).or_default().push(type_name);
+                // Check if this is a type reference, and if so extract its
+                // name, e.g. "rust-analyzer cargo ...
+                // curve_models/serial/backend/ProjectiveNielsPoint#" →
+                // "ProjectiveNielsPoint".
+                if let Some(type_name) = crate::scip_symbol::parse_symbol(&occ.symbol)
+                    .as_ref()
+                    .and_then(|parsed| parsed.type_name())
+                {
+                    if let Some((start_col, end_col)) = single_line_col_range(&occ.range) {
+                        line_to_type_spans.entry(line).or_default().push((
+                            start_col,
+                            end_col,
+                            type_name.to_string(),
+                        ));
                     }
                 }
             }
         }
+        let line_to_type_hints: HashMap<i32, Vec<TypeHint>> = line_to_type_spans
+            .into_iter()
+            .map(|(line, mut spans)| {
+                // Enclosing spans first, so the forest builder sees a
+                // parent before the children nested inside it.
+                spans.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+                (line, build_type_hint_forest(spans))
+            })
+            .collect();
 
         for occurrence in &ordered_occurrences {
-            let is_definition = occurrence.symbol_roles.unwrap_or(0) & 1 == 1;
+            let is_definition = is_definition(occurrence.symbol_roles);
             let line = if !occurrence.range.is_empty() {
                 occurrence.range[0]
             } else {
@@ -484,23 +775,7 @@ pub fn build_call_graph(
         }
     }
 
-    (call_graph, symbol_to_display_name)
-}
-
-/// Extract the type name from a SCIP symbol ending with #
-/// e.g., "rust-analyzer cargo curve25519-dalek 4.1.3 curve_models/serial/backend/ProjectiveNielsPoint#"
-/// → "ProjectiveNielsPoint"
-fn extract_type_name_from_symbol(symbol: &str) -> Option<String> {
-    // Strip the trailing #
-    let without_hash = symbol.trim_end_matches('#');
-    // Get the last path component
-    if let Some(last_slash) = without_hash.rfind('/') {
-        let name = &without_hash[last_slash + 1..];
-        if !name.is_empty() {
-            return Some(name.to_string());
-        }
-    }
-    None
+    (call_graph, symbol_to_display_name, all_function_symbols)
 }
 
 /// Extract type parameter info from a signature for trait impls.
@@ -575,45 +850,39 @@ fn extract_type_from_param(param: &str) -> Option<String> {
 
 /// Clean up a type string by removing lifetimes but PRESERVING the reference marker (&).
 /// This is important for distinguishing `impl From<&T>` from `impl From<T>`.
+/// Parses the type with [`type_normalize::normalize_type`] so lifetimes are
+/// stripped everywhere (including nested generics like `Mul<&'a Scalar>`),
+/// not just as a leading prefix.
 fn clean_type_string_preserve_ref(type_str: &str) -> String {
-    let type_str = type_str.trim();
-
-    // Check if it's a reference type
-    let is_ref = type_str.starts_with('&');
-
-    // Remove the & temporarily to clean up lifetimes
-    let without_ref = type_str.trim_start_matches('&').trim();
-
-    // Remove lifetime annotations
-    let clean = without_ref
-        .trim_start_matches("'a ")
-        .trim_start_matches("'b ")
-        .trim_start_matches("'_ ")
-        .trim_start_matches("mut ")
-        .trim();
-
-    if clean.is_empty() {
-        String::new()
-    } else if is_ref {
-        // Re-add the & for reference types
-        format!("&{}", clean)
-    } else {
-        clean.to_string()
-    }
+    type_normalize::normalize_type(type_str)
+        .map(|normalized| normalized.rendered_with_ref())
+        .unwrap_or_default()
 }
 
 /// Clean up a type string by removing references, lifetimes, and whitespace
 /// Used for return types where we don't care about reference distinction.
 fn clean_type_string(type_str: &str) -> String {
-    type_str
-        .trim()
-        .trim_start_matches('&')
-        .trim_start_matches("'a ")
-        .trim_start_matches("'b ")
-        .trim_start_matches("'_ ")
-        .trim_start_matches("mut ")
-        .trim()
-        .to_string()
+    type_normalize::normalize_type(type_str)
+        .map(|normalized| normalized.rendered)
+        .unwrap_or_default()
+}
+
+/// Normalize a bare type name for matching call-site type hints against
+/// definition-site type context entries, both of which are already bare
+/// names (not full type expressions) that can still differ by reference/
+/// lifetime decoration -- e.g. a hint of `&Scalar` against a context entry
+/// of `Scalar` should still match. Unlike [`clean_type_string`], falls back
+/// to the trimmed raw text (not an empty string) on a parse failure, since
+/// two unrelated unparseable names becoming `""` would wrongly compare
+/// equal; used by [`reachability::resolve_callee`] and
+/// `convert_to_atoms_with_lines_internal`'s dependency resolution, which
+/// both replace a looser raw-string substring test with this normalized
+/// comparison (the substring test over-matched, e.g. `NielsPoint` against
+/// `ProjectiveNielsPoint`).
+pub(crate) fn normalized_type_name(raw: &str) -> String {
+    type_normalize::normalize_type(raw)
+        .map(|n| n.rendered)
+        .unwrap_or_else(|| raw.trim().to_string())
 }
 
 /// Extract the Self type from a self parameter signature.
@@ -624,48 +893,58 @@ fn clean_type_string(type_str: &str) -> String {
 fn extract_self_type(self_signature: &str) -> Option<String> {
     // Pattern: "self: &Type" or "self: &'a Type" or "self: Type"
     let self_signature = self_signature.trim();
+    let colon_pos = self_signature.find(':')?;
+    let type_part = self_signature[colon_pos + 1..].trim();
+    type_normalize::normalize_type(type_part).map(|normalized| normalized.rendered_with_ref())
+}
 
-    if let Some(colon_pos) = self_signature.find(':') {
-        let type_part = self_signature[colon_pos + 1..].trim();
-
-        // Check if it's a reference type
-        let is_ref = type_part.starts_with('&');
-
-        // Remove lifetime annotations but preserve the & if present
-        let clean_type = type_part
-            .trim_start_matches('&')
-            .trim_start_matches("'a ")
-            .trim_start_matches("'b ")
-            .trim_start_matches("'_ ")
-            .trim();
-
-        if !clean_type.is_empty() {
-            // Re-add the & if it was a reference type
-            if is_ref {
-                return Some(format!("&{}", clean_type));
-            } else {
-                return Some(clean_type.to_string());
+/// Build a canonical, content-derived descriptor for one impl member, from
+/// its Self type and its parameter/return types (each normalized via
+/// [`type_normalize::normalize_type`], so e.g. `&'a Scalar` and `&Scalar`
+/// contribute the same descriptor). Used as a last-resort disambiguator
+/// when two impls collapse to the same `base_scip_name` and no
+/// discriminating type was found in `definition_type_context` -- unlike a
+/// source line number, this is stable across edits that don't touch the
+/// signature itself.
+fn canonical_impl_descriptor(signature: &str, self_type: Option<&str>) -> String {
+    let params = signature
+        .find('(')
+        .zip(signature.find(')'))
+        .map(|(start, end)| &signature[start + 1..end])
+        .unwrap_or("");
+
+    let param_types: Vec<String> = params
+        .split(',')
+        .filter_map(|param| {
+            let param = param.trim();
+            if param.is_empty() || param == "self" {
+                return None;
             }
-        }
-    }
-
-    None
-}
+            extract_type_from_param(param)
+        })
+        .collect();
 
-/// Check if a symbol path is missing the Self type (verus-analyzer inconsistency).
-/// verus-analyzer produces "module/Trait#method()" for reference Self types,
-/// but "module/Type#Trait#method()" for owned Self types.
-/// This function detects the former pattern.
-fn is_missing_self_type(symbol: &str) -> bool {
-    // Pattern for missing Self type: "module/Trait#method()" where Trait is capitalized
-    // Pattern for present Self type: "module/Type#Trait#method()" has two # separators
+    let return_type = signature
+        .find("->")
+        .map(|pos| clean_type_string(signature[pos + 2..].trim()))
+        .unwrap_or_default();
 
-    // Count the number of # in the symbol
-    let hash_count = symbol.matches('#').count();
+    format!(
+        "{}({})->{}",
+        self_type.unwrap_or(""),
+        param_types.join(","),
+        return_type
+    )
+}
 
-    // If there's only one #, and it's followed by a method name, Self type is likely missing
-    // e.g., "montgomery/Mul#mul()" vs "montgomery/MontgomeryPoint#Mul#mul()"
-    hash_count == 1
+/// A short, stable hash of [`canonical_impl_descriptor`], suitable for
+/// embedding in a scip_name as `@impl=<hex>`.
+fn canonical_impl_key(signature: &str, self_type: Option<&str>) -> String {
+    use std::hash::{Hash, Hasher};
+    let descriptor = canonical_impl_descriptor(signature, self_type);
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    descriptor.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
 }
 
 /// Convert symbol to a scip name, optionally including type info for disambiguation.
@@ -681,6 +960,16 @@ fn is_missing_self_type(symbol: &str) -> bool {
 /// 1. Adding trait type parameters (e.g., Mul -> Mul<Scalar>) for disambiguation
 /// 2. Adding the Self type when missing (e.g., montgomery/Mul#mul -> montgomery/MontgomeryPoint#Mul#mul)
 /// 3. Adding line number suffix when type info alone can't disambiguate (e.g., generic impls)
+///
+/// The result is not a flattened display string: it's the package prefix
+/// followed by [`scip_symbol::ParsedSymbol::render_descriptors`]'s rendering
+/// of the symbol's typed descriptor chain (`Namespace` segments joined by
+/// `/`, `Type` segments by `#`, the trailing `Method` by `().`, and so on),
+/// with the receiver type spliced in as its own `Type` segment by the Self
+/// insertion above. `impl Foo { fn bar }` and `impl Baz { fn bar }` render
+/// to different descriptor chains (`Foo#bar()` vs `Baz#bar()`) for exactly
+/// that reason, even though both start from a `bar()` symbol with no
+/// receiver encoded.
 fn symbol_to_scip_name(
     symbol: &str,
     display_name: &str,
@@ -725,80 +1014,107 @@ fn symbol_to_scip_name_full(
     line_number: Option<usize>,
     target_type: Option<&str>,
 ) -> String {
-    // Step 1: Strip "rust-analyzer cargo " prefix
-    let s = symbol
-        .strip_prefix("rust-analyzer cargo ")
-        .unwrap_or_else(|| {
-            panic!(
-                "Symbol does not start with 'rust-analyzer cargo ': {}",
-                symbol
-            )
-        });
+    use scip_symbol::DescriptorKind;
 
-    // Step 2 & 3: Check if s ends with "display_name()."
-    let expected_suffix = format!("{}().", display_name);
+    let mut parsed = scip_symbol::parse_symbol(symbol)
+        .unwrap_or_else(|| panic!("Symbol does not parse as a SCIP symbol: {}", symbol));
 
-    if !s.ends_with(&expected_suffix) {
-        panic!("Symbol does not end with '{}': {}", expected_suffix, symbol);
+    if parsed.scheme != "rust-analyzer" || parsed.package.manager != "cargo" {
+        panic!(
+            "Symbol does not start with 'rust-analyzer cargo ': {}",
+            symbol
+        );
+    }
+    match parsed.last_descriptor() {
+        Some(d) if d.kind == DescriptorKind::Method && d.name == display_name => {}
+        _ => panic!(
+            "Symbol does not end with '{}().': {}",
+            display_name, symbol
+        ),
     }
 
-    // Delete the last character of s
-    let mut result = s[..s.len() - 1].to_string();
-
-    // If we have a signature, try to add type info for disambiguation
+    // If we have a signature, try to add type info for disambiguation.
     // This helps distinguish e.g., Mul<&Scalar>::mul vs Mul<&MontgomeryPoint>::mul
+    // by decorating the trait descriptor right before the trailing method
+    // descriptor, e.g. "montgomery/Mul#mul()" -> "montgomery/Mul<Scalar>#mul()".
     if let Some(sig) = signature {
         if let Some(type_info) = extract_impl_type_info(sig) {
-            // Check if this looks like a trait method (contains #)
-            // e.g., "4.1.3 montgomery/Mul#mul()"
-            if result.contains('#') {
-                // Insert the type parameter before the #
-                // "montgomery/Mul#mul()" -> "montgomery/Mul<Scalar>#mul()"
-                if let Some(hash_pos) = result.rfind('#') {
-                    result = format!(
-                        "{}<{}>{}",
-                        &result[..hash_pos],
-                        type_info,
-                        &result[hash_pos..]
-                    );
-                }
+            let method_index = parsed.descriptors.len() - 1;
+            if method_index > 0 && parsed.descriptors[method_index - 1].kind == DescriptorKind::Type
+            {
+                let trait_descriptor = &mut parsed.descriptors[method_index - 1];
+                trait_descriptor.name = format!("{}<{}>", trait_descriptor.name, type_info);
             }
         }
     }
 
-    // If Self type is provided and the symbol is missing it (verus-analyzer inconsistency),
-    // insert the Self type to make it consistent with rust-analyzer format.
+    // If Self type is provided and the symbol is missing it (verus-analyzer
+    // inconsistency -- a lone Type descriptor for the trait, with no
+    // preceding one for Self), insert a Type descriptor for it.
     // e.g., "montgomery/Mul<Scalar>#mul()" -> "montgomery/MontgomeryPoint#Mul<Scalar>#mul()"
+    //
+    // A lone Type descriptor is ambiguous on its own: for a trait impl it's
+    // the trait (Self is genuinely missing), but for an inherent impl
+    // (`impl Scalar { fn add(..) }`) it's already Self, and rust-analyzer's
+    // symbol is complete. Distinguish the two structurally by comparing the
+    // descriptor's name to the Self type itself (ignoring a leading `&`,
+    // since `&self` makes self_type a reference but Self is never one) --
+    // only insert when they differ.
     if let Some(self_t) = self_type {
-        if is_missing_self_type(&result) {
-            // Find the position after "module/" to insert the Self type
-            // Pattern: "version module/Trait#method()" or "version module/Trait<T>#method()"
-            if let Some(slash_pos) = result.rfind('/') {
-                // Insert Self type after the slash, before the trait
-                let before_slash = &result[..=slash_pos];
-                let after_slash = &result[slash_pos + 1..];
-                result = format!("{}{}#{}", before_slash, self_t, after_slash);
+        let trait_index = parsed
+            .descriptors
+            .iter()
+            .position(|d| d.kind == DescriptorKind::Type);
+        let type_count = parsed
+            .descriptors
+            .iter()
+            .filter(|d| d.kind == DescriptorKind::Type)
+            .count();
+        if type_count == 1 {
+            if let Some(trait_index) = trait_index {
+                let already_self = parsed.descriptors[trait_index]
+                    .name
+                    .trim_start_matches('&')
+                    == self_t.trim_start_matches('&');
+                if !already_self {
+                    parsed.descriptors.insert(
+                        trait_index,
+                        scip_symbol::Descriptor {
+                            name: self_t.to_string(),
+                            kind: DescriptorKind::Type,
+                        },
+                    );
+                }
             }
         }
     }
 
-    // If target_type is provided, add it as a type parameter to the struct name.
-    // This enriches the symbol to be more like rust-analyzer's format.
+    // If target_type is provided, decorate the struct's own Type descriptor
+    // (the first one) with it, enriching the symbol toward rust-analyzer's
+    // format, unless it's already decorated.
     // e.g., "window/NafLookupTable5#From<&EdwardsPoint>#from()"
     //    -> "window/NafLookupTable5<ProjectiveNielsPoint>#From<&EdwardsPoint>#from()"
     if let Some(target_t) = target_type {
-        // Find the struct name (first # after the module path)
-        // Pattern: "version module/StructName#Trait..." or "version module/StructName#Trait<T>#..."
-        if let Some(first_hash) = result.find('#') {
-            // Check if there's already a type parameter before this #
-            let before_hash = &result[..first_hash];
-            if !before_hash.ends_with('>') {
-                // No existing type parameter, add one
-                result = format!("{}<{}>{}", before_hash, target_t, &result[first_hash..]);
+        if let Some(struct_descriptor) = parsed
+            .descriptors
+            .iter_mut()
+            .find(|d| d.kind == DescriptorKind::Type)
+        {
+            if !struct_descriptor.name.ends_with('>') {
+                struct_descriptor.name = format!("{}<{}>", struct_descriptor.name, target_t);
             }
         }
     }
 
+    let mut result = format!(
+        "{} {}",
+        parsed.package.name_and_version(),
+        parsed.render_descriptors()
+    );
+    // Drop the trailing "." that terminates the method descriptor -- this
+    // crate's scip name format omits it.
+    result.pop();
+
     // If line number is provided (and no target_type), add it as a suffix for disambiguation.
     // This is a last resort for cases where symbol+signature+self_type are all identical
     // (e.g., generic trait impls that differ only in type parameters not in the signature).
@@ -811,49 +1127,34 @@ fn symbol_to_scip_name_full(
 
 /// Convert symbol to a path format with specified separator
 fn symbol_to_path_with_sep(symbol: &str, display_name: &str, sep: &str) -> String {
-    let mut s = symbol;
-    let mut crate_name = String::new();
-
-    // Skip "rust-analyzer cargo " prefix and extract crate name
-    if let Some(rest) = symbol.strip_prefix("rust-analyzer cargo ") {
-        s = rest;
-        // Extract crate name (everything before the first space, which precedes the version)
-        if let Some(space_pos) = s.find(' ') {
-            crate_name = s[..space_pos].replace('-', "_");
-            s = &s[space_pos + 1..]; // Move past crate name
-        }
-    }
+    let angle_generics =
+        regex::Regex::new(r"<[^>]*>").unwrap_or_else(|_| regex::Regex::new(r"").unwrap());
 
-    // Skip version part if present (e.g., "4.1.3 ")
-    if let Some(pos) = s.find(|c: char| c.is_ascii_digit()) {
-        if let Some(space_pos) = s[pos..].find(' ') {
-            s = s[(pos + space_pos + 1)..].trim();
+    let mut crate_name = String::new();
+    let mut parts: Vec<String> = Vec::new();
+
+    if let Some(parsed) = scip_symbol::parse_symbol(symbol) {
+        crate_name = parsed.package.name.replace('-', "_");
+        for descriptor in &parsed.descriptors {
+            // Type parameters and method parameters don't name a path segment.
+            if matches!(
+                descriptor.kind,
+                scip_symbol::DescriptorKind::TypeParameter | scip_symbol::DescriptorKind::Parameter
+            ) {
+                continue;
+            }
+            // Strip any `<...>` type-parameter decoration this crate adds
+            // for disambiguation -- it isn't part of the path.
+            let name = angle_generics
+                .replace_all(&descriptor.name, "")
+                .replace('-', "_");
+            if !name.is_empty() {
+                parts.push(name);
+            }
         }
     }
 
-    let sep_char = sep.chars().next().unwrap_or('/');
-    let mut clean_path = s
-        .trim_end_matches('.')
-        .replace('-', "_")
-        .replace(['[', ']', '#'], sep)
-        .replace('/', sep)
-        .trim_end_matches(sep_char)
-        .replace(&['`', '(', ')', '[', ']'][..], "");
-
-    // Clean up double separators
-    let double_sep = format!("{}{}", sep, sep);
-    while clean_path.contains(&double_sep) {
-        clean_path = clean_path.replace(&double_sep, sep);
-    }
-
-    // Remove angle-bracketed generics
-    let re = regex::Regex::new(r"<[^>]*>").unwrap_or_else(|_| regex::Regex::new(r"").unwrap());
-    clean_path = re.replace_all(&clean_path, "").to_string();
-
-    // Clean up leading/trailing separators
-    clean_path = clean_path
-        .trim_matches(&sep.chars().collect::<Vec<_>>()[..])
-        .to_string();
+    let mut clean_path = parts.join(sep);
 
     // Add crate name prefix if we have one and it's not already there
     if !crate_name.is_empty() && !clean_path.starts_with(&crate_name) {
@@ -865,6 +1166,10 @@ fn symbol_to_path_with_sep(symbol: &str, display_name: &str, sep: &str) -> Strin
         clean_path = format!("{}{}{}", clean_path, sep, display_name)
     }
 
+    clean_path = clean_path
+        .trim_matches(&sep.chars().collect::<Vec<_>>()[..])
+        .to_string();
+
     // Truncate if too long
     if clean_path.len() > 200 {
         clean_path.truncate(200);
@@ -889,19 +1194,33 @@ pub fn symbol_to_path(symbol: &str, display_name: &str) -> String {
 /// so lines_start and lines_end will be the same (or close for multi-line spans).
 /// For accurate function body spans, use `convert_to_atoms_with_parsed_spans` instead.
 pub fn convert_to_atoms_with_lines(
-    call_graph: &HashMap<String, FunctionNode>,
+    call_graph: &HashMap<FullyQualifiedSymbol, FunctionNode>,
     symbol_to_display_name: &HashMap<String, String>,
 ) -> Vec<AtomWithLines> {
-    convert_to_atoms_with_lines_internal(call_graph, symbol_to_display_name, None)
+    convert_to_atoms_with_lines_internal(call_graph, symbol_to_display_name, None, false)
 }
 
 /// Convert call graph to atoms with accurate line numbers by parsing source files.
 ///
 /// This version uses verus_syn to parse source files and get accurate function body spans.
+///
+/// `disambiguate_by_line` controls the last-resort disambiguator for impls that
+/// share a `base_scip_name` with no discriminating type and no unique
+/// [`canonical_impl_key`]: when `true`, such impls fall back to a `@{line}`
+/// suffix (unstable across unrelated edits to the file); when `false`, they
+/// keep their shared `base_scip_name` instead.
+///
+/// `span_cache_path`, when given, is passed to
+/// [`verus_parser::build_function_span_map_cached`] instead of the
+/// non-cached [`verus_parser::build_function_span_map`], so a repeat CLI
+/// invocation (or a `--watch` loop) skips re-parsing any source file whose
+/// content hash hasn't changed since the cache was last written.
 pub fn convert_to_atoms_with_parsed_spans(
-    call_graph: &HashMap<String, FunctionNode>,
+    call_graph: &HashMap<FullyQualifiedSymbol, FunctionNode>,
     symbol_to_display_name: &HashMap<String, String>,
     project_root: &Path,
+    disambiguate_by_line: bool,
+    span_cache_path: Option<&Path>,
 ) -> Vec<AtomWithLines> {
     // Collect all unique relative paths
     let relative_paths: Vec<String> = call_graph
@@ -911,10 +1230,21 @@ pub fn convert_to_atoms_with_parsed_spans(
         .into_iter()
         .collect();
 
-    // Build the span map by parsing all source files
-    let span_map = verus_parser::build_function_span_map(project_root, &relative_paths);
+    // Build the span map by parsing all source files, reusing cached spans
+    // for unchanged files when a cache path is given.
+    let span_map = match span_cache_path {
+        Some(cache_path) => {
+            verus_parser::build_function_span_map_cached(project_root, &relative_paths, cache_path)
+        }
+        None => verus_parser::build_function_span_map(project_root, &relative_paths),
+    };
 
-    convert_to_atoms_with_lines_internal(call_graph, symbol_to_display_name, Some(&span_map))
+    convert_to_atoms_with_lines_internal(
+        call_graph,
+        symbol_to_display_name,
+        Some(&span_map),
+        disambiguate_by_line,
+    )
 }
 
 /// Internal function that does the actual conversion.
@@ -923,9 +1253,10 @@ pub fn convert_to_atoms_with_parsed_spans(
 /// 2. Build a map: raw_symbol → list of final_scip_names
 /// 3. Resolve dependencies using the map (include all matches for ambiguous refs)
 fn convert_to_atoms_with_lines_internal(
-    call_graph: &HashMap<String, FunctionNode>,
+    call_graph: &HashMap<FullyQualifiedSymbol, FunctionNode>,
     symbol_to_display_name: &HashMap<String, String>,
     span_map: Option<&HashMap<(String, String, usize), usize>>,
+    disambiguate_by_line: bool,
 ) -> Vec<AtomWithLines> {
     // === Phase 1: Compute line ranges and base scip_names for all nodes ===
     struct NodeData<'a> {
@@ -1043,28 +1374,66 @@ fn convert_to_atoms_with_lines_internal(
                 > 1;
 
             if is_duplicate {
-                // Try to use discriminating type first, fall back to line number
+                // Try a discriminating type first, then a canonical signature
+                // hash (stable across edits that don't touch the signature),
+                // and only fall back to a source line number (unstable
+                // across unrelated edits) if neither disambiguates and the
+                // caller opted into that fallback.
                 if let Some(Some(target_type)) = node_discriminating_type.get(&idx) {
-                    symbol_to_scip_name_full(
+                    let scip_name = symbol_to_scip_name_full(
                         &data.node.symbol,
                         &data.node.display_name,
                         Some(&data.node.signature_text),
                         data.node.self_type.as_deref(),
                         None, // No line number needed
                         Some(target_type),
-                    )
-                } else if data.lines_start > 0 {
-                    // Fall back to line number if no discriminating type found
-                    symbol_to_scip_name_full(
+                    );
+                    diagnostics::trace_selftype(
                         &data.node.symbol,
-                        &data.node.display_name,
-                        Some(&data.node.signature_text),
                         data.node.self_type.as_deref(),
-                        Some(data.lines_start),
-                        None,
-                    )
+                        &data.node.signature_text,
+                        &scip_name,
+                    );
+                    scip_name
                 } else {
-                    data.base_scip_name.clone()
+                    let canonical_key = canonical_impl_key(
+                        &data.node.signature_text,
+                        data.node.self_type.as_deref(),
+                    );
+                    let canonical_is_unique = scip_name_to_nodes[data.base_scip_name.as_str()]
+                        .iter()
+                        .filter(|&&other_idx| {
+                            canonical_impl_key(
+                                &node_data[other_idx].node.signature_text,
+                                node_data[other_idx].node.self_type.as_deref(),
+                            ) == canonical_key
+                        })
+                        .count()
+                        == 1;
+
+                    if canonical_is_unique {
+                        format!("{}@impl={}", data.base_scip_name, canonical_key)
+                    } else if disambiguate_by_line && data.lines_start > 0 {
+                        // Fall back to line number if signatures genuinely tie
+                        let scip_name = symbol_to_scip_name_full(
+                            &data.node.symbol,
+                            &data.node.display_name,
+                            Some(&data.node.signature_text),
+                            data.node.self_type.as_deref(),
+                            Some(data.lines_start),
+                            None,
+                        );
+                        diagnostics::trace_disambig(
+                            &data.node.symbol,
+                            data.node.self_type.as_deref(),
+                            &data.node.signature_text,
+                            &scip_name,
+                            data.lines_start,
+                        );
+                        scip_name
+                    } else {
+                        data.base_scip_name.clone()
+                    }
                 }
             } else {
                 data.base_scip_name.clone()
@@ -1095,6 +1464,106 @@ fn convert_to_atoms_with_lines_internal(
             });
     }
 
+    /// How a callee's type hints resolved against the candidates sharing
+    /// its raw symbol.
+    enum CandidateResolution {
+        /// Exactly one candidate's normalized type context covers every
+        /// discriminating hint -- the one dependency edge to record.
+        Unique(String),
+        /// No single candidate uniquely covers the hints (including the
+        /// no-hints case) -- every remaining candidate is recorded as a
+        /// (possibly spurious) edge, and flagged as ambiguous.
+        Ambiguous(Vec<String>),
+    }
+
+    /// Resolve a callee's type hints against the candidates sharing its raw
+    /// symbol, via normalized-type set subtraction: a hint "discriminates"
+    /// a group of candidates when it appears in some but not all of their
+    /// normalized type contexts, and a candidate is selected when it's the
+    /// only one whose context covers every discriminating hint.
+    fn resolve_candidates(
+        type_hints: &[TypeHint],
+        candidates: &[ScipNameWithContext],
+    ) -> CandidateResolution {
+        if candidates.len() == 1 {
+            return CandidateResolution::Unique(candidates[0].scip_name.clone());
+        }
+        if type_hints.is_empty() {
+            return CandidateResolution::Ambiguous(
+                candidates.iter().map(|c| c.scip_name.clone()).collect(),
+            );
+        }
+
+        let normalized_contexts: Vec<HashSet<String>> = candidates
+            .iter()
+            .map(|c| c.type_context.iter().map(|t| normalized_type_name(t)).collect())
+            .collect();
+
+        // Prefer a hint's first generic argument as the discriminator --
+        // an exact match on the actual target type (e.g. the
+        // `RistrettoPoint` in `id::<Mul<RistrettoPoint>>()`) -- falling
+        // back to the flattened hint names (a weaker, generic-parameter-
+        // inclusive match) only when no first-argument hint discriminates.
+        let first_arg_hints: HashSet<String> = type_hints
+            .iter()
+            .filter_map(|hint| hint.args.first())
+            .map(|arg| normalized_type_name(&arg.name))
+            .collect();
+        let flattened_hints: HashSet<String> = type_hints
+            .iter()
+            .flat_map(|h| h.flatten())
+            .map(normalized_type_name)
+            .collect();
+
+        // Keep hints that appear in some but not all candidates' contexts
+        // -- set subtraction over the normalized context sets, not a raw
+        // string scan.
+        let discriminate = |hints: &HashSet<String>| -> HashSet<String> {
+            hints
+                .iter()
+                .filter(|hint| {
+                    let matching_count = normalized_contexts
+                        .iter()
+                        .filter(|ctx| ctx.contains(*hint))
+                        .count();
+                    matching_count > 0 && matching_count < candidates.len()
+                })
+                .cloned()
+                .collect()
+        };
+
+        let from_first_args = discriminate(&first_arg_hints);
+        let discriminating_hints = if !from_first_args.is_empty() {
+            from_first_args
+        } else {
+            discriminate(&flattened_hints)
+        };
+
+        let hints_to_match = if !discriminating_hints.is_empty() {
+            &discriminating_hints
+        } else {
+            // No hint discriminates at all -- match on the full flattened
+            // set so at least an unambiguous single candidate containing
+            // none of this is still preferred over blind inclusion.
+            &flattened_hints
+        };
+
+        let matched: Vec<usize> = normalized_contexts
+            .iter()
+            .enumerate()
+            .filter(|(_, ctx)| hints_to_match.iter().all(|hint| ctx.contains(hint)))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        if matched.len() == 1 {
+            CandidateResolution::Unique(candidates[matched[0]].scip_name.clone())
+        } else {
+            CandidateResolution::Ambiguous(
+                candidates.iter().map(|c| c.scip_name.clone()).collect(),
+            )
+        }
+    }
+
     // === Phase 4: Build final atoms with resolved dependencies ===
     node_data
         .into_iter()
@@ -1102,67 +1571,19 @@ fn convert_to_atoms_with_lines_internal(
         .map(|(data, scip_name)| {
             // Resolve dependencies: map raw symbols to their full scip_names
             let mut dependencies = HashSet::new();
+            let mut ambiguous_dependencies = HashSet::new();
             for callee in &data.node.callees {
                 // Check if this callee is a project function with known scip_names
                 if let Some(scip_name_contexts) = raw_symbol_to_scip_names.get(&callee.symbol) {
-                    if scip_name_contexts.len() == 1 {
-                        // Only one implementation - use it directly
-                        dependencies.insert(scip_name_contexts[0].scip_name.clone());
-                    } else if !callee.type_hints.is_empty() {
-                        // Multiple implementations - try to match using type hints
-                        // First, find types in call-site hints that DON'T appear in ALL impl contexts
-                        // (i.e., discriminating types like ProjectiveNielsPoint vs AffineNielsPoint)
-                        let discriminating_hints: Vec<_> = callee
-                            .type_hints
-                            .iter()
-                            .filter(|hint| {
-                                // Count how many impls have this type in their context
-                                let matching_count = scip_name_contexts
-                                    .iter()
-                                    .filter(|ctx| ctx.type_context.iter().any(|t| t == *hint))
-                                    .count();
-                                // Keep hints that match some but not all impls
-                                matching_count > 0 && matching_count < scip_name_contexts.len()
-                            })
-                            .collect();
-
-                        let matched: Vec<_> = if !discriminating_hints.is_empty() {
-                            // Use discriminating hints to filter
-                            scip_name_contexts
-                                .iter()
-                                .filter(|ctx| {
-                                    discriminating_hints
-                                        .iter()
-                                        .any(|hint| ctx.type_context.iter().any(|t| t == *hint))
-                                })
-                                .collect()
-                        } else {
-                            // Fallback: use all hints
-                            scip_name_contexts
-                                .iter()
-                                .filter(|ctx| {
-                                    callee.type_hints.iter().any(|hint| {
-                                        ctx.type_context
-                                            .iter()
-                                            .any(|t| t.contains(hint) || hint.contains(t))
-                                    })
-                                })
-                                .collect()
-                        };
-
-                        if matched.len() == 1 {
-                            // Found exactly one match - use it
-                            dependencies.insert(matched[0].scip_name.clone());
-                        } else {
-                            // Still ambiguous - include all
-                            for ctx in scip_name_contexts {
-                                dependencies.insert(ctx.scip_name.clone());
-                            }
+                    match resolve_candidates(&callee.type_hints, scip_name_contexts) {
+                        CandidateResolution::Unique(scip_name) => {
+                            dependencies.insert(scip_name);
                         }
-                    } else {
-                        // No type hints - include all possible implementations
-                        for ctx in scip_name_contexts {
-                            dependencies.insert(ctx.scip_name.clone());
+                        CandidateResolution::Ambiguous(scip_names) => {
+                            for name in scip_names {
+                                dependencies.insert(name.clone());
+                                ambiguous_dependencies.insert(name);
+                            }
                         }
                     }
                 } else {
@@ -1180,6 +1601,7 @@ fn convert_to_atoms_with_lines_internal(
                 display_name: data.node.display_name.clone(),
                 scip_name,
                 dependencies,
+                ambiguous_dependencies,
                 code_path: data.node.relative_path.clone(),
                 code_text: CodeTextInfo {
                     lines_start: data.lines_start,
@@ -1222,16 +1644,394 @@ pub fn find_duplicate_scip_names(atoms: &[AtomWithLines]) -> Vec<DuplicateScipNa
     scip_name_to_atoms
         .into_iter()
         .filter(|(_, atoms)| atoms.len() > 1)
-        .map(|(scip_name, atoms)| DuplicateScipName {
-            scip_name,
-            occurrences: atoms
-                .into_iter()
-                .map(|a| DuplicateOccurrence {
-                    display_name: a.display_name.clone(),
-                    code_path: a.code_path.clone(),
-                    lines_start: a.code_text.lines_start,
-                })
-                .collect(),
+        .map(|(scip_name, atoms)| {
+            for atom in &atoms {
+                diagnostics::trace_dupe(&atom.scip_name, &scip_name);
+            }
+            DuplicateScipName {
+                scip_name,
+                occurrences: atoms
+                    .into_iter()
+                    .map(|a| DuplicateOccurrence {
+                        display_name: a.display_name.clone(),
+                        code_path: a.code_path.clone(),
+                        lines_start: a.code_text.lines_start,
+                    })
+                    .collect(),
+            }
         })
         .collect()
 }
+
+/// Repair duplicate `scip_name`s in place by appending a deterministic
+/// disambiguator suffix, the same role a `u32` disambiguator plays in
+/// rustc's `DisambiguatedDefPathData`: sibling items that would otherwise
+/// mangle to the same path get a number appended so each one is unique.
+///
+/// Atoms sharing a `scip_name` are sorted by `(code_path, lines_start)` --
+/// a key that doesn't depend on iteration or hashing order -- and suffixed
+/// `#0`, `#1`, ... in that order, so the result is stable across runs given
+/// identical input. Names that are already unique are left untouched.
+pub fn disambiguate_scip_names(atoms: &mut [AtomWithLines]) {
+    let mut scip_name_to_indices: HashMap<String, Vec<usize>> = HashMap::new();
+    for (idx, atom) in atoms.iter().enumerate() {
+        scip_name_to_indices
+            .entry(atom.scip_name.clone())
+            .or_default()
+            .push(idx);
+    }
+
+    for (scip_name, mut indices) in scip_name_to_indices {
+        if indices.len() <= 1 {
+            continue;
+        }
+        indices.sort_by(|&a, &b| {
+            (&atoms[a].code_path, atoms[a].code_text.lines_start)
+                .cmp(&(&atoms[b].code_path, atoms[b].code_text.lines_start))
+        });
+        for (disambiguator, idx) in indices.into_iter().enumerate() {
+            atoms[idx].scip_name = format!("{scip_name}#{disambiguator}");
+        }
+    }
+}
+
+/// Whether `cmd` can be found on `PATH`, by trying to invoke `cmd --version`.
+fn command_exists(cmd: &str) -> bool {
+    std::process::Command::new(cmd)
+        .arg("--version")
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok()
+}
+
+/// Generate SCIP-based call graph atoms for `project_path`: reuse an
+/// existing `data/index.scip.json` unless `regenerate_scip` forces a fresh
+/// `verus-analyzer scip` + `scip print --json` pass, then parse it into a
+/// call graph and convert that into [`AtomWithLines`] with accurate source
+/// spans.
+///
+/// This is the library entry point behind the `atoms` subcommand -- embed it
+/// directly to get `Vec<AtomWithLines>` in-process instead of shelling out to
+/// the `scip-atoms` binary and re-parsing its JSON output. Errors are
+/// returned rather than printed, via [`error::ProbeError`]'s
+/// `ExternalTool`/`ProjectValidation`/`ScipParse`/`FileIo` variants, so a
+/// caller can distinguish a missing `verus-analyzer` from an invalid project
+/// path from a malformed SCIP index.
+pub fn run_atoms(
+    project_path: &Path,
+    regenerate_scip: bool,
+    disambiguate_by_line: bool,
+) -> error::ProbeResult<Vec<AtomWithLines>> {
+    if !project_path.exists() {
+        return Err(error::ProbeError::ProjectValidation(format!(
+            "Project path does not exist: {}",
+            project_path.display()
+        )));
+    }
+    if !project_path.join("Cargo.toml").exists() {
+        return Err(error::ProbeError::ProjectValidation(format!(
+            "Not a valid Rust project (Cargo.toml not found): {}",
+            project_path.display()
+        )));
+    }
+
+    let data_dir = project_path.join("data");
+    let cached_scip_path = data_dir.join("index.scip");
+    let cached_json_path = data_dir.join("index.scip.json");
+
+    if !cached_json_path.exists() || regenerate_scip {
+        if !command_exists("verus-analyzer") {
+            return Err(error::ProbeError::external_tool(
+                "verus-analyzer",
+                "not found in PATH (install with: rustup component add verus-analyzer)",
+            ));
+        }
+        if !command_exists("scip") {
+            return Err(error::ProbeError::external_tool(
+                "scip",
+                "not found in PATH (install with: cargo install scip-cli)",
+            ));
+        }
+
+        let scip_status = std::process::Command::new("verus-analyzer")
+            .args(["scip", "."])
+            .current_dir(project_path)
+            .status()
+            .map_err(|e| {
+                error::ProbeError::external_tool("verus-analyzer", format!("failed to run: {e}"))
+            })?;
+        if !scip_status.success() {
+            return Err(error::ProbeError::external_tool(
+                "verus-analyzer",
+                format!("scip failed with status: {scip_status}"),
+            ));
+        }
+
+        let generated_scip_path = project_path.join("index.scip");
+        if !generated_scip_path.exists() {
+            return Err(error::ProbeError::external_tool(
+                "verus-analyzer",
+                format!(
+                    "index.scip not found at {} (scip may have failed silently)",
+                    generated_scip_path.display()
+                ),
+            ));
+        }
+
+        if !data_dir.exists() {
+            std::fs::create_dir_all(&data_dir).map_err(|e| error::ProbeError::file_io(&data_dir, e))?;
+        }
+        std::fs::rename(&generated_scip_path, &cached_scip_path)
+            .map_err(|e| error::ProbeError::file_io(&generated_scip_path, e))?;
+
+        let scip_output = std::process::Command::new("scip")
+            .args([
+                "print",
+                "--json",
+                cached_scip_path.to_str().unwrap_or_default(),
+            ])
+            .output()
+            .map_err(|e| error::ProbeError::external_tool("scip", format!("failed to run: {e}")))?;
+        if !scip_output.status.success() {
+            let stderr = String::from_utf8_lossy(&scip_output.stderr);
+            return Err(error::ProbeError::external_tool(
+                "scip",
+                format!("print failed with status: {} ({stderr})", scip_output.status),
+            ));
+        }
+        std::fs::write(&cached_json_path, scip_output.stdout)
+            .map_err(|e| error::ProbeError::file_io(&cached_json_path, e))?;
+    }
+
+    let scip_json_bytes =
+        std::fs::read(&cached_json_path).map_err(|e| error::ProbeError::file_io(&cached_json_path, e))?;
+
+    if !regenerate_scip {
+        if let Some(atoms) = atom_cache::read(project_path, &scip_json_bytes) {
+            return Ok(atoms);
+        }
+    }
+
+    let scip_index = parse_scip_json(cached_json_path.to_str().unwrap_or_default())
+        .map_err(|e| error::ProbeError::ScipParse(e.to_string()))?;
+
+    let (call_graph, symbol_to_display_name, _all_function_symbols) =
+        build_call_graph(&scip_index, Some(project_path));
+
+    let span_cache_path = data_dir.join("span_map_cache.json");
+    let mut atoms = convert_to_atoms_with_parsed_spans(
+        &call_graph,
+        &symbol_to_display_name,
+        project_path,
+        disambiguate_by_line,
+        Some(&span_cache_path),
+    );
+    disambiguate_scip_names(&mut atoms);
+
+    // Best-effort: a failure writing the archived cache shouldn't fail an
+    // otherwise-successful `atoms` run, just mean the next run recomputes.
+    if let Err(e) = atom_cache::write(project_path, &scip_json_bytes, &atoms) {
+        eprintln!("Warning: failed to write atom cache: {e}");
+    }
+
+    Ok(atoms)
+}
+
+/// List every function in `path` (file or directory) via
+/// [`verus_parser::parse_all_functions`] -- the library entry point behind
+/// the `functions` subcommand.
+pub fn run_functions(
+    path: &Path,
+    include_verus_constructs: bool,
+    include_methods: bool,
+    show_visibility: bool,
+    show_kind: bool,
+) -> error::ProbeResult<verus_parser::ParsedOutput> {
+    if !path.exists() {
+        return Err(error::ProbeError::ProjectValidation(format!(
+            "Path does not exist: {}",
+            path.display()
+        )));
+    }
+    Ok(verus_parser::parse_all_functions(
+        path,
+        include_verus_constructs,
+        include_methods,
+        show_visibility,
+        show_kind,
+        true,
+    ))
+}
+
+/// Run Verus verification for `project_path` and analyze the result -- the
+/// library entry point behind a live (non-`--watch`, non-`--from-file`) run
+/// of the `verify` subcommand. Returns the full
+/// [`verification::AnalysisResult`] (whose `verification` field is the
+/// [`verification::VerificationResult`] downstream tools want) instead of
+/// writing it to a file, so an embedding caller gets the parsed result
+/// in-process.
+#[allow(clippy::too_many_arguments)]
+pub fn run_verify(
+    project_path: &Path,
+    package: Option<&str>,
+    verify_only_module: Option<&str>,
+    verify_function: Option<&str>,
+) -> error::ProbeResult<verification::AnalysisResult> {
+    if !project_path.exists() {
+        return Err(error::ProbeError::ProjectValidation(format!(
+            "Project path does not exist: {}",
+            project_path.display()
+        )));
+    }
+
+    let runner = verification::VerusRunner::new();
+    let (captured, exit_code) = runner
+        .run_verification(project_path, package, verify_only_module, verify_function, None)
+        .map_err(|e| error::ProbeError::external_tool("verus", e.to_string()))?;
+
+    let analyzer = verification::VerificationAnalyzer::new();
+    Ok(analyzer.analyze_output(
+        project_path,
+        &captured.text,
+        Some(exit_code),
+        verify_only_module,
+        verify_function,
+        None,
+    ))
+}
+
+#[cfg(test)]
+mod symbol_to_scip_name_tests {
+    use super::*;
+
+    #[test]
+    fn inserts_self_type_through_a_fully_qualified_trait_path() {
+        // The trait descriptor sits behind two namespace segments; Self is
+        // still missing and should be inserted right before it regardless.
+        let symbol = "rust-analyzer cargo my-crate 0.1.0 std/ops/Mul#mul().";
+        let scip_name =
+            symbol_to_scip_name_full(symbol, "mul", None, Some("MontgomeryPoint"), None, None);
+        assert_eq!(scip_name, "my-crate 0.1.0 std/ops/MontgomeryPoint#Mul#mul()");
+    }
+
+    #[test]
+    fn inserts_self_type_for_an_associated_function_with_no_self_param() {
+        // `From::from` takes no `self`, but Self is still read from the
+        // enclosing impl's AST context and should still be inserted.
+        let symbol = "rust-analyzer cargo my-crate 0.1.0 window/From#from().";
+        let scip_name =
+            symbol_to_scip_name_full(symbol, "from", None, Some("LookupTable"), None, None);
+        assert_eq!(scip_name, "my-crate 0.1.0 window/LookupTable#From#from()");
+    }
+
+    #[test]
+    fn inserts_self_type_alongside_a_const_generic_type_parameter() {
+        // `impl<const N: usize> Mul<Scalar> for Foo<N>` carries a
+        // TypeParameter descriptor for `N` -- it must not be mistaken for
+        // the lone Type descriptor when deciding whether Self is missing.
+        let symbol = "rust-analyzer cargo my-crate 0.1.0 mymod/[N]Mul#mul().";
+        let scip_name = symbol_to_scip_name_full(symbol, "mul", None, Some("Foo"), None, None);
+        assert_eq!(scip_name, "my-crate 0.1.0 mymod/[N]Foo#Mul#mul()");
+    }
+
+    #[test]
+    fn does_not_duplicate_self_type_for_an_inherent_impl() {
+        // `impl Scalar { fn add(..) }` already has its one Type descriptor
+        // as Self -- inserting again would yield `Scalar#Scalar#add()`.
+        let symbol = "rust-analyzer cargo my-crate 0.1.0 mymod/Scalar#add().";
+        let scip_name = symbol_to_scip_name_full(symbol, "add", None, Some("Scalar"), None, None);
+        assert_eq!(scip_name, "my-crate 0.1.0 mymod/Scalar#add()");
+    }
+
+    #[test]
+    fn does_not_duplicate_self_type_when_self_param_is_a_reference() {
+        // `&self` makes `self_type` a reference (`&Scalar`) even though the
+        // Type descriptor itself never carries `&` -- compare with the `&`
+        // stripped so this inherent-impl case still isn't treated as missing.
+        let symbol = "rust-analyzer cargo my-crate 0.1.0 mymod/Scalar#neg().";
+        let scip_name =
+            symbol_to_scip_name_full(symbol, "neg", None, Some("&Scalar"), None, None);
+        assert_eq!(scip_name, "my-crate 0.1.0 mymod/Scalar#neg()");
+    }
+
+    #[test]
+    fn distinct_receiver_types_never_collide_on_a_shared_trait_method_name() {
+        // Two unrelated impls of the same trait method render to different
+        // descriptor chains because the receiver type is its own Type
+        // segment, not folded away into a single display-derived string.
+        let symbol = "rust-analyzer cargo my-crate 0.1.0 mymod/Bar#bar().";
+        let foo_name = symbol_to_scip_name_full(symbol, "bar", None, Some("Foo"), None, None);
+        let baz_name = symbol_to_scip_name_full(symbol, "bar", None, Some("Baz"), None, None);
+        assert_ne!(foo_name, baz_name);
+        assert_eq!(foo_name, "my-crate 0.1.0 mymod/Foo#Bar#bar()");
+        assert_eq!(baz_name, "my-crate 0.1.0 mymod/Baz#Bar#bar()");
+    }
+}
+
+#[cfg(test)]
+mod disambiguate_scip_names_tests {
+    use super::*;
+
+    fn atom(scip_name: &str, code_path: &str, lines_start: usize) -> AtomWithLines {
+        AtomWithLines {
+            display_name: "add".to_string(),
+            scip_name: scip_name.to_string(),
+            dependencies: HashSet::new(),
+            ambiguous_dependencies: HashSet::new(),
+            code_path: code_path.to_string(),
+            code_text: CodeTextInfo {
+                lines_start,
+                lines_end: lines_start + 1,
+            },
+        }
+    }
+
+    #[test]
+    fn leaves_a_unique_scip_name_untouched() {
+        let mut atoms = vec![atom("my-crate 0.1.0 mymod/Scalar#add()", "src/mymod.rs", 10)];
+        disambiguate_scip_names(&mut atoms);
+        assert_eq!(atoms[0].scip_name, "my-crate 0.1.0 mymod/Scalar#add()");
+    }
+
+    #[test]
+    fn suffixes_duplicates_in_code_path_then_line_order() {
+        let mut atoms = vec![
+            atom("my-crate 0.1.0 mymod/Scalar#add()", "src/b.rs", 5),
+            atom("my-crate 0.1.0 mymod/Scalar#add()", "src/a.rs", 20),
+            atom("my-crate 0.1.0 mymod/Scalar#add()", "src/a.rs", 10),
+        ];
+        disambiguate_scip_names(&mut atoms);
+        assert_eq!(
+            atoms[0].scip_name,
+            "my-crate 0.1.0 mymod/Scalar#add()#2"
+        );
+        assert_eq!(
+            atoms[1].scip_name,
+            "my-crate 0.1.0 mymod/Scalar#add()#1"
+        );
+        assert_eq!(
+            atoms[2].scip_name,
+            "my-crate 0.1.0 mymod/Scalar#add()#0"
+        );
+    }
+
+    #[test]
+    fn is_deterministic_across_input_order() {
+        let mut forward = vec![
+            atom("dup", "src/a.rs", 1),
+            atom("dup", "src/b.rs", 2),
+        ];
+        let mut backward = vec![
+            atom("dup", "src/b.rs", 2),
+            atom("dup", "src/a.rs", 1),
+        ];
+        disambiguate_scip_names(&mut forward);
+        disambiguate_scip_names(&mut backward);
+        let forward_names: HashSet<String> =
+            forward.iter().map(|a| a.scip_name.clone()).collect();
+        let backward_names: HashSet<String> =
+            backward.iter().map(|a| a.scip_name.clone()).collect();
+        assert_eq!(forward_names, backward_names);
+    }
+}
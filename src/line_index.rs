@@ -0,0 +1,170 @@
+//! Byte-offset <-> line conversion for a source file.
+//!
+//! [`LineIndex`] precomputes the byte offset of the start of every line
+//! once per file, then converts a line to its offset by direct lookup and
+//! an offset to its line via binary search --
+//! used to normalize SCIP line numbers against spans reported by other
+//! parsers without falling back to a fixed line-count tolerance.
+
+use std::io;
+use std::path::Path;
+
+/// Read a source file for parsing/span extraction, stripping a leading
+/// byte-order mark first so byte offsets -- and anything compared against
+/// them, like a SCIP occurrence range -- line up with the positions other
+/// tools see on disk rather than being shifted by however many bytes the
+/// BOM itself occupied. A UTF-16 BOM is transcoded to UTF-8; a file that
+/// still isn't valid UTF-8 once any BOM is accounted for is reported as a
+/// warning and decoded lossily rather than failing the caller outright.
+pub fn read_source_file(path: &Path) -> io::Result<String> {
+    let mut bytes = std::fs::read(path)?;
+
+    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        bytes.drain(..3);
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return Ok(decode_utf16(rest, u16::from_le_bytes));
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return Ok(decode_utf16(rest, u16::from_be_bytes));
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok(text),
+        Err(e) => {
+            eprintln!(
+                "Warning: {} is not valid UTF-8; decoding lossily",
+                path.display()
+            );
+            Ok(String::from_utf8_lossy(e.as_bytes()).into_owned())
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|chunk| from_bytes([chunk[0], chunk[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Precomputed newline byte offsets for a single source file.
+pub struct LineIndex {
+    /// Byte offset of the start of each line; `line_starts[0] == 0`.
+    line_starts: Vec<usize>,
+    len: usize,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        for (i, b) in text.bytes().enumerate() {
+            if b == b'\n' {
+                line_starts.push(i + 1);
+            }
+        }
+        LineIndex {
+            line_starts,
+            len: text.len(),
+        }
+    }
+
+    /// Byte offset of the start of `line` (1-indexed). Clamped to the end
+    /// of the file if `line` is past the last line.
+    pub fn offset_of_line(&self, line: usize) -> usize {
+        let idx = line.saturating_sub(1);
+        self.line_starts.get(idx).copied().unwrap_or(self.len)
+    }
+
+    /// 1-indexed line number containing byte `offset`.
+    pub fn line_of_offset(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(idx) => idx + 1,
+            Err(idx) => idx,
+        }
+    }
+
+    /// The text of `line` (1-indexed), excluding its trailing newline.
+    pub fn line_text<'a>(&self, text: &'a str, line: usize) -> &'a str {
+        let start = self.offset_of_line(line);
+        let end = self.offset_of_line(line + 1);
+        text[start..end]
+            .trim_end_matches('\n')
+            .trim_end_matches('\r')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_of_line_finds_line_starts() {
+        let text = "fn a() {}\nfn b() {}\n";
+        let index = LineIndex::new(text);
+        assert_eq!(index.offset_of_line(1), 0);
+        assert_eq!(index.offset_of_line(2), 10);
+    }
+
+    #[test]
+    fn line_of_offset_round_trips_with_offset_of_line() {
+        let text = "fn a() {}\nfn b() {}\nfn c() {}\n";
+        let index = LineIndex::new(text);
+        for line in 1..=3 {
+            let offset = index.offset_of_line(line);
+            assert_eq!(index.line_of_offset(offset), line);
+        }
+    }
+
+    #[test]
+    fn line_text_excludes_the_trailing_newline() {
+        let text = "first\nsecond\n";
+        let index = LineIndex::new(text);
+        assert_eq!(index.line_text(text, 1), "first");
+        assert_eq!(index.line_text(text, 2), "second");
+    }
+
+    #[test]
+    fn offset_of_line_clamps_past_the_last_line() {
+        let text = "only\n";
+        let index = LineIndex::new(text);
+        assert_eq!(index.offset_of_line(5), text.len());
+    }
+
+    #[test]
+    fn read_source_file_strips_a_utf8_bom() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bom.rs");
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice(b"fn main() {}\n");
+        std::fs::write(&path, &bytes).unwrap();
+
+        let content = read_source_file(&path).unwrap();
+        assert_eq!(content, "fn main() {}\n");
+    }
+
+    #[test]
+    fn read_source_file_transcodes_a_utf16_le_bom() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("utf16le.rs");
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in "fn f() {}".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        std::fs::write(&path, &bytes).unwrap();
+
+        let content = read_source_file(&path).unwrap();
+        assert_eq!(content, "fn f() {}");
+    }
+
+    #[test]
+    fn read_source_file_passes_through_plain_utf8() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plain.rs");
+        std::fs::write(&path, b"fn g() {}\n").unwrap();
+
+        let content = read_source_file(&path).unwrap();
+        assert_eq!(content, "fn g() {}\n");
+    }
+}
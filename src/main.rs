@@ -4,21 +4,31 @@
 //! - `atoms`: Generate SCIP-based call graph data with line numbers
 //! - `functions`: List all functions in a Rust/Verus project
 //! - `verify`: Run Verus verification and analyze results (or analyze existing output)
+//! - `check-expectations`: Check `//~` function annotations against actual verification results
 
 use clap::{Parser, Subcommand};
 use scip_atoms::{
-    build_call_graph, convert_to_atoms_with_parsed_spans, find_duplicate_scip_names,
-    parse_scip_json,
-    verification::{AnalysisStatus, VerificationAnalyzer, VerusRunner},
+    build_call_graph, config_file, edge_diagnostics,
+    expect::{self, FunctionExpectationMismatch},
+    find_duplicate_scip_names, parse_scip_json,
+    verification::{
+        AnalysisStatus, FunctionCategory, ProgressReporter, VerificationAnalyzer, VerusRunner,
+    },
     verus_parser::{self, ParsedOutput},
 };
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "scip-atoms")]
 #[command(author, version, about = "Generate compact call graph data and analyze Verus verification", long_about = None)]
 struct Cli {
+    /// Print the effective invocation after layering scip-atoms.toml and
+    /// environment-variable overrides (see `scip_atoms::config_file`) on
+    /// top of the built-in defaults, then exit without running the
+    /// subcommand.
+    #[arg(long, global = true)]
+    print_config: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -37,6 +47,23 @@ enum Commands {
         /// Force regeneration of the SCIP index
         #[arg(short, long)]
         regenerate_scip: bool,
+
+        /// Print a report of call-graph edges that failed to resolve uniquely
+        #[arg(long)]
+        show_edge_diagnostics: bool,
+
+        /// Disambiguate impls that share a scip_name by source line number
+        /// when a canonical signature hash still ties. Off by default since
+        /// a line number shifts on unrelated edits; the signature hash does not.
+        #[arg(long)]
+        disambiguate_by_line: bool,
+
+        /// Output encoding for `output`. `json` is human-readable and the
+        /// default; `binary` is the canonical byte encoding from
+        /// `scip_atoms::canonical`, useful when a downstream tool wants to
+        /// hash the result or needs byte-for-byte reproducible artifacts.
+        #[arg(long, value_enum, default_value = "json")]
+        output_format: AtomsOutputFormat,
     },
 
     /// List all functions in a Rust/Verus project
@@ -103,6 +130,33 @@ enum Commands {
         /// Don't cache the verification output
         #[arg(long)]
         no_cache: bool,
+
+        /// Re-run verification automatically whenever the project's
+        /// source tree changes (requires a project path; incompatible
+        /// with --from-file)
+        #[arg(long)]
+        watch: bool,
+
+        /// Show live progress (a bar on a TTY, periodic lines otherwise)
+        /// while Verus runs, instead of only printing the final summary
+        #[arg(long)]
+        progress: bool,
+    },
+
+    /// Check `//~ VERIFY-FAIL` / `//~ ASSUME` function annotations against
+    /// actual verification results, compiletest/ui_test style
+    CheckExpectations {
+        /// Path to the Rust/Verus project
+        project_path: PathBuf,
+
+        /// Package to verify (for workspace projects)
+        #[arg(short, long)]
+        package: Option<String>,
+
+        /// Rewrite annotations in place to match the current results
+        /// instead of reporting mismatches
+        #[arg(long)]
+        bless: bool,
     },
 }
 
@@ -116,177 +170,66 @@ enum OutputFormat {
     Detailed,
 }
 
-fn check_command_exists(cmd: &str) -> bool {
-    Command::new("which")
-        .arg(cmd)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .map(|status| status.success())
-        .unwrap_or(false)
+/// Output encoding for the `atoms` subcommand. Both encode the exact same
+/// logical content -- only the bytes on disk differ.
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum AtomsOutputFormat {
+    /// Pretty-printed JSON (the default).
+    Json,
+    /// The canonical binary encoding from `scip_atoms::canonical`.
+    Binary,
 }
 
-fn cmd_atoms(project_path: PathBuf, output: PathBuf, regenerate_scip: bool) {
+/// Thin CLI wrapper around [`scip_atoms::run_atoms`]: prints progress and the
+/// final summary, writes `output`, and maps a returned `Err` to a process
+/// exit via [`scip_atoms::error::ProbeError::exit_code`] instead of each
+/// failure path calling `std::process::exit` itself.
+fn cmd_atoms(
+    project_path: PathBuf,
+    output: PathBuf,
+    regenerate_scip: bool,
+    show_edge_diagnostics: bool,
+    disambiguate_by_line: bool,
+    output_format: AtomsOutputFormat,
+) {
     println!("═══════════════════════════════════════════════════════════");
     println!("  SCIP Atoms - Generate Compact Call Graph Data");
     println!("═══════════════════════════════════════════════════════════");
     println!();
 
-    // Verify project path exists
-    if !project_path.exists() {
-        eprintln!(
-            "✗ Error: Project path does not exist: {}",
-            project_path.display()
-        );
-        std::process::exit(1);
-    }
-
-    // Check if it's a valid Rust project
-    let cargo_toml = project_path.join("Cargo.toml");
-    if !cargo_toml.exists() {
-        eprintln!(
-            "✗ Error: Not a valid Rust project (Cargo.toml not found): {}",
-            project_path.display()
-        );
-        std::process::exit(1);
-    }
-    println!("  ✓ Valid Rust project found");
-
-    // Check for existing SCIP JSON in data/ folder
-    let data_dir = project_path.join("data");
-    let cached_scip_path = data_dir.join("index.scip");
-    let cached_json_path = data_dir.join("index.scip.json");
-
-    // Use cached JSON if available and not regenerating
-    if cached_json_path.exists() && !regenerate_scip {
-        println!(
-            "  ✓ Found existing SCIP JSON at {}",
-            cached_json_path.display()
-        );
-        println!("    (use --regenerate-scip to force regeneration)");
-        println!();
-    } else {
-        // Need to generate - check prerequisites
-        if !check_command_exists("verus-analyzer") {
-            eprintln!("✗ Error: verus-analyzer not found in PATH");
-            eprintln!("  Install with: rustup component add verus-analyzer");
-            std::process::exit(1);
-        }
-        if !check_command_exists("scip") {
-            eprintln!("✗ Error: scip not found in PATH");
-            eprintln!("  Install with: cargo install scip-cli");
-            std::process::exit(1);
+    let atoms = match scip_atoms::run_atoms(&project_path, regenerate_scip, disambiguate_by_line) {
+        Ok(atoms) => atoms,
+        Err(e) => {
+            eprintln!("✗ Error: {}", e);
+            std::process::exit(e.exit_code());
         }
-        println!("  ✓ Prerequisites verified (verus-analyzer, scip)");
-        println!();
-
-        // Run verus-analyzer scip to generate index
-        let reason = if regenerate_scip {
-            "(regeneration requested)"
-        } else {
-            "(no existing SCIP data found)"
-        };
-        println!(
-            "Generating SCIP index for {} {}...",
-            project_path.display(),
-            reason
-        );
-        println!("  (This may take a while for large projects)");
-
-        let scip_status = Command::new("verus-analyzer")
-            .args(["scip", "."])
-            .current_dir(&project_path)
-            .status();
+    };
+    println!("  ✓ Converted {} functions to atoms format", atoms.len());
 
-        match scip_status {
-            Ok(status) if status.success() => {
-                println!("  ✓ SCIP index generated successfully");
-            }
-            Ok(status) => {
-                eprintln!(
-                    "✗ Error: verus-analyzer scip failed with status: {}",
-                    status
+    if show_edge_diagnostics {
+        // run_atoms has just ensured data/index.scip.json is up to date, so
+        // re-parse it here to get the call graph edge diagnostics care
+        // about -- the one piece of reporting run_atoms itself doesn't
+        // surface, since it returns only the final Vec<AtomWithLines>.
+        let cached_json_path = project_path.join("data").join("index.scip.json");
+        if let Ok(scip_index) = parse_scip_json(cached_json_path.to_str().unwrap_or_default()) {
+            let (call_graph, _symbol_to_display_name, all_function_symbols) =
+                build_call_graph(&scip_index, Some(&project_path));
+            let diagnostics = edge_diagnostics::diagnose_edges(&call_graph, &all_function_symbols);
+            let problem_count = diagnostics.iter().filter(|d| d.is_problem()).count();
+            if problem_count == 0 {
+                println!("  ✓ All call-graph edges resolved cleanly");
+            } else {
+                println!(
+                    "  ⚠ {} call-graph edge(s) did not resolve cleanly:",
+                    problem_count
                 );
-                eprintln!("  Make sure the project compiles successfully first");
-                std::process::exit(1);
-            }
-            Err(e) => {
-                eprintln!("✗ Error: Failed to run verus-analyzer: {}", e);
-                std::process::exit(1);
+                print!("{}", edge_diagnostics::render_report(&diagnostics));
             }
+            println!();
         }
-
-        let generated_scip_path = project_path.join("index.scip");
-        if !generated_scip_path.exists() {
-            eprintln!(
-                "✗ Error: index.scip not found at {}",
-                generated_scip_path.display()
-            );
-            eprintln!("  verus-analyzer scip may have failed silently");
-            std::process::exit(1);
-        }
-
-        // Ensure data directory exists
-        if !data_dir.exists() {
-            std::fs::create_dir_all(&data_dir).expect("Failed to create data directory");
-        }
-
-        // Move the generated index.scip to data/ folder
-        std::fs::rename(&generated_scip_path, &cached_scip_path)
-            .expect("Failed to move index.scip to data folder");
-        println!("  ✓ index.scip saved to {}", cached_scip_path.display());
-
-        // Convert SCIP to JSON and save to data/ folder
-        println!("Converting index.scip to JSON...");
-
-        let scip_output = Command::new("scip")
-            .args(["print", "--json", cached_scip_path.to_str().unwrap()])
-            .output();
-
-        match scip_output {
-            Ok(output) if output.status.success() => {
-                std::fs::write(&cached_json_path, output.stdout)
-                    .expect("Failed to write SCIP JSON file");
-                println!("  ✓ SCIP JSON saved to {}", cached_json_path.display());
-            }
-            Ok(output) => {
-                eprintln!("✗ Error: scip print failed with status: {}", output.status);
-                if !output.stderr.is_empty() {
-                    eprintln!("  stderr: {}", String::from_utf8_lossy(&output.stderr));
-                }
-                std::process::exit(1);
-            }
-            Err(e) => {
-                eprintln!("✗ Error: Failed to run scip: {}", e);
-                std::process::exit(1);
-            }
-        }
-        println!();
     }
 
-    // Parse SCIP JSON and build call graph
-    println!("Parsing SCIP JSON and building call graph...");
-
-    let scip_index = match parse_scip_json(cached_json_path.to_str().unwrap()) {
-        Ok(idx) => idx,
-        Err(e) => {
-            eprintln!("✗ Failed to parse SCIP JSON: {}", e);
-            std::process::exit(1);
-        }
-    };
-
-    let (call_graph, symbol_to_display_name) = build_call_graph(&scip_index);
-    println!("  ✓ Call graph built with {} functions", call_graph.len());
-    println!();
-
-    // Convert to atoms format with line numbers
-    println!("Converting to atoms format with accurate line numbers...");
-    println!("  Parsing source files with verus_syn for accurate function spans...");
-
-    let atoms =
-        convert_to_atoms_with_parsed_spans(&call_graph, &symbol_to_display_name, &project_path);
-    println!("  ✓ Converted {} functions to atoms format", atoms.len());
-
     // Check for duplicate scip_names
     let duplicates = find_duplicate_scip_names(&atoms);
     if !duplicates.is_empty() {
@@ -310,9 +253,18 @@ fn cmd_atoms(project_path: PathBuf, output: PathBuf, regenerate_scip: bool) {
         println!("    causing problems with downstream tools.");
     }
 
-    // Write the output
-    let json = serde_json::to_string_pretty(&atoms).expect("Failed to serialize JSON");
-    std::fs::write(&output, &json).expect("Failed to write output file");
+    // Write the output. Both formats emit identical logical content --
+    // only the on-disk bytes differ.
+    match output_format {
+        AtomsOutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&atoms).expect("Failed to serialize JSON");
+            std::fs::write(&output, &json).expect("Failed to write output file");
+        }
+        AtomsOutputFormat::Binary => {
+            let bytes = scip_atoms::canonical::encode_atoms(&atoms);
+            std::fs::write(&output, &bytes).expect("Failed to write output file");
+        }
+    }
 
     println!();
     println!("═══════════════════════════════════════════════════════════");
@@ -331,6 +283,9 @@ fn cmd_atoms(project_path: PathBuf, output: PathBuf, regenerate_scip: bool) {
     println!();
 }
 
+/// Thin CLI wrapper around [`scip_atoms::run_functions`]: renders the
+/// requested `format` and maps a returned `Err` to a process exit via
+/// [`scip_atoms::error::ProbeError::exit_code`].
 fn cmd_functions(
     path: PathBuf,
     format: OutputFormat,
@@ -340,21 +295,22 @@ fn cmd_functions(
     show_kind: bool,
     json_output: Option<PathBuf>,
 ) {
-    if !path.exists() {
-        eprintln!("Error: Path does not exist: {}", path.display());
-        std::process::exit(1);
-    }
-
     let include_verus_constructs = !exclude_verus_constructs;
     let include_methods = !exclude_methods;
 
-    let output: ParsedOutput = verus_parser::parse_all_functions(
+    let output: ParsedOutput = match scip_atoms::run_functions(
         &path,
         include_verus_constructs,
         include_methods,
         show_visibility,
         show_kind,
-    );
+    ) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    };
 
     // Determine actual output format
     let actual_format = if json_output.is_some() {
@@ -419,6 +375,281 @@ struct VerificationConfig {
     exit_code: i32,
 }
 
+/// Analyze a verification run's captured output, write the JSON report to
+/// `json_output`, and print the summary -- the tail shared by a live
+/// Verus run, a cached run, and `--from-file` analysis of a saved output.
+/// Returns the resulting status rather than exiting, so callers (a
+/// `--watch` loop in particular) can decide what to do with a failure.
+#[allow(clippy::too_many_arguments)]
+fn analyze_and_report(
+    project_path: &Path,
+    verification_output: &str,
+    exit_code: i32,
+    verify_only_module: Option<&str>,
+    verify_function: Option<&str>,
+    json_output: &Path,
+) -> AnalysisStatus {
+    let analyzer = VerificationAnalyzer::new();
+    let result = analyzer.analyze_output(
+        project_path,
+        verification_output,
+        Some(exit_code),
+        verify_only_module,
+        verify_function,
+        None,
+    );
+
+    let json = serde_json::to_string_pretty(&result).expect("Failed to serialize JSON");
+    std::fs::write(json_output, &json).expect("Failed to write JSON output");
+
+    println!();
+    println!("Summary:");
+    println!("  Status: {:?}", result.status);
+    println!(
+        "  Total verifiable functions: {}",
+        result.summary.total_functions
+    );
+    println!("  Verified: {}", result.summary.verified_functions);
+    println!("  Failed: {}", result.summary.failed_functions);
+    println!(
+        "  Unverified (assume/admit): {}",
+        result.summary.unverified_functions
+    );
+
+    if !result.verification.failed_functions.is_empty() {
+        println!();
+        println!("Failed functions:");
+        for func in &result.verification.failed_functions {
+            println!(
+                "  - {} @ {}:{}",
+                func.display_name, func.code_path, func.code_text.lines_start
+            );
+        }
+    }
+
+    if !result.compilation.errors.is_empty() {
+        println!();
+        println!("Compilation errors:");
+        for err in &result.compilation.errors {
+            println!("  - {}", err.message);
+            if let Some(ref file) = err.file {
+                if let Some(line) = err.line {
+                    println!("    at {}:{}", file, line);
+                }
+            }
+        }
+    }
+
+    println!();
+    println!("JSON output written to {}", json_output.display());
+
+    result.status
+}
+
+/// Run Verus for `path` via `VerusRunner`, cache the output unless
+/// `no_cache`, then hand off to [`analyze_and_report`]. Returns `Err`
+/// instead of exiting the process when `VerusRunner` itself fails to run
+/// (as opposed to verification finding errors in the code), so a
+/// `--watch` loop can print the failure and keep watching.
+#[allow(clippy::too_many_arguments)]
+fn run_verify_once(
+    path: &Path,
+    package: Option<&str>,
+    verify_only_module: Option<&str>,
+    verify_function: Option<&str>,
+    no_cache: bool,
+    progress: bool,
+    json_output: &Path,
+) -> scip_atoms::error::ProbeResult<AnalysisStatus> {
+    println!("════════════════════════════════════════════════════════════");
+    println!("  Running Verus verification...");
+    println!("════════════════════════════════════════════════════════════");
+
+    let runner = VerusRunner::new();
+    let reporter = progress.then(|| {
+        let total = verus_parser::parse_all_functions(path, true, true, false, false, true)
+            .functions
+            .len();
+        ProgressReporter::new(total)
+    });
+    let verification_result = match &reporter {
+        Some(reporter) => runner.run_verification_with_progress(
+            path,
+            package,
+            verify_only_module,
+            verify_function,
+            None,
+            reporter,
+        ),
+        None => runner.run_verification(path, package, verify_only_module, verify_function, None),
+    };
+    if let Some(reporter) = &reporter {
+        reporter.finish();
+    }
+    let (verification_output, exit_code) =
+        match verification_result {
+            Ok((captured, code)) => {
+                let output = captured.text;
+                println!();
+                println!("════════════════════════════════════════════════════════════");
+                println!("  Verification completed with exit code: {}", code);
+                println!("════════════════════════════════════════════════════════════");
+                println!();
+                if captured.truncated {
+                    println!("(output exceeded the capture limit; middle section omitted)");
+                }
+
+                // Quick status check
+                if output.contains("verification results::") {
+                    if output.contains(", 0 errors") {
+                        println!("✓ Verification succeeded!");
+                    } else {
+                        println!("✗ Verification failed with errors");
+                    }
+                } else if code != 0 {
+                    println!("✗ Compilation or verification failed");
+                }
+
+                // Cache the output unless --no-cache is specified
+                if !no_cache {
+                    if let Err(e) = std::fs::create_dir_all(DATA_DIR) {
+                        eprintln!("Warning: Could not create data directory: {}", e);
+                    } else {
+                        // Save verification output
+                        if let Err(e) = std::fs::write(CACHE_OUTPUT_FILE, &output) {
+                            eprintln!("Warning: Could not cache verification output: {}", e);
+                        }
+                        // Save config (project path, package, exit code)
+                        let config = VerificationConfig {
+                            project_path: path.to_string_lossy().to_string(),
+                            package: package.map(|p| p.to_string()),
+                            exit_code: code,
+                        };
+                        if let Ok(config_json) = serde_json::to_string_pretty(&config) {
+                            if let Err(e) = std::fs::write(CACHE_CONFIG_FILE, config_json) {
+                                eprintln!("Warning: Could not save verification config: {}", e);
+                            } else {
+                                println!("Cached verification output to {}", CACHE_OUTPUT_FILE);
+                            }
+                        }
+                    }
+                }
+
+                (output, code)
+            }
+            Err(e) => {
+                return Err(scip_atoms::error::ProbeError::external_tool(
+                    "verus",
+                    e.to_string(),
+                ))
+            }
+        };
+
+    Ok(analyze_and_report(
+        path,
+        &verification_output,
+        exit_code,
+        verify_only_module,
+        verify_function,
+        json_output,
+    ))
+}
+
+/// Re-run [`run_verify_once`] every time `path`'s source tree changes,
+/// coalescing a burst of filesystem events (e.g. format-on-save touching
+/// several files) arriving within `WATCH_DEBOUNCE` into a single rebuild
+/// instead of one per file. The watched root is resolved once, up front,
+/// from `path` as given at startup -- not re-read from the current
+/// working directory each cycle -- so a build step that changes the
+/// process's cwd partway through doesn't move the goalposts. Runs until
+/// the process is killed; a Verus failure is printed and watching
+/// continues rather than exiting, so one bad edit doesn't end the
+/// feedback loop.
+///
+/// Requires the `notify` crate as a dependency.
+#[allow(clippy::too_many_arguments)]
+fn run_verify_watch(
+    path: &Path,
+    package: Option<&str>,
+    verify_only_module: Option<&str>,
+    verify_function: Option<&str>,
+    no_cache: bool,
+    progress: bool,
+    json_output: &Path,
+) -> scip_atoms::error::ProbeResult<()> {
+    use notify::{RecursiveMode, Watcher};
+    use scip_atoms::error::ProbeError;
+    use std::sync::mpsc::{channel, RecvTimeoutError};
+    use std::time::Duration;
+
+    const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+    let watch_root = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    println!(
+        "Watching {} for changes (Ctrl-C to stop)...",
+        watch_root.display()
+    );
+
+    if let Err(e) = run_verify_once(
+        path,
+        package,
+        verify_only_module,
+        verify_function,
+        no_cache,
+        progress,
+        json_output,
+    ) {
+        eprintln!("✗ {}", e);
+    }
+
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(tx)
+        .map_err(|e| ProbeError::external_tool("notify", e.to_string()))?;
+    watcher
+        .watch(&watch_root, RecursiveMode::Recursive)
+        .map_err(|e| {
+            ProbeError::external_tool(
+                "notify",
+                format!("failed to watch {}: {}", watch_root.display(), e),
+            )
+        })?;
+
+    loop {
+        // Block for the first event of the next batch, then drain
+        // whatever else arrives within the debounce window.
+        if rx.recv().is_err() {
+            break;
+        }
+        loop {
+            match rx.recv_timeout(WATCH_DEBOUNCE) {
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+
+        println!();
+        println!("Change detected, re-running verification...");
+        if let Err(e) = run_verify_once(
+            path,
+            package,
+            verify_only_module,
+            verify_function,
+            no_cache,
+            progress,
+            json_output,
+        ) {
+            eprintln!("✗ {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Thin CLI wrapper around [`run_cmd_verify`]: maps a returned `Err` to a
+/// process exit via [`scip_atoms::error::ProbeError::exit_code`] and a
+/// non-`Success` status to exit code 1, instead of each failure path along
+/// the way calling `std::process::exit` itself.
 #[allow(clippy::too_many_arguments)]
 fn cmd_verify(
     project_path: Option<PathBuf>,
@@ -429,105 +660,121 @@ fn cmd_verify(
     verify_function: Option<String>,
     json_output: Option<PathBuf>,
     no_cache: bool,
+    watch: bool,
+    progress: bool,
 ) {
-    // Determine the project path and verification output source
-    let (project_path, verification_output, exit_code) = if let Some(ref path) = project_path {
-        // Project path provided
-        if !path.exists() {
-            eprintln!("Error: Project path does not exist: {}", path.display());
-            std::process::exit(1);
+    match run_cmd_verify(
+        project_path,
+        from_file,
+        exit_code_arg,
+        package,
+        verify_only_module,
+        verify_function,
+        json_output,
+        no_cache,
+        watch,
+        progress,
+    ) {
+        Ok(status) => {
+            if status != AnalysisStatus::Success {
+                std::process::exit(1);
+            }
         }
+        Err(e) => {
+            eprintln!("✗ {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
 
-        let (output, code) = if let Some(ref output_file) = from_file {
-            // Use provided output file
-            if !output_file.exists() {
-                eprintln!(
-                    "Error: Output file does not exist: {}",
-                    output_file.display()
-                );
-                std::process::exit(1);
+#[allow(clippy::too_many_arguments)]
+fn run_cmd_verify(
+    project_path: Option<PathBuf>,
+    from_file: Option<PathBuf>,
+    exit_code_arg: Option<i32>,
+    package: Option<String>,
+    verify_only_module: Option<String>,
+    verify_function: Option<String>,
+    json_output: Option<PathBuf>,
+    no_cache: bool,
+    watch: bool,
+    progress: bool,
+) -> scip_atoms::error::ProbeResult<AnalysisStatus> {
+    use scip_atoms::error::ProbeError;
+
+    let output_path = json_output.unwrap_or_else(|| PathBuf::from("results.json"));
+
+    // A live run (a project path given, with no saved/cached output to
+    // analyze instead) is the only case --watch/--progress apply to: it's
+    // the only one actually invoking Verus rather than replaying output.
+    if let Some(ref path) = project_path {
+        if from_file.is_none() {
+            if !path.exists() {
+                return Err(ProbeError::ProjectValidation(format!(
+                    "Project path does not exist: {}",
+                    path.display()
+                )));
             }
 
-            let content = match std::fs::read_to_string(output_file) {
-                Ok(c) => c,
-                Err(e) => {
-                    eprintln!("Error reading output file: {}", e);
-                    std::process::exit(1);
-                }
-            };
+            if watch {
+                run_verify_watch(
+                    path,
+                    package.as_deref(),
+                    verify_only_module.as_deref(),
+                    verify_function.as_deref(),
+                    no_cache,
+                    progress,
+                    &output_path,
+                )?;
+                return Ok(AnalysisStatus::Success);
+            }
 
-            println!(
-                "Analyzing verification output from: {}",
-                output_file.display()
-            );
-            (content, exit_code_arg.unwrap_or(0))
-        } else {
-            // Run verification
-            println!("════════════════════════════════════════════════════════════");
-            println!("  Running Verus verification...");
-            println!("════════════════════════════════════════════════════════════");
-
-            let runner = VerusRunner::new();
-            match runner.run_verification(
+            return run_verify_once(
                 path,
                 package.as_deref(),
                 verify_only_module.as_deref(),
                 verify_function.as_deref(),
-                None,
-            ) {
-                Ok((output, code)) => {
-                    println!();
-                    println!("════════════════════════════════════════════════════════════");
-                    println!("  Verification completed with exit code: {}", code);
-                    println!("════════════════════════════════════════════════════════════");
-                    println!();
-
-                    // Quick status check
-                    if output.contains("verification results::") {
-                        if output.contains(", 0 errors") {
-                            println!("✓ Verification succeeded!");
-                        } else {
-                            println!("✗ Verification failed with errors");
-                        }
-                    } else if code != 0 {
-                        println!("✗ Compilation or verification failed");
-                    }
+                no_cache,
+                progress,
+                &output_path,
+            );
+        }
+    }
 
-                    // Cache the output unless --no-cache is specified
-                    if !no_cache {
-                        if let Err(e) = std::fs::create_dir_all(DATA_DIR) {
-                            eprintln!("Warning: Could not create data directory: {}", e);
-                        } else {
-                            // Save verification output
-                            if let Err(e) = std::fs::write(CACHE_OUTPUT_FILE, &output) {
-                                eprintln!("Warning: Could not cache verification output: {}", e);
-                            }
-                            // Save config (project path, package, exit code)
-                            let config = VerificationConfig {
-                                project_path: path.to_string_lossy().to_string(),
-                                package: package.clone(),
-                                exit_code: code,
-                            };
-                            if let Ok(config_json) = serde_json::to_string_pretty(&config) {
-                                if let Err(e) = std::fs::write(CACHE_CONFIG_FILE, config_json) {
-                                    eprintln!("Warning: Could not save verification config: {}", e);
-                                } else {
-                                    println!("Cached verification output to {}", CACHE_OUTPUT_FILE);
-                                }
-                            }
-                        }
-                    }
+    if watch {
+        return Err(ProbeError::ProjectValidation(
+            "--watch requires a project path (and no --from-file)".to_string(),
+        ));
+    }
 
-                    (output, code)
-                }
-                Err(e) => {
-                    eprintln!("✗ Failed to run verification: {}", e);
-                    std::process::exit(1);
-                }
-            }
-        };
+    // Determine the project path and verification output source from a
+    // saved output file or the cache.
+    let (project_path, verification_output, exit_code) = if let Some(ref path) = project_path {
+        // from_file is always Some here: the live-run case above already
+        // returned when it wasn't.
+        if !path.exists() {
+            return Err(ProbeError::ProjectValidation(format!(
+                "Project path does not exist: {}",
+                path.display()
+            )));
+        }
+
+        let output_file = from_file.as_ref().unwrap();
+        if !output_file.exists() {
+            return Err(ProbeError::ProjectValidation(format!(
+                "Output file does not exist: {}",
+                output_file.display()
+            )));
+        }
+
+        let content = std::fs::read_to_string(output_file)
+            .map_err(|e| ProbeError::file_io(output_file.clone(), e))?;
 
-        (path.clone(), output, code)
+        println!(
+            "Analyzing verification output from: {}",
+            output_file.display()
+        );
+        (path.clone(), content, exit_code_arg.unwrap_or(0))
     } else {
         // No project path - use cached output
         println!("════════════════════════════════════════════════════════════");
@@ -536,29 +783,18 @@ fn cmd_verify(
 
         // Load config
         let config: VerificationConfig = match std::fs::read_to_string(CACHE_CONFIG_FILE) {
-            Ok(content) => match serde_json::from_str(&content) {
-                Ok(c) => c,
-                Err(e) => {
-                    eprintln!("Error: Could not parse {}: {}", CACHE_CONFIG_FILE, e);
-                    eprintln!("Run with a project path first to cache verification output.");
-                    std::process::exit(1);
-                }
-            },
+            Ok(content) => serde_json::from_str(&content)?,
             Err(_) => {
-                eprintln!("Error: No cached verification found.");
-                eprintln!("Run with a project path first: scip-atoms verify <project-path>");
-                std::process::exit(1);
+                return Err(ProbeError::ProjectValidation(
+                    "No cached verification found. Run with a project path first: scip-atoms verify <project-path>"
+                        .to_string(),
+                ));
             }
         };
 
         // Load cached output
-        let output = match std::fs::read_to_string(CACHE_OUTPUT_FILE) {
-            Ok(c) => c,
-            Err(e) => {
-                eprintln!("Error: Could not read cached output: {}", e);
-                std::process::exit(1);
-            }
-        };
+        let output = std::fs::read_to_string(CACHE_OUTPUT_FILE)
+            .map_err(|e| ProbeError::file_io(CACHE_OUTPUT_FILE, e))?;
 
         let path = PathBuf::from(&config.project_path);
         if !path.exists() {
@@ -579,79 +815,198 @@ fn cmd_verify(
         (path, output, config.exit_code)
     };
 
-    // Analyze the output
-    let analyzer = VerificationAnalyzer::new();
-    let result = analyzer.analyze_output(
+    let status = analyze_and_report(
         &project_path,
         &verification_output,
-        Some(exit_code),
+        exit_code,
         verify_only_module.as_deref(),
         verify_function.as_deref(),
+        &output_path,
     );
 
-    // Always write to JSON file (default: results.json)
-    let output_path = json_output.unwrap_or_else(|| PathBuf::from("results.json"));
-    let json = serde_json::to_string_pretty(&result).expect("Failed to serialize JSON");
-    std::fs::write(&output_path, &json).expect("Failed to write JSON output");
+    Ok(status)
+}
+
+/// Run verification, then check every function's actual
+/// [`FunctionCategory`] against its `//~ VERIFY-FAIL` / `//~ ASSUME`
+/// annotation (see [`scip_atoms::expect`]) -- compiletest/ui_test style
+/// regression testing for intended proof outcomes. Only files that already
+/// carry at least one `//~` annotation are checked or blessed; a file with
+/// none has nothing to reconcile.
+fn cmd_check_expectations(project_path: PathBuf, package: Option<String>, bless: bool) {
+    match run_cmd_check_expectations(project_path, package, bless) {
+        Ok(has_mismatches) => {
+            if has_mismatches {
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("✗ {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+/// Core logic behind [`cmd_check_expectations`]; returns whether any
+/// expectation mismatch was found (always `false` when `bless` is set,
+/// since blessing reconciles rather than reports).
+fn run_cmd_check_expectations(
+    project_path: PathBuf,
+    package: Option<String>,
+    bless: bool,
+) -> scip_atoms::error::ProbeResult<bool> {
+    use scip_atoms::error::ProbeError;
 
-    // Print summary
+    if !project_path.exists() {
+        return Err(ProbeError::ProjectValidation(format!(
+            "Project path does not exist: {}",
+            project_path.display()
+        )));
+    }
+
+    println!("═══════════════════════════════════════════════════════════");
+    println!("  Checking verification expectations...");
+    println!("═══════════════════════════════════════════════════════════");
     println!();
-    println!("Summary:");
-    println!("  Status: {:?}", result.status);
-    println!(
-        "  Total verifiable functions: {}",
-        result.summary.total_functions
-    );
-    println!("  Verified: {}", result.summary.verified_functions);
-    println!("  Failed: {}", result.summary.failed_functions);
-    println!(
-        "  Unverified (assume/admit): {}",
-        result.summary.unverified_functions
+
+    let runner = VerusRunner::new();
+    let (captured, exit_code) = runner
+        .run_verification(&project_path, package.as_deref(), None, None, None)
+        .map_err(|e| ProbeError::external_tool("verus", e.to_string()))?;
+
+    let analyzer = VerificationAnalyzer::new();
+    let result = analyzer.analyze_output(
+        &project_path,
+        &captured.text,
+        Some(exit_code),
+        None,
+        None,
+        None,
     );
 
-    if !result.verification.failed_functions.is_empty() {
-        println!();
-        println!("Failed functions:");
-        for func in &result.verification.failed_functions {
-            println!(
-                "  - {} @ {}:{}",
-                func.display_name, func.code_path, func.code_text.lines_start
-            );
-        }
+    let mut category_by_location: std::collections::HashMap<(String, usize), FunctionCategory> =
+        std::collections::HashMap::new();
+    for loc in &result.verification.verified_functions {
+        category_by_location.insert(
+            (loc.code_path.clone(), loc.code_text.lines_start),
+            FunctionCategory::Verified,
+        );
+    }
+    for loc in &result.verification.failed_functions {
+        category_by_location.insert(
+            (loc.code_path.clone(), loc.code_text.lines_start),
+            FunctionCategory::Failed,
+        );
+    }
+    for loc in &result.verification.unverified_functions {
+        category_by_location.insert(
+            (loc.code_path.clone(), loc.code_text.lines_start),
+            FunctionCategory::Unverified,
+        );
     }
 
-    if !result.compilation.errors.is_empty() {
-        println!();
-        println!("Compilation errors:");
-        for err in &result.compilation.errors {
-            println!("  - {}", err.message);
-            if let Some(ref file) = err.file {
-                if let Some(line) = err.line {
-                    println!("    at {}:{}", file, line);
-                }
+    let parsed = verus_parser::parse_all_functions(&project_path, true, true, false, false, true);
+    let mut functions_by_file: std::collections::HashMap<String, Vec<verus_parser::FunctionInfo>> =
+        std::collections::HashMap::new();
+    for func in parsed.functions {
+        functions_by_file
+            .entry(func.file.clone().unwrap_or_default())
+            .or_default()
+            .push(func);
+    }
+
+    let mut all_mismatches: Vec<(String, FunctionExpectationMismatch)> = Vec::new();
+    let mut files_checked = 0usize;
+
+    for (file, functions) in &functions_by_file {
+        let full_path = project_path.join(file);
+        let Ok(source) = std::fs::read_to_string(&full_path) else {
+            continue;
+        };
+        // Nothing to reconcile (or bless) in a file that names no expectations.
+        if !source.contains("//~") {
+            continue;
+        }
+        files_checked += 1;
+
+        let actual = |f: &verus_parser::FunctionInfo| {
+            category_by_location
+                .get(&(file.clone(), f.start_line))
+                .copied()
+                .unwrap_or(FunctionCategory::Verified)
+        };
+
+        if bless {
+            if let Err(e) =
+                expect::bless_function_expectations(&full_path, &source, functions, actual)
+            {
+                eprintln!("Warning: failed to bless {}: {}", full_path.display(), e);
             }
+            continue;
         }
+
+        all_mismatches.extend(
+            expect::check_function_expectations(&source, functions, actual)
+                .into_iter()
+                .map(|mismatch| (file.clone(), mismatch)),
+        );
     }
 
-    println!();
-    println!("JSON output written to {}", output_path.display());
+    if bless {
+        println!("✓ Blessed expectations in {} file(s)", files_checked);
+        return Ok(false);
+    }
 
-    // Exit with appropriate code
-    if result.status != AnalysisStatus::Success {
-        std::process::exit(1);
+    if all_mismatches.is_empty() {
+        println!(
+            "✓ All {} annotated file(s) matched their expectations",
+            files_checked
+        );
+        return Ok(false);
     }
+
+    println!("✗ {} expectation mismatch(es):", all_mismatches.len());
+    for (file, mismatch) in &all_mismatches {
+        println!(
+            "  - {} @ {}:{} - expected {:?}, got {:?}",
+            mismatch.function.display_name,
+            file,
+            mismatch.function.code_text.lines_start,
+            mismatch.expected,
+            mismatch.actual
+        );
+    }
+    Ok(true)
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let config = config_file::load(&cwd);
+    let argv = config_file::resolve_args(std::env::args().collect(), &config);
+    let cli = Cli::parse_from(argv.clone());
+
+    if cli.print_config {
+        println!("{}", config_file::describe_resolved_invocation(&argv));
+        return;
+    }
 
     match cli.command {
         Commands::Atoms {
             project_path,
             output,
             regenerate_scip,
+            show_edge_diagnostics,
+            disambiguate_by_line,
+            output_format,
         } => {
-            cmd_atoms(project_path, output, regenerate_scip);
+            cmd_atoms(
+                project_path,
+                output,
+                regenerate_scip,
+                show_edge_diagnostics,
+                disambiguate_by_line,
+                output_format,
+            );
         }
         Commands::Functions {
             path,
@@ -681,6 +1036,8 @@ fn main() {
             verify_function,
             json_output,
             no_cache,
+            watch,
+            progress,
         } => {
             cmd_verify(
                 project_path,
@@ -691,7 +1048,16 @@ fn main() {
                 verify_function,
                 json_output,
                 no_cache,
+                watch,
+                progress,
             );
         }
+        Commands::CheckExpectations {
+            project_path,
+            package,
+            bless,
+        } => {
+            cmd_check_expectations(project_path, package, bless);
+        }
     }
 }
@@ -0,0 +1,139 @@
+//! File-scope narrowing matcher, modeled on Mercurial's narrowspec.
+//!
+//! A large workspace may only want verification failures reported for the
+//! subtree someone is actually working on. A [`Matcher`] compiles a set of
+//! client-supplied patterns into an include matcher, plus an optional
+//! difference matcher for excludes. Each pattern must carry one of two safe
+//! prefixes -- `path:DIR` (everything under `DIR`, recursively) or
+//! `rootfilesin:DIR` (files directly inside `DIR`, non-recursive) -- so there's
+//! no ambiguity about what a pattern means the way a bare glob would have.
+
+use std::path::Path;
+
+/// A single narrowspec pattern, already split from its prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pattern {
+    /// `path:DIR` -- matches `DIR` and everything below it.
+    Path(String),
+    /// `rootfilesin:DIR` -- matches only files directly inside `DIR`.
+    RootFilesIn(String),
+}
+
+impl Pattern {
+    fn parse(spec: &str) -> Result<Self, String> {
+        if let Some(dir) = spec.strip_prefix("path:") {
+            Ok(Pattern::Path(normalize(dir)))
+        } else if let Some(dir) = spec.strip_prefix("rootfilesin:") {
+            Ok(Pattern::RootFilesIn(normalize(dir)))
+        } else {
+            Err(format!(
+                "unsupported narrowspec pattern `{}`: must start with `path:` or `rootfilesin:`",
+                spec
+            ))
+        }
+    }
+
+    fn matches(&self, file: &Path) -> bool {
+        match self {
+            Pattern::Path(dir) => dir.is_empty() || file.starts_with(dir),
+            Pattern::RootFilesIn(dir) => {
+                let parent = file.parent().unwrap_or_else(|| Path::new(""));
+                parent == Path::new(dir)
+            }
+        }
+    }
+}
+
+fn normalize(dir: &str) -> String {
+    dir.trim_matches('/').to_string()
+}
+
+/// A compiled set of include/exclude narrowspec patterns.
+///
+/// An empty include set matches every file: narrowing is opt-in.
+pub struct Matcher {
+    includes: Vec<Pattern>,
+    excludes: Vec<Pattern>,
+}
+
+impl Matcher {
+    /// A matcher with no patterns at all, which matches every file.
+    pub fn all() -> Self {
+        Self {
+            includes: Vec::new(),
+            excludes: Vec::new(),
+        }
+    }
+
+    /// Compile `include_specs` (each `path:...`/`rootfilesin:...`) into an
+    /// include matcher, with no excludes.
+    pub fn from_patterns(include_specs: &[String]) -> Result<Self, String> {
+        let includes = include_specs
+            .iter()
+            .map(|s| Pattern::parse(s))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            includes,
+            excludes: Vec::new(),
+        })
+    }
+
+    /// Compile `exclude_specs` into a difference matcher: files matched by an
+    /// exclude pattern are removed from what the include patterns matched.
+    pub fn with_excludes(mut self, exclude_specs: &[String]) -> Result<Self, String> {
+        self.excludes = exclude_specs
+            .iter()
+            .map(|s| Pattern::parse(s))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(self)
+    }
+
+    /// Whether `file` is in scope: matched by some include pattern (or there
+    /// are none, meaning "everything"), and not matched by any exclude pattern.
+    pub fn matches(&self, file: &str) -> bool {
+        let file = Path::new(file);
+        let included = self.includes.is_empty() || self.includes.iter().any(|p| p.matches(file));
+        let excluded = self.excludes.iter().any(|p| p.matches(file));
+        included && !excluded
+    }
+}
+
+impl Default for Matcher {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_pattern_matches_recursively() {
+        let matcher = Matcher::from_patterns(&["path:src/lemmas".to_string()]).unwrap();
+        assert!(matcher.matches("src/lemmas/field_lemmas/constants.rs"));
+        assert!(!matcher.matches("src/other/file.rs"));
+    }
+
+    #[test]
+    fn rootfilesin_pattern_is_non_recursive() {
+        let matcher = Matcher::from_patterns(&["rootfilesin:src".to_string()]).unwrap();
+        assert!(matcher.matches("src/lib.rs"));
+        assert!(!matcher.matches("src/lemmas/constants.rs"));
+    }
+
+    #[test]
+    fn excludes_narrow_an_include_match() {
+        let matcher = Matcher::from_patterns(&["path:src".to_string()])
+            .unwrap()
+            .with_excludes(&["path:src/generated".to_string()])
+            .unwrap();
+        assert!(matcher.matches("src/lib.rs"));
+        assert!(!matcher.matches("src/generated/bindings.rs"));
+    }
+
+    #[test]
+    fn rejects_unprefixed_patterns() {
+        assert!(Matcher::from_patterns(&["src/lib.rs".to_string()]).is_err());
+    }
+}
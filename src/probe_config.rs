@@ -0,0 +1,157 @@
+//! Per-project overrides for the matching tolerances and symbol-kind
+//! tables that are otherwise hardcoded `const`s, following the pattern
+//! rust-analyzer's own `config.rs` uses: a serde-deserialized config
+//! struct, optional on disk, whose fields fall back to the tool's built-in
+//! defaults when absent.
+//!
+//! Different indexer versions (verus-analyzer vs. upstream rust-analyzer)
+//! emit different SCIP `kind` numbers and symbol prefixes, so a project
+//! that's indexed with an unusual toolchain can drop a `probe_config.json`
+//! in its root rather than requiring a recompile.
+//!
+//! Like [`crate::diagnostics::Diagnostics`], the resolved config is read
+//! once and cached behind a [`OnceLock`] rather than threaded as a
+//! parameter through every call site -- [`set_global`] lets a command
+//! (`cmd_atomize`, `atomize_internal`) install the project's config before
+//! `build_call_graph`/`convert_to_atoms_with_parsed_spans` run.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Filename searched for in the project root.
+pub const PROBE_CONFIG_FILE_NAME: &str = "probe_config.json";
+
+/// SCIP kinds this tool treats as function-like by default: Method,
+/// Function, Constructor, and the Macro kind verus-analyzer uses for some
+/// function-like macros.
+const DEFAULT_FUNCTION_LIKE_KINDS: &[i32] = &[6, 17, 26, 80];
+
+/// Default symbol prefix emitted by rust-analyzer/verus-analyzer.
+const DEFAULT_SYMBOL_PREFIX: &str = "rust-analyzer cargo ";
+
+/// Default lookback window (in source lines) for collecting nearby type
+/// references to disambiguate trait impls.
+const DEFAULT_TYPE_CONTEXT_LOOKBACK_LINES: i32 = 5;
+
+/// Matching tolerances and symbol-kind tables for one project, overriding
+/// the tool's built-in defaults. Every field is optional on disk; an
+/// absent field falls back to the corresponding default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProbeConfig {
+    /// Line-number slack allowed when matching a SCIP definition's range
+    /// against a source span parsed independently (e.g. by `verus_syn`).
+    /// `0` requires an exact match.
+    pub line_tolerance: i32,
+    /// How many lines to look back from a definition for nearby type
+    /// references, when disambiguating trait impls.
+    pub type_context_lookback_lines: i32,
+    /// SCIP `kind` values treated as function-like.
+    pub function_like_kinds: Vec<i32>,
+    /// Expected prefix on symbols emitted by the indexer.
+    pub symbol_prefix: String,
+}
+
+impl Default for ProbeConfig {
+    fn default() -> Self {
+        ProbeConfig {
+            line_tolerance: 0,
+            type_context_lookback_lines: DEFAULT_TYPE_CONTEXT_LOOKBACK_LINES,
+            function_like_kinds: DEFAULT_FUNCTION_LIKE_KINDS.to_vec(),
+            symbol_prefix: DEFAULT_SYMBOL_PREFIX.to_string(),
+        }
+    }
+}
+
+impl ProbeConfig {
+    /// Load `probe_config.json` from `project_root`, falling back to
+    /// [`ProbeConfig::default`] when the file is absent or fails to parse.
+    pub fn load_from_project(project_root: &Path) -> Self {
+        let path = project_root.join(PROBE_CONFIG_FILE_NAME);
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Whether `kind` is one of [`ProbeConfig::function_like_kinds`].
+    pub fn is_function_like(&self, kind: i32) -> bool {
+        self.function_like_kinds.contains(&kind)
+    }
+
+    /// Whether `symbol_roles` marks a definition occurrence. Not currently
+    /// configurable -- every indexer observed so far uses bit `1` for this
+    /// -- but kept as a method alongside [`ProbeConfig::is_function_like`]
+    /// so callers consult the config uniformly rather than some helpers
+    /// being config-aware and others not.
+    pub fn is_definition(&self, symbol_roles: Option<i32>) -> bool {
+        symbol_roles.unwrap_or(0) & 1 == 1
+    }
+
+    /// Install `self` as the process-wide config consulted by
+    /// [`global`]. Only the first call takes effect, matching
+    /// [`crate::diagnostics::Diagnostics::global`]'s read-once semantics --
+    /// call this before `build_call_graph`/`convert_to_atoms_with_parsed_spans`
+    /// run, not after.
+    pub fn set_global(self) {
+        let _ = GLOBAL.set(self);
+    }
+
+    /// The process-wide config, defaulting to [`ProbeConfig::default`] if
+    /// [`set_global`] was never called.
+    pub fn global() -> &'static ProbeConfig {
+        GLOBAL.get_or_init(ProbeConfig::default)
+    }
+}
+
+static GLOBAL: OnceLock<ProbeConfig> = OnceLock::new();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_matches_the_built_in_constants() {
+        let config = ProbeConfig::default();
+        assert_eq!(config.function_like_kinds, vec![6, 17, 26, 80]);
+        assert_eq!(config.symbol_prefix, "rust-analyzer cargo ");
+        assert_eq!(config.type_context_lookback_lines, 5);
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let config: ProbeConfig = serde_json::from_str(r#"{"line_tolerance": 3}"#).unwrap();
+        assert_eq!(config.line_tolerance, 3);
+        assert_eq!(config.function_like_kinds, vec![6, 17, 26, 80]);
+    }
+
+    #[test]
+    fn load_from_project_falls_back_when_the_file_is_absent() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ProbeConfig::load_from_project(dir.path());
+        assert_eq!(config, ProbeConfig::default());
+    }
+
+    #[test]
+    fn load_from_project_reads_an_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(PROBE_CONFIG_FILE_NAME),
+            r#"{"symbol_prefix": "custom "}"#,
+        )
+        .unwrap();
+        let config = ProbeConfig::load_from_project(dir.path());
+        assert_eq!(config.symbol_prefix, "custom ");
+    }
+
+    #[test]
+    fn is_function_like_consults_the_configured_kinds() {
+        let config = ProbeConfig {
+            function_like_kinds: vec![99],
+            ..ProbeConfig::default()
+        };
+        assert!(config.is_function_like(99));
+        assert!(!config.is_function_like(17));
+    }
+}
@@ -0,0 +1,347 @@
+//! Reachability analysis over the call graph.
+//!
+//! Given root symbols (entry points, test functions, or a user-supplied
+//! allowlist), [`reachable_from`] finds every function transitively
+//! reachable by BFS over [`FunctionNode::callees`] and reports the rest as
+//! dead code -- the same job a linker does when it strips unreachable
+//! imports. [`filter_live_atoms`] lets callers prune `AtomWithLines` output
+//! down to only the atoms actually exercised from the chosen roots.
+//!
+//! [`find_call_cycles`] answers a related but different question over the
+//! same graph: not "what's unreachable" but "what calls itself, directly
+//! or through a cycle of mutual calls" -- the functions a naive inliner or
+//! unroller could blow up on.
+
+use crate::{AtomWithLines, CalleeInfo, FullyQualifiedSymbol, FunctionNode};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The result of [`reachable_from`]: every function transitively reachable
+/// from the roots, and every function that wasn't.
+#[derive(Debug, Clone)]
+pub struct Reachable {
+    pub live: HashSet<FullyQualifiedSymbol>,
+    pub dead: Vec<FunctionNode>,
+    /// `(code_path, lines_start)` of every live function, for matching
+    /// against `AtomWithLines` output in [`filter_live_atoms`].
+    live_locations: HashSet<(String, usize)>,
+}
+
+/// Resolve a callee to its candidate target key(s) among the project
+/// definitions sharing its raw symbol, using the callee's turbofish type
+/// hints to disambiguate when more than one definition matches -- the same
+/// approach `convert_to_atoms_with_lines_internal` uses to resolve
+/// dependencies for `AtomWithLines` output.
+pub(crate) fn resolve_callee<'a>(
+    callee: &CalleeInfo,
+    candidates: &[(&'a FullyQualifiedSymbol, &Vec<String>)],
+) -> Vec<&'a FullyQualifiedSymbol> {
+    if candidates.len() == 1 {
+        return vec![candidates[0].0];
+    }
+    if callee.type_hints.is_empty() {
+        return candidates.iter().map(|(key, _)| *key).collect();
+    }
+
+    // Prefer a hint's first generic argument as the discriminator (the same
+    // priority `convert_to_atoms_with_lines_internal` gives it), falling
+    // back to the flattened hint names. Both sides are normalized before
+    // comparing -- a raw-string substring test over-matches, e.g.
+    // `NielsPoint` against `ProjectiveNielsPoint`.
+    let normalized_contexts: Vec<HashSet<String>> = candidates
+        .iter()
+        .map(|(_, ctx)| ctx.iter().map(|t| crate::normalized_type_name(t)).collect())
+        .collect();
+    let first_arg_hints: HashSet<String> = callee
+        .type_hints
+        .iter()
+        .filter_map(|hint| hint.args.first())
+        .map(|arg| crate::normalized_type_name(&arg.name))
+        .collect();
+    let flattened_hints: HashSet<String> = callee
+        .type_hints
+        .iter()
+        .flat_map(|h| h.flatten())
+        .map(crate::normalized_type_name)
+        .collect();
+
+    let discriminate = |hints: &HashSet<String>| -> HashSet<String> {
+        hints
+            .iter()
+            .filter(|hint| {
+                let matching_count = normalized_contexts
+                    .iter()
+                    .filter(|ctx| ctx.contains(*hint))
+                    .count();
+                matching_count > 0 && matching_count < candidates.len()
+            })
+            .cloned()
+            .collect()
+    };
+
+    let from_first_args = discriminate(&first_arg_hints);
+    let discriminating_hints = if !from_first_args.is_empty() {
+        from_first_args
+    } else {
+        discriminate(&flattened_hints)
+    };
+    let hints_to_match = if !discriminating_hints.is_empty() {
+        &discriminating_hints
+    } else {
+        &flattened_hints
+    };
+
+    let matched: Vec<&FullyQualifiedSymbol> = candidates
+        .iter()
+        .zip(normalized_contexts.iter())
+        .filter(|(_, ctx)| hints_to_match.iter().all(|hint| ctx.contains(hint)))
+        .map(|((key, _), _)| *key)
+        .collect();
+
+    if matched.len() == 1 {
+        matched
+    } else {
+        candidates.iter().map(|(key, _)| *key).collect()
+    }
+}
+
+/// Build a forward adjacency list: for every function in the call graph,
+/// every key its callees resolve to. A `CalleeInfo` only carries the raw
+/// SCIP symbol (references don't carry the disambiguated key), so
+/// definitions are first grouped by raw symbol to resolve against.
+fn build_adjacency(
+    call_graph: &HashMap<FullyQualifiedSymbol, FunctionNode>,
+) -> HashMap<FullyQualifiedSymbol, Vec<FullyQualifiedSymbol>> {
+    let mut by_symbol: HashMap<&str, Vec<(&FullyQualifiedSymbol, &Vec<String>)>> = HashMap::new();
+    for (key, node) in call_graph {
+        by_symbol
+            .entry(node.symbol.as_str())
+            .or_default()
+            .push((key, &node.definition_type_context));
+    }
+
+    call_graph
+        .iter()
+        .map(|(key, node)| {
+            let mut targets = Vec::new();
+            for callee in &node.callees {
+                if let Some(candidates) = by_symbol.get(callee.symbol.as_str()) {
+                    targets.extend(resolve_callee(callee, candidates).into_iter().cloned());
+                }
+            }
+            (key.clone(), targets)
+        })
+        .collect()
+}
+
+/// Find every function reachable from `roots` by BFS over the call graph,
+/// and report the rest as dead code. `roots` is matched against each
+/// function's raw SCIP symbol, so callers can name entry points without
+/// knowing their disambiguated key.
+pub fn reachable_from(
+    call_graph: &HashMap<FullyQualifiedSymbol, FunctionNode>,
+    roots: &HashSet<String>,
+) -> Reachable {
+    let adjacency = build_adjacency(call_graph);
+
+    let mut live: HashSet<FullyQualifiedSymbol> = HashSet::new();
+    let mut queue: VecDeque<FullyQualifiedSymbol> = VecDeque::new();
+
+    for (key, node) in call_graph {
+        if roots.contains(&node.symbol) && live.insert(key.clone()) {
+            queue.push_back(key.clone());
+        }
+    }
+
+    while let Some(key) = queue.pop_front() {
+        if let Some(targets) = adjacency.get(&key) {
+            for target in targets {
+                if live.insert(target.clone()) {
+                    queue.push_back(target.clone());
+                }
+            }
+        }
+    }
+
+    let mut live_locations = HashSet::new();
+    let mut dead = Vec::new();
+    for (key, node) in call_graph {
+        if live.contains(key) {
+            if !node.range.is_empty() {
+                live_locations.insert((node.relative_path.clone(), node.range[0] as usize + 1));
+            }
+        } else {
+            dead.push(node.clone());
+        }
+    }
+
+    Reachable {
+        live,
+        dead,
+        live_locations,
+    }
+}
+
+/// Every direct or mutual recursion in `call_graph`: functions grouped
+/// into a strongly-connected component of size 2 or more, plus any single
+/// function with a self-edge. Each component is reported as the raw SCIP
+/// symbol (`FunctionNode::symbol`) of its members, same identity
+/// `reachable_from`'s `roots` are matched against.
+///
+/// Runs Tarjan's algorithm over the same resolved adjacency
+/// [`reachable_from`] builds -- so a call site naming a bare trait-method
+/// symbol (e.g. `ristretto/Mul#mul()`) is over-approximated to every impl
+/// sharing that symbol, the same as reachability's dead-code analysis.
+/// That over-approximation only widens cycles it reports, never hides a
+/// real one.
+pub fn find_call_cycles(call_graph: &HashMap<FullyQualifiedSymbol, FunctionNode>) -> Vec<Vec<String>> {
+    let adjacency = build_adjacency(call_graph);
+
+    let keys: Vec<&FullyQualifiedSymbol> = call_graph.keys().collect();
+    let index_of: HashMap<&FullyQualifiedSymbol, usize> =
+        keys.iter().enumerate().map(|(i, k)| (*k, i)).collect();
+    let edges: Vec<Vec<usize>> = keys
+        .iter()
+        .map(|key| {
+            adjacency
+                .get(*key)
+                .map(|targets| targets.iter().filter_map(|t| index_of.get(t).copied()).collect())
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut tarjan = CallGraphTarjan::new(keys.len());
+    for idx in 0..keys.len() {
+        if tarjan.index[idx].is_none() {
+            tarjan.strong_connect(idx, &edges);
+        }
+    }
+
+    tarjan
+        .components
+        .into_iter()
+        .filter(|component| component.len() > 1 || edges[component[0]].contains(&component[0]))
+        .map(|component| {
+            component
+                .into_iter()
+                .map(|idx| call_graph[keys[idx]].symbol.clone())
+                .collect()
+        })
+        .collect()
+}
+
+/// Mutable working state for Tarjan's strongly-connected-components
+/// algorithm over the call graph's resolved adjacency, iterative (explicit
+/// stack) to avoid recursion depth limits on a deep call chain.
+struct CallGraphTarjan {
+    index: Vec<Option<usize>>,
+    lowlink: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next_index: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl CallGraphTarjan {
+    fn new(node_count: usize) -> Self {
+        CallGraphTarjan {
+            index: vec![None; node_count],
+            lowlink: vec![0; node_count],
+            on_stack: vec![false; node_count],
+            stack: Vec::new(),
+            next_index: 0,
+            components: Vec::new(),
+        }
+    }
+
+    fn strong_connect(&mut self, start: usize, edges: &[Vec<usize>]) {
+        // Each explicit-stack frame tracks which outgoing edge to resume
+        // from, since an iterative DFS can't rely on the call stack to
+        // remember it.
+        let mut call_stack: Vec<(usize, usize)> = vec![(start, 0)];
+        self.visit(start);
+
+        while let Some(&(node, edge_pos)) = call_stack.last() {
+            if edge_pos < edges[node].len() {
+                let next = edges[node][edge_pos];
+                call_stack.last_mut().unwrap().1 += 1;
+                if self.index[next].is_none() {
+                    self.visit(next);
+                    call_stack.push((next, 0));
+                } else if self.on_stack[next] {
+                    self.lowlink[node] = self.lowlink[node].min(self.index[next].unwrap());
+                }
+                continue;
+            }
+
+            call_stack.pop();
+            if let Some(&(parent, _)) = call_stack.last() {
+                self.lowlink[parent] = self.lowlink[parent].min(self.lowlink[node]);
+            }
+
+            if self.lowlink[node] == self.index[node].unwrap() {
+                let mut component = Vec::new();
+                loop {
+                    let member = self.stack.pop().unwrap();
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    fn visit(&mut self, node: usize) {
+        self.index[node] = Some(self.next_index);
+        self.lowlink[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+    }
+}
+
+/// Keep only the atoms whose location matches a live function, pruning
+/// verification/atom extraction down to the code actually exercised from
+/// the chosen roots -- the way a linker eliminates unreachable imports.
+pub fn filter_live_atoms(atoms: Vec<AtomWithLines>, reachable: &Reachable) -> Vec<AtomWithLines> {
+    atoms
+        .into_iter()
+        .filter(|atom| {
+            reachable
+                .live_locations
+                .contains(&(atom.code_path.clone(), atom.code_text.lines_start))
+        })
+        .collect()
+}
+
+/// [`reachable_from`] plus [`filter_live_atoms`] in one call: every atom
+/// transitively reachable from `roots`.
+pub fn reachable_atoms(
+    atoms: Vec<AtomWithLines>,
+    call_graph: &HashMap<FullyQualifiedSymbol, FunctionNode>,
+    roots: &HashSet<String>,
+) -> Vec<AtomWithLines> {
+    let reachable = reachable_from(call_graph, roots);
+    filter_live_atoms(atoms, &reachable)
+}
+
+/// The complement of [`reachable_atoms`]: every atom whose location does
+/// *not* match a function reachable from `roots`, for callers that want to
+/// report or prune dead code directly rather than work from [`Reachable`].
+pub fn find_dead_code(
+    atoms: Vec<AtomWithLines>,
+    call_graph: &HashMap<FullyQualifiedSymbol, FunctionNode>,
+    roots: &HashSet<String>,
+) -> Vec<AtomWithLines> {
+    let reachable = reachable_from(call_graph, roots);
+    atoms
+        .into_iter()
+        .filter(|atom| {
+            !reachable
+                .live_locations
+                .contains(&(atom.code_path.clone(), atom.code_text.lines_start))
+        })
+        .collect()
+}
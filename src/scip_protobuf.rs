@@ -0,0 +1,129 @@
+//! Native SCIP protobuf index parsing, as an alternative to the
+//! `index.scip` -> `index.scip.json` round-trip [`crate::parse_scip_json`]
+//! reads.
+//!
+//! Behind the `scip-protobuf` feature, [`parse_scip_protobuf`] decodes
+//! `index.scip` directly with the `scip`/`prost`-generated `Index` message
+//! and translates it into the same [`crate::ScipIndex`] that
+//! [`crate::build_call_graph`] consumes, so callers can't tell which
+//! decoder produced it. Without the feature, it's an unconditional error
+//! rather than a compile failure, so call sites don't need to `cfg`-gate
+//! themselves -- the same shape as [`crate::atom_cache`]'s `rkyv-impl`
+//! path being a no-op cache miss when the feature is off.
+
+use crate::{Document, Metadata, Occurrence, ScipIndex, SignatureDocumentation, Symbol, ToolInfo};
+use std::path::Path;
+
+/// Parse a native `index.scip` protobuf file into the same [`ScipIndex`]
+/// shape [`crate::parse_scip_json`] produces from `index.scip.json`.
+///
+/// Requires the `scip-protobuf` feature. Without it, always returns an
+/// error so a caller can fall back to the JSON path uniformly, the same
+/// way it already falls back when `index.scip` is simply absent.
+#[cfg(feature = "scip-protobuf")]
+pub fn parse_scip_protobuf(path: &Path) -> Result<ScipIndex, Box<dyn std::error::Error>> {
+    decode::from_file(path)
+}
+
+#[cfg(not(feature = "scip-protobuf"))]
+pub fn parse_scip_protobuf(_path: &Path) -> Result<ScipIndex, Box<dyn std::error::Error>> {
+    Err("scip-protobuf feature not enabled; rebuild with --features scip-protobuf \
+         or regenerate index.scip.json"
+        .into())
+}
+
+#[cfg(feature = "scip-protobuf")]
+mod decode {
+    use super::*;
+    use prost::Message;
+
+    pub(super) fn from_file(path: &Path) -> Result<ScipIndex, Box<dyn std::error::Error>> {
+        let bytes = std::fs::read(path)?;
+        let index = scip::types::Index::decode(bytes.as_slice())?;
+        Ok(translate(index))
+    }
+
+    /// Translate the `prost`-generated wire types into our own `ScipIndex`,
+    /// so everything downstream of parsing (`build_call_graph`,
+    /// `convert_to_atoms_with_lines`, ...) stays decoder-agnostic.
+    fn translate(index: scip::types::Index) -> ScipIndex {
+        let metadata = index.metadata.unwrap_or_default();
+        let tool_info = metadata.tool_info.unwrap_or_default();
+
+        ScipIndex {
+            metadata: Metadata {
+                tool_info: ToolInfo {
+                    name: tool_info.name,
+                    version: tool_info.version,
+                },
+                project_root: metadata.project_root,
+                text_document_encoding: metadata.text_document_encoding as i32,
+            },
+            documents: index.documents.into_iter().map(translate_document).collect(),
+        }
+    }
+
+    fn translate_document(doc: scip::types::Document) -> Document {
+        Document {
+            language: doc.language,
+            relative_path: doc.relative_path,
+            occurrences: doc.occurrences.into_iter().map(translate_occurrence).collect(),
+            symbols: doc.symbols.into_iter().map(translate_symbol).collect(),
+            position_encoding: doc.position_encoding as i32,
+        }
+    }
+
+    fn translate_occurrence(occ: scip::types::Occurrence) -> Occurrence {
+        Occurrence {
+            range: occ.range,
+            symbol: occ.symbol,
+            symbol_roles: if occ.symbol_roles != 0 {
+                Some(occ.symbol_roles)
+            } else {
+                None
+            },
+        }
+    }
+
+    fn translate_symbol(sym: scip::types::SymbolInformation) -> Symbol {
+        let signature_documentation = sym.signature_documentation.unwrap_or_default();
+        Symbol {
+            symbol: sym.symbol,
+            kind: sym.kind as i32,
+            display_name: if sym.display_name.is_empty() {
+                None
+            } else {
+                Some(sym.display_name)
+            },
+            documentation: if sym.documentation.is_empty() {
+                None
+            } else {
+                Some(sym.documentation)
+            },
+            signature_documentation: SignatureDocumentation {
+                language: signature_documentation.language,
+                text: signature_documentation.text,
+                position_encoding: signature_documentation.position_encoding as i32,
+            },
+            enclosing_symbol: if sym.enclosing_symbol.is_empty() {
+                None
+            } else {
+                Some(sym.enclosing_symbol)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn without_the_feature_parsing_fails_rather_than_panicking() {
+        #[cfg(not(feature = "scip-protobuf"))]
+        {
+            let result = parse_scip_protobuf(Path::new("index.scip"));
+            assert!(result.is_err());
+        }
+    }
+}
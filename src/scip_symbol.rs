@@ -0,0 +1,391 @@
+//! Structured parser for SCIP symbol strings.
+//!
+//! A SCIP symbol follows the grammar `<scheme> <manager> <package> <version>
+//! <descriptor>+`, where each descriptor is suffix-tagged to say what kind of
+//! thing it names: `name/` namespace, `name#` type, `name.` term,
+//! `name(disambiguator).` method, `[name]` type parameter, `(name)`
+//! parameter, `name:` meta. Parsing this once gives exact type-reference
+//! detection and type-name extraction, instead of the heuristics this
+//! crate used to lean on (`ends_with('#')`, `trim_end_matches('#')` +
+//! `rfind('/')`).
+//!
+//! [`ParsedSymbol`] also renders back to SCIP syntax via `Display`/
+//! `to_string`, so the disambiguation code in `lib.rs` that used to splice
+//! a Self type or a `<T>` type parameter into the raw string directly now
+//! inserts or edits a [`Descriptor`] and re-renders it -- which works the
+//! same way regardless of which indexer produced the original symbol.
+//!
+//! See <https://github.com/sourcegraph/scip/blob/main/scip.proto> for the
+//! full grammar, including the space- and backtick-escaping rules for
+//! package names and descriptor names respectively.
+
+/// The kind of a single descriptor in a SCIP symbol, tagged by its
+/// surrounding punctuation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DescriptorKind {
+    /// `name/`
+    Namespace,
+    /// `name#`
+    Type,
+    /// `name.`
+    Term,
+    /// `name(disambiguator).`
+    Method,
+    /// `[name]`
+    TypeParameter,
+    /// `(name)`
+    Parameter,
+    /// `name:`
+    Meta,
+}
+
+/// One descriptor of a parsed SCIP symbol: its name and what kind of thing
+/// it names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Descriptor {
+    pub name: String,
+    pub kind: DescriptorKind,
+}
+
+impl Descriptor {
+    /// Render this descriptor back to SCIP syntax, e.g. a `Type` named
+    /// `"Foo"` becomes `"Foo#"`, backtick-escaping the name if it contains
+    /// grammar punctuation. Method disambiguators aren't round-tripped --
+    /// [`parse_descriptors`] doesn't retain them, since nothing in this
+    /// crate keys on them.
+    pub fn render(&self) -> String {
+        let name = escape_name(&self.name);
+        match self.kind {
+            DescriptorKind::Namespace => format!("{name}/"),
+            DescriptorKind::Type => format!("{name}#"),
+            DescriptorKind::Term => format!("{name}."),
+            DescriptorKind::Method => format!("{name}()."),
+            DescriptorKind::TypeParameter => format!("[{name}]"),
+            DescriptorKind::Parameter => format!("({name})"),
+            DescriptorKind::Meta => format!("{name}:"),
+        }
+    }
+}
+
+/// A symbol's package: the dependency manager, package name, and version,
+/// e.g. `cargo curve25519-dalek 4.1.3`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    pub manager: String,
+    pub name: String,
+    pub version: String,
+}
+
+impl Package {
+    /// `name` and `version` only (no `manager`), space-escaped, e.g.
+    /// `"curve25519-dalek 4.1.3"`.
+    pub fn name_and_version(&self) -> String {
+        format!("{} {}", escape_field_space(&self.name), self.version)
+    }
+}
+
+impl std::fmt::Display for Package {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.manager, self.name_and_version())
+    }
+}
+
+/// A SCIP symbol parsed into its grammar components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedSymbol {
+    pub scheme: String,
+    pub package: Package,
+    pub descriptors: Vec<Descriptor>,
+}
+
+impl ParsedSymbol {
+    /// The last descriptor, if any -- the thing the symbol ultimately
+    /// refers to (a type, a method, a term, ...).
+    pub fn last_descriptor(&self) -> Option<&Descriptor> {
+        self.descriptors.last()
+    }
+
+    /// Whether the symbol's last descriptor is a type reference.
+    pub fn is_type(&self) -> bool {
+        matches!(self.last_descriptor(), Some(d) if d.kind == DescriptorKind::Type)
+    }
+
+    /// The name of the last descriptor, if it's a `Type`.
+    pub fn type_name(&self) -> Option<&str> {
+        self.last_descriptor()
+            .filter(|d| d.kind == DescriptorKind::Type)
+            .map(|d| d.name.as_str())
+    }
+
+    /// Render just the descriptor chain, e.g. `"montgomery/Mul#mul()."`,
+    /// without the `scheme package` prefix.
+    pub fn render_descriptors(&self) -> String {
+        self.descriptors.iter().map(Descriptor::render).collect()
+    }
+}
+
+impl std::fmt::Display for ParsedSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.scheme, self.package, self.render_descriptors())
+    }
+}
+
+/// Parse a raw SCIP symbol string into its structured form.
+///
+/// Returns `None` for `local ...` symbols, which have no package/descriptor
+/// grammar, or for a string too short to contain the four whitespace-
+/// separated prefix fields.
+pub fn parse_symbol(symbol: &str) -> Option<ParsedSymbol> {
+    if symbol.starts_with("local ") || symbol == "local" {
+        return None;
+    }
+
+    let (mut fields, rest_start) = split_prefix_fields(symbol, 4)?;
+    let version = fields.pop().unwrap();
+    let name = fields.pop().unwrap();
+    let manager = fields.pop().unwrap();
+    let scheme = fields.pop().unwrap();
+
+    Some(ParsedSymbol {
+        scheme,
+        package: Package {
+            manager,
+            name,
+            version,
+        },
+        descriptors: parse_descriptors(&symbol[rest_start..]),
+    })
+}
+
+/// Double literal spaces in a package-name field, SCIP's escaping rule for
+/// the one prefix field allowed to contain a space.
+fn escape_field_space(field: &str) -> String {
+    field.replace(' ', "  ")
+}
+
+/// Backtick-escape a descriptor name if it contains any of the punctuation
+/// that would otherwise be parsed as a descriptor delimiter.
+fn escape_name(name: &str) -> String {
+    let needs_escaping = name.is_empty()
+        || name
+            .chars()
+            .any(|c| matches!(c, '/' | '#' | '.' | '(' | ')' | '[' | ']' | ':' | '`' | ' '));
+    if !needs_escaping {
+        return name.to_string();
+    }
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// Split off the first `n` space-separated fields of `symbol`, honoring
+/// SCIP's space-escaping rule (a doubled space is a literal space inside a
+/// field, not a separator). Returns the fields plus the byte offset where
+/// the remainder of the string (the descriptors) begins.
+fn split_prefix_fields(symbol: &str, n: usize) -> Option<(Vec<String>, usize)> {
+    let mut chars = symbol.char_indices().peekable();
+    let mut fields = Vec::with_capacity(n);
+    let mut current = String::new();
+
+    while fields.len() < n {
+        match chars.next() {
+            None => return None,
+            Some((_, ' ')) => {
+                if let Some(&(_, ' ')) = chars.peek() {
+                    chars.next();
+                    current.push(' ');
+                    continue;
+                }
+                fields.push(std::mem::take(&mut current));
+            }
+            Some((_, c)) => current.push(c),
+        }
+    }
+
+    let rest_start = chars.peek().map(|&(idx, _)| idx).unwrap_or(symbol.len());
+    Some((fields, rest_start))
+}
+
+/// Parse a backtick-escaped or bare descriptor name starting at the current
+/// position, consuming it (and its closing backtick, if any) from `chars`.
+fn parse_name(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    if chars.peek() != Some(&'`') {
+        let mut name = String::new();
+        while let Some(&c) = chars.peek() {
+            if matches!(c, '/' | '#' | '.' | '(' | ')' | '[' | ']' | ':') {
+                break;
+            }
+            name.push(c);
+            chars.next();
+        }
+        return name;
+    }
+
+    chars.next(); // opening backtick
+    let mut name = String::new();
+    while let Some(c) = chars.next() {
+        if c == '`' {
+            if chars.peek() == Some(&'`') {
+                chars.next();
+                name.push('`');
+                continue;
+            }
+            break;
+        }
+        name.push(c);
+    }
+    name
+}
+
+/// Parse the descriptor portion of a symbol (everything after the four
+/// prefix fields) into a sequence of [`Descriptor`]s.
+fn parse_descriptors(input: &str) -> Vec<Descriptor> {
+    let mut chars = input.chars().peekable();
+    let mut descriptors = Vec::new();
+
+    while let Some(&next) = chars.peek() {
+        match next {
+            '(' => {
+                chars.next();
+                let name = parse_name(&mut chars);
+                if chars.peek() == Some(&')') {
+                    chars.next();
+                }
+                descriptors.push(Descriptor {
+                    name,
+                    kind: DescriptorKind::Parameter,
+                });
+            }
+            '[' => {
+                chars.next();
+                let name = parse_name(&mut chars);
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                }
+                descriptors.push(Descriptor {
+                    name,
+                    kind: DescriptorKind::TypeParameter,
+                });
+            }
+            _ => {
+                let name = parse_name(&mut chars);
+                match chars.next() {
+                    Some('/') => descriptors.push(Descriptor {
+                        name,
+                        kind: DescriptorKind::Namespace,
+                    }),
+                    Some('#') => descriptors.push(Descriptor {
+                        name,
+                        kind: DescriptorKind::Type,
+                    }),
+                    Some('.') => descriptors.push(Descriptor {
+                        name,
+                        kind: DescriptorKind::Term,
+                    }),
+                    Some(':') => descriptors.push(Descriptor {
+                        name,
+                        kind: DescriptorKind::Meta,
+                    }),
+                    Some('(') => {
+                        // Method disambiguator: `(digits or '+').` -- the
+                        // disambiguator itself isn't part of the name.
+                        for c in chars.by_ref() {
+                            if c == ')' {
+                                break;
+                            }
+                        }
+                        if chars.peek() == Some(&'.') {
+                            chars.next();
+                        }
+                        descriptors.push(Descriptor {
+                            name,
+                            kind: DescriptorKind::Method,
+                        });
+                    }
+                    // Unterminated name: malformed input, stop rather than
+                    // guess at a kind.
+                    _ => break,
+                }
+            }
+        }
+    }
+
+    descriptors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_type_descriptor() {
+        let symbol = "rust-analyzer cargo curve25519-dalek 4.1.3 curve_models/serial/backend/ProjectiveNielsPoint#";
+        let parsed = parse_symbol(symbol).expect("should parse");
+        assert_eq!(parsed.scheme, "rust-analyzer");
+        assert_eq!(parsed.package.manager, "cargo");
+        assert_eq!(parsed.package.name, "curve25519-dalek");
+        assert_eq!(parsed.package.version, "4.1.3");
+        assert!(parsed.is_type());
+        assert_eq!(parsed.type_name(), Some("ProjectiveNielsPoint"));
+    }
+
+    #[test]
+    fn parses_namespace_and_method_descriptors() {
+        let symbol = "rust-analyzer cargo my-crate 0.1.0 mymod/MyStruct#method().";
+        let parsed = parse_symbol(symbol).expect("should parse");
+        assert_eq!(parsed.descriptors.len(), 3);
+        assert_eq!(parsed.descriptors[0].kind, DescriptorKind::Namespace);
+        assert_eq!(parsed.descriptors[0].name, "mymod");
+        assert_eq!(parsed.descriptors[1].kind, DescriptorKind::Type);
+        assert_eq!(parsed.descriptors[1].name, "MyStruct");
+        assert_eq!(parsed.descriptors[2].kind, DescriptorKind::Method);
+        assert_eq!(parsed.descriptors[2].name, "method");
+        assert!(!parsed.is_type());
+    }
+
+    #[test]
+    fn handles_backtick_escaped_name() {
+        let symbol = "rust-analyzer cargo my-crate 0.1.0 mymod/`weird name`#";
+        let parsed = parse_symbol(symbol).expect("should parse");
+        assert_eq!(parsed.type_name(), Some("weird name"));
+    }
+
+    #[test]
+    fn handles_doubled_space_in_package_name() {
+        let symbol = "scip-typescript npm @types/node  lts 22.5.4 index.d.ts/`global`.";
+        let parsed = parse_symbol(symbol).expect("should parse");
+        assert_eq!(parsed.package.name, "@types/node lts");
+        assert_eq!(parsed.package.version, "22.5.4");
+    }
+
+    #[test]
+    fn local_symbols_are_not_parsed() {
+        assert!(parse_symbol("local 5").is_none());
+    }
+
+    #[test]
+    fn to_string_round_trips() {
+        let symbols = [
+            "rust-analyzer cargo curve25519-dalek 4.1.3 curve_models/serial/backend/ProjectiveNielsPoint#",
+            "rust-analyzer cargo my-crate 0.1.0 mymod/MyStruct#method().",
+            "rust-analyzer cargo my-crate 0.1.0 mymod/`weird name`#",
+        ];
+        for symbol in symbols {
+            let parsed = parse_symbol(symbol).expect("should parse");
+            assert_eq!(parsed.to_string(), symbol);
+        }
+    }
+
+    #[test]
+    fn to_string_leaves_names_without_delimiters_unescaped() {
+        // Round-tripping through the Descriptor representation, a name
+        // built by splicing in a disambiguating type parameter (which
+        // contains `<`/`>`/`,` but no grammar-delimiter punctuation this
+        // parser checks for) still renders back out unescaped.
+        let mut parsed = parse_symbol("rust-analyzer cargo my-crate 0.1.0 mymod/Mul#mul().")
+            .expect("should parse");
+        parsed.descriptors[1].name = "Mul<Scalar>".to_string();
+        assert_eq!(
+            parsed.to_string(),
+            "rust-analyzer cargo my-crate 0.1.0 mymod/Mul<Scalar>#mul()."
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! A global, thread-safe string interner for the scope-path segments
+//! [`symbol_table`](crate::symbol_table) indexes on.
+//!
+//! Atom extraction produces a lot of repeated strings -- the same module
+//! name, type name, and method name recur across every impl and every
+//! call site. [`SymbolTable`](crate::symbol_table::SymbolTable) looks
+//! symbols up by decomposed scope path, so it ends up hashing and
+//! comparing those repeated segments over and over. Interning them once
+//! into a [`Symbol`] -- a small `Copy` handle -- turns that into integer
+//! equality and a single `u32` hash instead of byte-by-byte string
+//! comparison, and means each distinct segment is stored exactly once.
+//!
+//! [`Symbol`] serializes as the string it was interned from, so callers
+//! at the edge (e.g. `json_output`) never need to know interning
+//! happened at all.
+
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// A small `Copy` handle standing in for an interned string. Equality and
+/// hashing are on the handle's `u32`, not the string it names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Intern `text`, returning the same [`Symbol`] every time the same
+    /// string is interned.
+    pub fn intern(text: &str) -> Self {
+        interner().intern(text)
+    }
+
+    /// Resolve this handle back to the string it was interned from.
+    ///
+    /// Panics if `self` was not produced by [`Symbol::intern`] in this
+    /// process, which can't happen through the public API.
+    pub fn as_str(self) -> &'static str {
+        interner().resolve(self)
+    }
+}
+
+impl std::fmt::Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(text: &str) -> Self {
+        Symbol::intern(text)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(text: String) -> Self {
+        Symbol::intern(&text)
+    }
+}
+
+/// Resolves back to the underlying string at the edge, so a serialized
+/// [`Symbol`] is indistinguishable from a plain `String` field.
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Ok(Symbol::intern(&text))
+    }
+}
+
+/// The process-wide atom table: distinct strings leaked once (so
+/// [`Symbol::as_str`] can hand back a `&'static str` without cloning) and
+/// indexed by insertion order.
+#[derive(Default)]
+struct Interner {
+    table: RwLock<InternerTable>,
+}
+
+#[derive(Default)]
+struct InternerTable {
+    strings: Vec<&'static str>,
+    ids: HashMap<&'static str, u32>,
+}
+
+impl Interner {
+    fn intern(&self, text: &str) -> Symbol {
+        if let Some(id) = self.table.read().unwrap().ids.get(text) {
+            return Symbol(*id);
+        }
+
+        let mut table = self.table.write().unwrap();
+        // Another thread may have interned `text` while we waited for the
+        // write lock; re-check before allocating a new id.
+        if let Some(id) = table.ids.get(text) {
+            return Symbol(*id);
+        }
+
+        let leaked: &'static str = Box::leak(text.to_string().into_boxed_str());
+        let id = table.strings.len() as u32;
+        table.strings.push(leaked);
+        table.ids.insert(leaked, id);
+        Symbol(id)
+    }
+
+    fn resolve(&self, symbol: Symbol) -> &'static str {
+        self.table.read().unwrap().strings[symbol.0 as usize]
+    }
+}
+
+fn interner() -> &'static Interner {
+    static INTERNER: OnceLock<Interner> = OnceLock::new();
+    INTERNER.get_or_init(Interner::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interning_the_same_string_twice_returns_the_same_handle() {
+        let a = Symbol::intern("scalar/Scalar#add()");
+        let b = Symbol::intern("scalar/Scalar#add()");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn interning_different_strings_returns_different_handles() {
+        let a = Symbol::intern("scalar/Scalar#add()");
+        let b = Symbol::intern("scalar/Scalar#sub()");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn a_handle_resolves_back_to_its_original_string() {
+        let symbol = Symbol::intern("montgomery/MontgomeryPoint#mul()");
+        assert_eq!(symbol.as_str(), "montgomery/MontgomeryPoint#mul()");
+    }
+
+    #[test]
+    fn symbol_serializes_as_the_interned_string() {
+        let symbol = Symbol::intern("serialize-me");
+        let json = serde_json::to_string(&symbol).unwrap();
+        assert_eq!(json, "\"serialize-me\"");
+    }
+
+    #[test]
+    fn symbol_round_trips_through_json() {
+        let symbol = Symbol::intern("round-trip-me");
+        let json = serde_json::to_string(&symbol).unwrap();
+        let back: Symbol = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, symbol);
+        assert_eq!(back.as_str(), "round-trip-me");
+    }
+}
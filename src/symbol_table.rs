@@ -0,0 +1,180 @@
+//! Hierarchical symbol table over `AtomWithLines` output, keyed by
+//! decomposed scope path (module/type/method) rather than a flat
+//! `Symbol -> display_name` map.
+//!
+//! `symbol_to_display_name` only ever supports one lookup: raw SCIP symbol
+//! to display name, falling back to `"unknown"` when a callee isn't
+//! present in the map at all. [`SymbolTable`] indexes the richer
+//! `scip_name` path instead, so callers can resolve a full path exactly,
+//! look up every candidate sharing a leaf (method/type) name when the path
+//! is ambiguous, or ask "what lives under this module" without rescanning
+//! the whole atom list.
+//!
+//! The same module/type/method segments recur across every impl and every
+//! call site, so scope paths are decomposed into interned
+//! [`Segment`](crate::symbol_interner::Symbol) handles rather than
+//! `String`s: building the table hashes each distinct segment once, and
+//! every lookup afterwards is handle equality instead of byte comparison.
+
+use crate::symbol_interner::Symbol;
+use crate::AtomWithLines;
+use std::collections::HashMap;
+
+/// One segment of a decomposed scope path, interned so repeated
+/// module/type/method names across atoms share a single handle.
+pub type Segment = Symbol;
+
+/// Split a `scip_name`'s descriptor path into ordered, interned scope
+/// segments, e.g. `"curve25519-dalek 4.1.3 scalar/Scalar#hash_from_bytes()"`
+/// becomes `[scalar, Scalar, hash_from_bytes]`. The package name and
+/// version prefix is dropped; descriptor suffix punctuation
+/// (`/ # . ( ) : [ ]`) is just a separator once the path is decomposed
+/// into segments.
+pub fn scope_path(scip_name: &str) -> Vec<Segment> {
+    let path = scip_name.splitn(3, ' ').nth(2).unwrap_or(scip_name);
+    path.split(['/', '#', '.', '(', ')', ':', '[', ']'])
+        .filter(|segment| !segment.is_empty())
+        .map(Segment::intern)
+        .collect()
+}
+
+/// A hierarchical index over a set of atoms, keyed by decomposed scope path
+/// instead of a single flat string.
+pub struct SymbolTable<'a> {
+    by_path: HashMap<Vec<Segment>, &'a AtomWithLines>,
+    by_leaf_name: HashMap<Segment, Vec<&'a AtomWithLines>>,
+}
+
+impl<'a> SymbolTable<'a> {
+    /// Index every atom's `scip_name`, decomposed via [`scope_path`].
+    pub fn build(atoms: &'a [AtomWithLines]) -> Self {
+        let mut by_path = HashMap::new();
+        let mut by_leaf_name: HashMap<Segment, Vec<&'a AtomWithLines>> = HashMap::new();
+
+        for atom in atoms {
+            let path = scope_path(&atom.scip_name);
+            if let Some(&leaf) = path.last() {
+                by_leaf_name.entry(leaf).or_default().push(atom);
+            }
+            by_path.insert(path, atom);
+        }
+
+        SymbolTable {
+            by_path,
+            by_leaf_name,
+        }
+    }
+
+    /// Resolve a full scope path, e.g. `[scalar, Scalar,
+    /// hash_from_bytes]`, to the one atom it names, if any.
+    pub fn lookup_by_path(&self, path: &[Segment]) -> Option<&'a AtomWithLines> {
+        self.by_path.get(path).copied()
+    }
+
+    /// Every atom whose scope path ends in `name` -- the leaf-name lookup,
+    /// returning all candidates when more than one definition shares it
+    /// (e.g. the same method name across several impls) instead of forcing
+    /// callers to pick one.
+    pub fn lookup_by_leaf_name(&self, name: &str) -> &[&'a AtomWithLines] {
+        self.by_leaf_name
+            .get(&Segment::intern(name))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Every atom whose scope path starts with `prefix` -- "what lives
+    /// under this module/type" without rescanning the whole atom list.
+    pub fn lookup_by_prefix(&self, prefix: &[Segment]) -> Vec<&'a AtomWithLines> {
+        self.by_path
+            .iter()
+            .filter(|(path, _)| path.starts_with(prefix))
+            .map(|(_, atom)| *atom)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CodeTextInfo;
+    use std::collections::HashSet;
+
+    fn atom(scip_name: &str) -> AtomWithLines {
+        AtomWithLines {
+            display_name: scip_name.rsplit(['.', '#', '/']).next().unwrap_or("").to_string(),
+            scip_name: scip_name.to_string(),
+            dependencies: HashSet::new(),
+            ambiguous_dependencies: HashSet::new(),
+            code_path: "src/scalar.rs".to_string(),
+            code_text: CodeTextInfo {
+                lines_start: 1,
+                lines_end: 2,
+            },
+        }
+    }
+
+    #[test]
+    fn splits_a_scip_name_into_scope_segments() {
+        let path = scope_path("curve25519-dalek 4.1.3 scalar/Scalar#hash_from_bytes()");
+        assert_eq!(
+            path,
+            vec![
+                Segment::intern("scalar"),
+                Segment::intern("Scalar"),
+                Segment::intern("hash_from_bytes"),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_an_exact_path() {
+        let atoms = vec![atom("my-crate 0.1.0 scalar/Scalar#add()")];
+        let table = SymbolTable::build(&atoms);
+        let found = table
+            .lookup_by_path(&[
+                Segment::intern("scalar"),
+                Segment::intern("Scalar"),
+                Segment::intern("add"),
+            ])
+            .expect("expected a match");
+        assert_eq!(found.scip_name, "my-crate 0.1.0 scalar/Scalar#add()");
+        assert!(table
+            .lookup_by_path(&[
+                Segment::intern("scalar"),
+                Segment::intern("Scalar"),
+                Segment::intern("sub"),
+            ])
+            .is_none());
+    }
+
+    #[test]
+    fn leaf_name_lookup_returns_every_ambiguous_candidate() {
+        let atoms = vec![
+            atom("my-crate 0.1.0 scalar/Scalar#add()"),
+            atom("my-crate 0.1.0 montgomery/MontgomeryPoint#add()"),
+            atom("my-crate 0.1.0 scalar/Scalar#invert()"),
+        ];
+        let table = SymbolTable::build(&atoms);
+        assert_eq!(table.lookup_by_leaf_name("add").len(), 2);
+        assert_eq!(table.lookup_by_leaf_name("invert").len(), 1);
+        assert!(table.lookup_by_leaf_name("missing").is_empty());
+    }
+
+    #[test]
+    fn prefix_lookup_returns_everything_under_a_module() {
+        let atoms = vec![
+            atom("my-crate 0.1.0 scalar/Scalar#add()"),
+            atom("my-crate 0.1.0 scalar/Scalar#invert()"),
+            atom("my-crate 0.1.0 montgomery/MontgomeryPoint#add()"),
+        ];
+        let table = SymbolTable::build(&atoms);
+        let under_scalar = table.lookup_by_prefix(&[Segment::intern("scalar")]);
+        assert_eq!(under_scalar.len(), 2);
+        let under_scalar_type = table.lookup_by_prefix(&[
+            Segment::intern("scalar"),
+            Segment::intern("Scalar"),
+            Segment::intern("add"),
+        ]);
+        assert_eq!(under_scalar_type.len(), 1);
+    }
+}
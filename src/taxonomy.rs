@@ -7,6 +7,7 @@
 use crate::verus_parser::FunctionInfo;
 use crate::FunctionMode;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Top-level taxonomy config (wraps the `[taxonomy]` table).
@@ -25,6 +26,25 @@ pub struct TaxonomyRoot {
     /// classification signal and can be filtered out to simplify rule writing.
     #[serde(default)]
     pub stop_words: Vec<String>,
+    /// Additive (every matching rule's label is emitted) or winner-take-all
+    /// (within a `group`, only the highest-priority match is emitted).
+    /// Defaults to additive, the original behavior.
+    #[serde(default)]
+    pub classification_mode: ClassificationMode,
+}
+
+/// How [`classify_function`] resolves multiple matching rules that share a
+/// [`TaxonomyRule::group`].
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassificationMode {
+    /// Every matching rule's label is emitted (the original behavior).
+    #[default]
+    Additive,
+    /// Among matching rules sharing a `group`, only the highest-priority
+    /// one's label is emitted (ties broken by earliest declaration order).
+    /// Rules with no `group` stay additive even in this mode.
+    WinnerTakeAll,
 }
 
 /// A single classification rule.
@@ -35,6 +55,84 @@ pub struct TaxonomyRule {
     pub trust: String,
     #[serde(rename = "match")]
     pub match_criteria: MatchCriteria,
+    /// Priority for winner-take-all resolution within `group` (higher
+    /// wins; ties broken by earliest declaration order). Ignored in
+    /// additive mode or when `group` is unset.
+    #[serde(default)]
+    pub priority: i64,
+    /// Exclusivity group for winner-take-all resolution. Rules with no
+    /// group are always additive, even in winner-take-all mode.
+    pub group: Option<String>,
+    /// An optional boolean rule expression (see [`RuleExpr`]), evaluated in
+    /// addition to `match_criteria` -- a rule matches only when both agree.
+    /// Lets a label scale past the flat `MatchCriteria` field list (e.g.
+    /// "exec and calls any `spec_*` and is not `#[verifier::external]`")
+    /// without needing a new named field for every combination. Not yet
+    /// reflected in [`explain_function`]'s per-rule explanations, which
+    /// only walk `match_criteria`.
+    pub rule_expr: Option<RuleExpr>,
+}
+
+/// How a [`CallMatcher`]'s `values` are compared against a call name.
+/// Borrowed from rust-analyzer SSR's shift from textual to resolved-path
+/// matching: `contains` is the old substring behavior (and the default, so
+/// existing rules keep working unchanged), while `exact`/`suffix` compare
+/// against the fully-qualified path in `ensures_calls_full`/
+/// `requires_calls_full` so a rule can pin to one specific function instead
+/// of every lexical lookalike (`is_valid` no longer spuriously matches
+/// `is_invalid`, and a same-named function in another module doesn't match
+/// at all).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchMode {
+    /// `values` must equal the fully-qualified call path exactly.
+    Exact,
+    /// The fully-qualified call path must end with one of `values`.
+    Suffix,
+    /// The call name must contain one of `values` as a substring.
+    Contains,
+    /// One of `values`, compiled as a regex, must match the fully-qualified
+    /// call path (falling back to the unqualified name). An invalid regex
+    /// never matches, the same "criterion not met, not an error" treatment
+    /// as elsewhere in this module.
+    Regex,
+}
+
+impl Default for MatchMode {
+    fn default() -> Self {
+        MatchMode::Contains
+    }
+}
+
+/// A call-name criterion: either a bare list of substrings (legacy TOML
+/// shape, e.g. `ensures_calls = ["is_canonical"]`, equivalent to
+/// `{ match = "contains", values = [...] }`) or an explicit
+/// `{ match = "...", values = [...] }` table selecting a [`MatchMode`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum CallMatcher {
+    Values(Vec<String>),
+    WithMode {
+        #[serde(rename = "match")]
+        mode: MatchMode,
+        values: Vec<String>,
+    },
+}
+
+impl CallMatcher {
+    fn mode(&self) -> MatchMode {
+        match self {
+            CallMatcher::Values(_) => MatchMode::Contains,
+            CallMatcher::WithMode { mode, .. } => *mode,
+        }
+    }
+
+    fn values(&self) -> &[String] {
+        match self {
+            CallMatcher::Values(values) => values,
+            CallMatcher::WithMode { values, .. } => values,
+        }
+    }
 }
 
 /// Match criteria for a rule. All specified criteria must match (AND).
@@ -45,6 +143,12 @@ pub struct MatchCriteria {
     pub mode: Option<Vec<String>>,
     /// Function context must be one of these (impl, trait, standalone)
     pub context: Option<Vec<String>>,
+    /// At least one ensures call must match, per the selected [`MatchMode`]
+    /// (`contains` by default). See [`CallMatcher`].
+    pub ensures_calls: Option<CallMatcher>,
+    /// At least one requires call must match, per the selected
+    /// [`MatchMode`]; see [`MatchCriteria::ensures_calls`].
+    pub requires_calls: Option<CallMatcher>,
     /// At least one ensures call name must contain one of these substrings
     pub ensures_calls_contain: Option<Vec<String>>,
     /// At least one requires call name must contain one of these substrings
@@ -77,6 +181,31 @@ pub struct MatchCriteria {
     pub requires_fn_calls_contain: Option<Vec<String>>,
     /// At least one requires method call must contain one of these substrings
     pub requires_method_calls_contain: Option<Vec<String>>,
+    /// SSR-style structural pattern over `ensures_text`, e.g.
+    /// `"$result == spec_$f($x)"` -- a template of literal tokens plus
+    /// `$name` placeholders, matched against any sub-node of the clause's
+    /// expression tree. See [`match_clause_pattern`].
+    pub ensures_pattern: Option<String>,
+    /// Structural pattern over `requires_text`; see
+    /// [`MatchCriteria::ensures_pattern`].
+    pub requires_pattern: Option<String>,
+    /// A UFCS-style path (e.g. `"foo::Bar::baz"`) that matches the ensures
+    /// clause's calls whether the spec helper was invoked as `Bar::baz(s, a)`
+    /// or as the method call `s.baz(a)`. One-directional: written to match
+    /// a method call, not the reverse. See [`ufcs_matches`].
+    pub ensures_ufcs_contain: Option<Vec<String>>,
+    /// UFCS-style match over the requires clause; see
+    /// [`MatchCriteria::ensures_ufcs_contain`].
+    pub requires_ufcs_contain: Option<Vec<String>>,
+    /// Nested criteria that must ALL pass, in addition to this level's own
+    /// leaf criteria (which are always ANDed). Lets a rule express
+    /// "(A) AND (B or C)" without duplicating leaf criteria across
+    /// separate TOML rules.
+    pub all_of: Option<Vec<MatchCriteria>>,
+    /// Nested criteria where at least ONE must pass.
+    pub any_of: Option<Vec<MatchCriteria>>,
+    /// Nested criteria that must NOT pass.
+    pub not: Option<Box<MatchCriteria>>,
 }
 
 /// Load a taxonomy config from a TOML file.
@@ -92,7 +221,29 @@ pub fn load_taxonomy_config(path: &Path) -> Result<TaxonomyConfig, String> {
 /// Rules are evaluated independently; all matching rules contribute their label.
 /// If the config defines `stop_words`, those are filtered from ensures_calls/requires_calls
 /// before rule evaluation.
+/// Under [`ClassificationMode::WinnerTakeAll`], only the winning rule of each
+/// `group` contributes its label; ungrouped rules still all contribute.
 pub fn classify_function(func: &FunctionInfo, config: &TaxonomyConfig) -> Vec<String> {
+    classify_function_impl(func, config, None)
+}
+
+/// Transitive-aware analogue of [`classify_function`]: a rule using
+/// [`RuleExpr::CallsMatchingTransitive`] is evaluated over `graph` -- built
+/// with [`CallGraph::build`] over the whole function corpus -- instead of
+/// falling back to a direct, single-hop check.
+pub fn classify_function_with_graph(
+    func: &FunctionInfo,
+    config: &TaxonomyConfig,
+    graph: &CallGraph,
+) -> Vec<String> {
+    classify_function_impl(func, config, Some(graph))
+}
+
+fn classify_function_impl(
+    func: &FunctionInfo,
+    config: &TaxonomyConfig,
+    graph: Option<&CallGraph>,
+) -> Vec<String> {
     // Apply stop-word filtering if configured
     let filtered;
     let effective_func = if config.taxonomy.stop_words.is_empty() {
@@ -102,13 +253,85 @@ pub fn classify_function(func: &FunctionInfo, config: &TaxonomyConfig) -> Vec<St
         &filtered
     };
 
-    let mut labels = Vec::new();
-    for rule in &config.taxonomy.rules {
-        if rule_matches(effective_func, &rule.match_criteria) {
-            labels.push(rule.label.clone());
+    let matched: Vec<&TaxonomyRule> = config
+        .taxonomy
+        .rules
+        .iter()
+        .filter(|rule| {
+            criteria_matches(effective_func, &rule.match_criteria)
+                && rule
+                    .rule_expr
+                    .as_ref()
+                    .map_or(true, |expr| eval_rule_expr(effective_func, expr, graph))
+        })
+        .collect();
+
+    resolve_labels(&matched, config.taxonomy.classification_mode)
+}
+
+/// For winner-take-all mode, the position (into `rules`) of each `group`'s
+/// winning rule: highest `priority`, ties broken by earliest declaration
+/// order (smallest index).
+fn winners_by_group<'a>(rules: &[&'a TaxonomyRule]) -> HashMap<&'a str, usize> {
+    let mut winners: HashMap<&str, usize> = HashMap::new();
+    for (i, rule) in rules.iter().enumerate() {
+        let Some(group) = rule.group.as_deref() else {
+            continue;
+        };
+        match winners.get(group) {
+            Some(&current) if rules[current].priority >= rule.priority => {}
+            _ => {
+                winners.insert(group, i);
+            }
+        }
+    }
+    winners
+}
+
+/// Resolve the label set emitted for a list of already-matched rules,
+/// honoring `mode`: additive emits every match; winner-take-all keeps, per
+/// `group`, only the winner from [`winners_by_group`] (ungrouped rules stay
+/// additive either way).
+fn resolve_labels(matched: &[&TaxonomyRule], mode: ClassificationMode) -> Vec<String> {
+    if mode == ClassificationMode::Additive {
+        return matched.iter().map(|rule| rule.label.clone()).collect();
+    }
+
+    let winners = winners_by_group(matched);
+    matched
+        .iter()
+        .enumerate()
+        .filter(|(i, rule)| match rule.group.as_deref() {
+            None => true,
+            Some(group) => winners.get(group) == Some(i),
+        })
+        .map(|(_, rule)| rule.label.clone())
+        .collect()
+}
+
+/// Evaluate a [`MatchCriteria`] recursively: this level's own leaf criteria
+/// (via [`rule_matches`]) ANDed with its `all_of`/`any_of`/`not`
+/// combinators, each built from the same recursive criteria algebra.
+fn criteria_matches(func: &FunctionInfo, criteria: &MatchCriteria) -> bool {
+    if !rule_matches(func, criteria) {
+        return false;
+    }
+    if let Some(children) = &criteria.all_of {
+        if !children.iter().all(|c| criteria_matches(func, c)) {
+            return false;
+        }
+    }
+    if let Some(children) = &criteria.any_of {
+        if !children.iter().any(|c| criteria_matches(func, c)) {
+            return false;
+        }
+    }
+    if let Some(child) = &criteria.not {
+        if criteria_matches(func, child) {
+            return false;
         }
     }
-    labels
+    true
 }
 
 /// Create a copy of FunctionInfo with stop words removed from ensures_calls and requires_calls.
@@ -128,8 +351,43 @@ fn filter_stop_words(func: &FunctionInfo, stop_words: &[String]) -> FunctionInfo
 pub struct RuleExplanation {
     pub label: String,
     pub matched: bool,
-    /// For each criterion that was checked, the name and whether it passed.
+    /// For each of the rule's top-level leaf criteria, the name and whether
+    /// it passed. Does not include `all_of`/`any_of`/`not` children -- see
+    /// [`RuleExplanation::criteria`] for the full nested tree.
     pub criteria_results: Vec<(String, bool)>,
+    /// Placeholder bindings captured by `ensures_pattern`/`requires_pattern`
+    /// at the top level, rendered back to text (e.g. `"f" -> "foo"` when
+    /// `$f` matched `foo` in `spec_foo(...)`), so a rule can report *what*
+    /// matched, not just that it did.
+    pub pattern_bindings: HashMap<String, String>,
+    /// The full recursive explanation, mirroring `MatchCriteria`'s
+    /// `all_of`/`any_of`/`not` structure so a failure inside a nested
+    /// combinator is attributable to the specific child that caused it.
+    pub criteria: CriteriaExplanation,
+    /// If this rule matched but, under winner-take-all mode, lost to a
+    /// higher-priority rule sharing its `group`, the winning rule's label.
+    /// `None` when this rule's own label was actually emitted (including
+    /// always, under additive mode or when `group` is unset).
+    pub suppressed_by: Option<String>,
+}
+
+/// Nested explanation for one level of [`MatchCriteria`] evaluation. Mirrors
+/// the criteria's own recursive shape: leaf results at this level, plus a
+/// child explanation per `all_of`/`any_of` entry and for `not`.
+#[derive(Debug)]
+pub struct CriteriaExplanation {
+    pub matched: bool,
+    /// This level's own leaf criteria (the existing flat fields), ANDed.
+    pub leaf_results: Vec<(String, bool)>,
+    /// Placeholder bindings captured by this level's `ensures_pattern`/
+    /// `requires_pattern`.
+    pub pattern_bindings: HashMap<String, String>,
+    /// `all_of` children, in order.
+    pub all_of: Vec<CriteriaExplanation>,
+    /// `any_of` children, in order.
+    pub any_of: Vec<CriteriaExplanation>,
+    /// `not` child, if present.
+    pub not: Option<Box<CriteriaExplanation>>,
 }
 
 /// Explain which rules matched and which didn't, and why.
@@ -144,25 +402,129 @@ pub fn explain_function(func: &FunctionInfo, config: &TaxonomyConfig) -> Vec<Rul
         &filtered
     };
 
+    let criteria_explanations: Vec<CriteriaExplanation> = config
+        .taxonomy
+        .rules
+        .iter()
+        .map(|rule| explain_criteria(effective_func, &rule.match_criteria))
+        .collect();
+
+    let matched: Vec<(usize, &TaxonomyRule)> = config
+        .taxonomy
+        .rules
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| criteria_explanations[*i].matched)
+        .collect();
+    let matched_rules: Vec<&TaxonomyRule> = matched.iter().map(|(_, rule)| *rule).collect();
+    let winners = winners_by_group(&matched_rules);
+    let position_among_matched: HashMap<usize, usize> = matched
+        .iter()
+        .enumerate()
+        .map(|(pos, (orig, _))| (*orig, pos))
+        .collect();
+
     config
         .taxonomy
         .rules
         .iter()
-        .map(|rule| {
-            let results = explain_rule_match(effective_func, &rule.match_criteria);
-            let all_passed = results.iter().all(|(_, passed)| *passed);
+        .zip(criteria_explanations)
+        .enumerate()
+        .map(|(i, (rule, criteria))| {
+            let suppressed_by = suppressed_by(
+                rule,
+                criteria.matched,
+                i,
+                config.taxonomy.classification_mode,
+                &winners,
+                &position_among_matched,
+                &matched_rules,
+            );
             RuleExplanation {
                 label: rule.label.clone(),
-                matched: all_passed,
-                criteria_results: results,
+                matched: criteria.matched,
+                criteria_results: criteria.leaf_results.clone(),
+                pattern_bindings: criteria.pattern_bindings.clone(),
+                criteria,
+                suppressed_by,
             }
         })
         .collect()
 }
 
-/// Explain each criterion of a rule match, returning (criterion_name, passed).
-fn explain_rule_match(func: &FunctionInfo, criteria: &MatchCriteria) -> Vec<(String, bool)> {
+/// The winning rule's label, if `rule` matched but lost winner-take-all
+/// resolution within its `group` -- `None` whenever this rule's own label
+/// would actually be emitted.
+#[allow(clippy::too_many_arguments)]
+fn suppressed_by(
+    rule: &TaxonomyRule,
+    matched: bool,
+    original_index: usize,
+    mode: ClassificationMode,
+    winners: &HashMap<&str, usize>,
+    position_among_matched: &HashMap<usize, usize>,
+    matched_rules: &[&TaxonomyRule],
+) -> Option<String> {
+    if !matched || mode == ClassificationMode::Additive {
+        return None;
+    }
+    let group = rule.group.as_deref()?;
+    let my_pos = position_among_matched[&original_index];
+    let winner_pos = *winners.get(group)?;
+    if winner_pos == my_pos {
+        None
+    } else {
+        Some(matched_rules[winner_pos].label.clone())
+    }
+}
+
+/// Build a [`CriteriaExplanation`] for one level of a [`MatchCriteria`],
+/// recursing into `all_of`/`any_of`/`not` with the same combinator
+/// semantics as [`criteria_matches`].
+fn explain_criteria(func: &FunctionInfo, criteria: &MatchCriteria) -> CriteriaExplanation {
+    let (leaf_results, pattern_bindings) = explain_rule_match(func, criteria);
+    let leaf_matched = leaf_results.iter().all(|(_, passed)| *passed);
+
+    let all_of: Vec<CriteriaExplanation> = criteria
+        .all_of
+        .iter()
+        .flatten()
+        .map(|c| explain_criteria(func, c))
+        .collect();
+    let all_of_matched = all_of.iter().all(|c| c.matched);
+
+    let any_of: Vec<CriteriaExplanation> = criteria
+        .any_of
+        .iter()
+        .flatten()
+        .map(|c| explain_criteria(func, c))
+        .collect();
+    let any_of_matched = criteria.any_of.is_none() || any_of.iter().any(|c| c.matched);
+
+    let not = criteria
+        .not
+        .as_ref()
+        .map(|c| Box::new(explain_criteria(func, c)));
+    let not_matched = not.as_ref().map_or(true, |c| !c.matched);
+
+    CriteriaExplanation {
+        matched: leaf_matched && all_of_matched && any_of_matched && not_matched,
+        leaf_results,
+        pattern_bindings,
+        all_of,
+        any_of,
+        not,
+    }
+}
+
+/// Explain each criterion of a rule match, returning (criterion_name, passed)
+/// for each plus any placeholder bindings captured along the way.
+fn explain_rule_match(
+    func: &FunctionInfo,
+    criteria: &MatchCriteria,
+) -> (Vec<(String, bool)>, HashMap<String, String>) {
     let mut results = Vec::new();
+    let mut bindings = HashMap::new();
 
     if let Some(modes) = &criteria.mode {
         let func_mode = mode_to_string(&func.mode);
@@ -176,6 +538,30 @@ fn explain_rule_match(func: &FunctionInfo, criteria: &MatchCriteria) -> Vec<(Str
         results.push((format!("context={:?}", contexts), passed));
     }
 
+    if let Some(matcher) = &criteria.ensures_calls {
+        let passed = call_matcher_matches(matcher, &func.ensures_calls, &func.ensures_calls_full);
+        results.push((
+            format!(
+                "ensures_calls(match={:?})={:?}",
+                matcher.mode(),
+                matcher.values()
+            ),
+            passed,
+        ));
+    }
+
+    if let Some(matcher) = &criteria.requires_calls {
+        let passed = call_matcher_matches(matcher, &func.requires_calls, &func.requires_calls_full);
+        results.push((
+            format!(
+                "requires_calls(match={:?})={:?}",
+                matcher.mode(),
+                matcher.values()
+            ),
+            passed,
+        ));
+    }
+
     if let Some(patterns) = &criteria.ensures_calls_contain {
         let passed = func
             .ensures_calls
@@ -290,7 +676,66 @@ fn explain_rule_match(func: &FunctionInfo, criteria: &MatchCriteria) -> Vec<(Str
         ));
     }
 
-    results
+    if let Some(pattern_str) = &criteria.ensures_pattern {
+        let matched = match_clause_pattern(pattern_str, func.ensures_text.as_deref());
+        let passed = matched.is_some();
+        if let Some(captured) = matched {
+            bindings.extend(captured);
+        }
+        results.push((format!("ensures_pattern={:?}", pattern_str), passed));
+    }
+
+    if let Some(pattern_str) = &criteria.requires_pattern {
+        let matched = match_clause_pattern(pattern_str, func.requires_text.as_deref());
+        let passed = matched.is_some();
+        if let Some(captured) = matched {
+            bindings.extend(captured);
+        }
+        results.push((format!("requires_pattern={:?}", pattern_str), passed));
+    }
+
+    if let Some(patterns) = &criteria.ensures_ufcs_contain {
+        let matched = ufcs_matches(
+            patterns,
+            &func.ensures_fn_calls,
+            &func.ensures_method_calls,
+            &func.ensures_calls_full,
+        );
+        let passed = matched.is_some();
+        results.push((
+            format!("ensures_ufcs_contain={:?}{}", patterns, describe_ufcs_match(&matched)),
+            passed,
+        ));
+    }
+
+    if let Some(patterns) = &criteria.requires_ufcs_contain {
+        let matched = ufcs_matches(
+            patterns,
+            &func.requires_fn_calls,
+            &func.requires_method_calls,
+            &func.requires_calls_full,
+        );
+        let passed = matched.is_some();
+        results.push((
+            format!("requires_ufcs_contain={:?}{}", patterns, describe_ufcs_match(&matched)),
+            passed,
+        ));
+    }
+
+    (results, bindings)
+}
+
+/// Human-readable suffix describing which form of a clause's calls matched
+/// an `*_ufcs_contain` criterion, for [`RuleExplanation::criteria_results`].
+fn describe_ufcs_match(matched: &Option<UfcsMatch>) -> String {
+    match matched {
+        Some(UfcsMatch::FnCall(full)) => format!(" (matched fn call {full:?})"),
+        Some(UfcsMatch::SynthesizedPath(full)) => {
+            format!(" (matched synthesized UFCS path {full:?})")
+        }
+        Some(UfcsMatch::MethodName(name)) => format!(" (matched method name {name:?})"),
+        None => String::new(),
+    }
 }
 
 /// Check if a function matches all specified criteria of a rule.
@@ -314,6 +759,20 @@ fn rule_matches(func: &FunctionInfo, criteria: &MatchCriteria) -> bool {
         }
     }
 
+    // ensures_calls: resolution-aware match, per the selected MatchMode
+    if let Some(matcher) = &criteria.ensures_calls {
+        if !call_matcher_matches(matcher, &func.ensures_calls, &func.ensures_calls_full) {
+            return false;
+        }
+    }
+
+    // requires_calls: resolution-aware match, per the selected MatchMode
+    if let Some(matcher) = &criteria.requires_calls {
+        if !call_matcher_matches(matcher, &func.requires_calls, &func.requires_calls_full) {
+            return false;
+        }
+    }
+
     // ensures_calls_contain: ANY call name contains ANY substring
     if let Some(patterns) = &criteria.ensures_calls_contain {
         if !func
@@ -453,6 +912,46 @@ fn rule_matches(func: &FunctionInfo, criteria: &MatchCriteria) -> bool {
         }
     }
 
+    // ensures_pattern/requires_pattern: structural match against the raw
+    // clause text (see match_clause_pattern)
+    if let Some(pattern_str) = &criteria.ensures_pattern {
+        if match_clause_pattern(pattern_str, func.ensures_text.as_deref()).is_none() {
+            return false;
+        }
+    }
+
+    if let Some(pattern_str) = &criteria.requires_pattern {
+        if match_clause_pattern(pattern_str, func.requires_text.as_deref()).is_none() {
+            return false;
+        }
+    }
+
+    if let Some(patterns) = &criteria.ensures_ufcs_contain {
+        if ufcs_matches(
+            patterns,
+            &func.ensures_fn_calls,
+            &func.ensures_method_calls,
+            &func.ensures_calls_full,
+        )
+        .is_none()
+        {
+            return false;
+        }
+    }
+
+    if let Some(patterns) = &criteria.requires_ufcs_contain {
+        if ufcs_matches(
+            patterns,
+            &func.requires_fn_calls,
+            &func.requires_method_calls,
+            &func.requires_calls_full,
+        )
+        .is_none()
+        {
+            return false;
+        }
+    }
+
     true
 }
 
@@ -464,122 +963,863 @@ fn mode_to_string(mode: &FunctionMode) -> &'static str {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::verus_parser::SpecText;
+// =============================================================================
+// Boolean rule expressions (TaxonomyRule::rule_expr)
+// =============================================================================
+//
+// A small recursive boolean algebra over a function's facts, for labels
+// whose condition doesn't fit the flat `MatchCriteria` field list. Each
+// primitive mirrors an existing `MatchCriteria` check (`mode`, calls,
+// `name_contains`) but composes through `All`/`Any`/`Not` instead of being
+// limited to one fixed AND-of-fields shape.
+
+/// A `*`/`?` shell-style wildcard pattern (`*` matches any run of
+/// characters including none, `?` matches exactly one), used by
+/// [`RuleExpr::CallsMatching`] and [`RuleExpr::NameMatching`]. Deliberately
+/// minimal -- no character classes, no external `glob` crate dependency --
+/// since the only things being matched are call/function names, not
+/// filesystem paths.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Glob(String);
+
+impl Glob {
+    /// Whether `text` matches this pattern in full (not a substring search).
+    pub fn matches(&self, text: &str) -> bool {
+        glob_match(self.0.as_bytes(), text.as_bytes())
+    }
+}
 
-    fn make_func(mode: FunctionMode, ensures_calls: Vec<&str>) -> FunctionInfo {
-        FunctionInfo {
-            name: "test_fn".to_string(),
-            file: Some("src/test.rs".to_string()),
-            spec_text: SpecText {
-                lines_start: 1,
-                lines_end: 10,
-            },
-            mode,
-            kind: None,
-            visibility: None,
-            context: Some("standalone".to_string()),
-            specified: !ensures_calls.is_empty(),
-            has_requires: false,
-            has_ensures: !ensures_calls.is_empty(),
-            has_decreases: false,
-            has_trusted_assumption: false,
-            requires_text: None,
-            ensures_text: None,
-            ensures_calls: ensures_calls.into_iter().map(String::from).collect(),
-            requires_calls: Vec::new(),
-            ensures_calls_full: Vec::new(),
-            requires_calls_full: Vec::new(),
-            ensures_fn_calls: Vec::new(),
-            ensures_method_calls: Vec::new(),
-            requires_fn_calls: Vec::new(),
-            requires_method_calls: Vec::new(),
+/// Recursive `*`/`?` matcher. Small enough patterns that the exponential
+/// worst case of naive backtracking never matters in practice here.
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(b'*') => {
+            glob_match(&pattern[1..], text) || (!text.is_empty() && glob_match(pattern, &text[1..]))
         }
+        Some(b'?') => !text.is_empty() && glob_match(&pattern[1..], &text[1..]),
+        Some(&c) => !text.is_empty() && text[0] == c && glob_match(&pattern[1..], &text[1..]),
     }
+}
 
-    fn make_config(toml_str: &str) -> TaxonomyConfig {
-        toml::from_str(toml_str).expect("Failed to parse test TOML")
+/// A boolean rule expression: small primitives over a function's facts
+/// (`ModeIs`, `CallsMatching`, `NameMatching`, `HasAttr`) combined with
+/// `All`/`Any`/`Not`. Deserializes from TOML/JSON as an externally tagged
+/// enum, e.g. `rule_expr = { all = [{ mode_is = "exec" }, { not = { has_attr
+/// = "verifier::external" } }] }`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleExpr {
+    /// The function's mode (`"exec"`, `"proof"`, or `"spec"`) equals this.
+    ModeIs(String),
+    /// Some ensures or requires call name matches this glob.
+    CallsMatching(Glob),
+    /// The function's own name matches this glob.
+    NameMatching(Glob),
+    /// The function carries this attribute (e.g. `"verifier::external"`).
+    HasAttr(String),
+    /// Like [`RuleExpr::CallsMatching`], but over the *transitive* call
+    /// graph rather than just this function's own direct calls -- catches
+    /// spec-in-exec leakage hidden behind a wrapper function. Needs a
+    /// [`CallGraph`] built over the whole corpus; see
+    /// [`classify_function_with_graph`]. Evaluated as a direct, single-hop
+    /// check (same as `CallsMatching`) when no graph is supplied, e.g. via
+    /// plain [`classify_function`].
+    CallsMatchingTransitive {
+        pattern: Glob,
+        /// Hop bound (`None` = unbounded). `Some(1)` is equivalent to
+        /// `CallsMatching`.
+        #[serde(default)]
+        max_depth: Option<usize>,
+    },
+    /// Every child expression is true. Vacuously true when empty.
+    All(Vec<RuleExpr>),
+    /// At least one child expression is true. Vacuously false when empty.
+    Any(Vec<RuleExpr>),
+    /// The child expression is false.
+    Not(Box<RuleExpr>),
+}
+
+/// Fold a [`RuleExpr`] against a function's facts, following the usual
+/// boolean algebra: empty `All` is `true`, empty `Any` is `false`, `Not`
+/// inverts its child -- the same invariants [`criteria_matches`] applies to
+/// `all_of`/`any_of`/`not`. `graph`, when supplied, backs
+/// `CallsMatchingTransitive`; see [`classify_function_with_graph`].
+fn eval_rule_expr(func: &FunctionInfo, expr: &RuleExpr, graph: Option<&CallGraph>) -> bool {
+    match expr {
+        RuleExpr::ModeIs(mode) => mode_to_string(&func.mode) == mode,
+        RuleExpr::CallsMatching(glob) => func
+            .ensures_calls
+            .iter()
+            .chain(func.requires_calls.iter())
+            .any(|call| glob.matches(call)),
+        RuleExpr::NameMatching(glob) => glob.matches(&func.name),
+        RuleExpr::HasAttr(attr) => func.attrs.iter().any(|a| a == attr),
+        RuleExpr::CallsMatchingTransitive { pattern, max_depth } => match graph {
+            Some(graph) => graph
+                .find_reachable_match(&func.name, pattern, *max_depth)
+                .is_some(),
+            None => func
+                .ensures_calls
+                .iter()
+                .chain(func.requires_calls.iter())
+                .any(|call| pattern.matches(call)),
+        },
+        RuleExpr::All(children) => children.iter().all(|child| eval_rule_expr(func, child, graph)),
+        RuleExpr::Any(children) => children.iter().any(|child| eval_rule_expr(func, child, graph)),
+        RuleExpr::Not(child) => !eval_rule_expr(func, child, graph),
     }
+}
 
-    #[test]
-    fn test_mode_match() {
-        let config = make_config(
-            r#"
-            [taxonomy]
-            version = "1"
-            [[taxonomy.rules]]
-            label = "spec-def"
-            description = "Specification definition"
-            trust = "n/a"
-            [taxonomy.rules.match]
-            mode = ["spec"]
-        "#,
-        );
-        let func = make_func(FunctionMode::Spec, vec![]);
-        assert_eq!(classify_function(&func, &config), vec!["spec-def"]);
+/// A directed call graph over a function corpus, keyed by function name,
+/// with an edge from each function to every name in its own
+/// `ensures_calls`/`requires_calls`. Backs
+/// [`RuleExpr::CallsMatchingTransitive`]: a direct call only sees a
+/// function's own calls, while a transitive rule needs to see through
+/// intermediate helper functions too.
+#[derive(Debug, Clone, Default)]
+pub struct CallGraph {
+    edges: HashMap<String, Vec<String>>,
+}
 
-        let exec_func = make_func(FunctionMode::Exec, vec![]);
-        assert!(classify_function(&exec_func, &config).is_empty());
+impl CallGraph {
+    /// Build a graph from a function corpus. An edge to a name that isn't
+    /// itself one of `functions` is harmless -- it's just a leaf that never
+    /// expands further.
+    pub fn build(functions: &[FunctionInfo]) -> Self {
+        let mut edges: HashMap<String, Vec<String>> = HashMap::new();
+        for func in functions {
+            let callees = edges.entry(func.name.clone()).or_default();
+            callees.extend(func.ensures_calls.iter().cloned());
+            callees.extend(func.requires_calls.iter().cloned());
+        }
+        CallGraph { edges }
     }
 
-    #[test]
-    fn test_ensures_calls_contain() {
-        let config = make_config(
-            r#"
-            [taxonomy]
-            version = "1"
-            [[taxonomy.rules]]
-            label = "data-invariant"
-            description = "Data invariant"
-            trust = "high"
-            [taxonomy.rules.match]
-            ensures_calls_contain = ["is_canonical", "is_valid"]
-        "#,
-        );
-        let func = make_func(
-            FunctionMode::Exec,
-            vec!["is_canonical_scalar52", "scalar52_to_nat"],
-        );
-        assert_eq!(classify_function(&func, &config), vec!["data-invariant"]);
+    /// BFS from `start` (exclusive of `start` itself), bounded by
+    /// `max_depth` hops (`None` = unbounded), for the first node whose name
+    /// matches `pattern`. A visited set guards against cycles/recursion, so
+    /// a match is reported via its *shortest* path. Returns the witnessing
+    /// path from `start` to the match (inclusive of both ends), so a report
+    /// can show *how* the function reaches it through intermediate helpers.
+    pub fn find_reachable_match(
+        &self,
+        start: &str,
+        pattern: &Glob,
+        max_depth: Option<usize>,
+    ) -> Option<Vec<String>> {
+        let mut visited: HashMap<String, Vec<String>> = HashMap::new();
+        visited.insert(start.to_string(), vec![start.to_string()]);
+        let mut frontier = vec![start.to_string()];
+        let mut depth = 0;
+
+        loop {
+            if let Some(bound) = max_depth {
+                if depth >= bound {
+                    return None;
+                }
+            }
 
-        let no_match = make_func(FunctionMode::Exec, vec!["scalar52_to_nat"]);
-        assert!(classify_function(&no_match, &config).is_empty());
+            let mut next_frontier = Vec::new();
+            for node in &frontier {
+                let Some(callees) = self.edges.get(node) else {
+                    continue;
+                };
+                for callee in callees {
+                    if visited.contains_key(callee) {
+                        continue;
+                    }
+                    let mut path = visited[node].clone();
+                    path.push(callee.clone());
+                    if pattern.matches(callee) {
+                        return Some(path);
+                    }
+                    visited.insert(callee.clone(), path.clone());
+                    next_frontier.push(callee.clone());
+                }
+            }
+
+            if next_frontier.is_empty() {
+                return None;
+            }
+            frontier = next_frontier;
+            depth += 1;
+        }
     }
+}
 
-    #[test]
-    fn test_multiple_labels() {
-        let config = make_config(
-            r#"
-            [taxonomy]
-            version = "1"
-            [[taxonomy.rules]]
-            label = "data-invariant"
-            description = "Data invariant"
-            trust = "high"
-            [taxonomy.rules.match]
-            ensures_calls_contain = ["is_canonical"]
-            [[taxonomy.rules]]
-            label = "functional-correctness"
-            description = "Functional correctness"
-            trust = "highest"
-            [taxonomy.rules.match]
-            ensures_calls_contain = ["_to_nat"]
-            mode = ["exec"]
-        "#,
-        );
-        let func = make_func(
-            FunctionMode::Exec,
-            vec!["is_canonical_scalar52", "scalar52_to_nat"],
-        );
-        let labels = classify_function(&func, &config);
-        assert_eq!(labels, vec!["data-invariant", "functional-correctness"]);
+// =============================================================================
+// Composable classifier pipeline
+// =============================================================================
+//
+// `classify_function` is one monolithic pass over a `TaxonomyConfig`. A
+// `ClassifierPipeline` lets callers chain several passes -- including
+// custom ones from their own crates -- where each stage sees the labels
+// every earlier stage already produced, with results merged/deduplicated
+// in first-seen order and an optional short-circuit once a "terminal"
+// label appears.
+
+/// A single classification pass in a [`ClassifierPipeline`]: given a
+/// function and the labels every earlier stage in the pipeline has already
+/// produced, contributes its own labels (possibly none).
+pub trait ClassificationStage {
+    fn classify(&self, func: &FunctionInfo, labels_so_far: &[String]) -> Vec<String>;
+}
+
+/// Adapts the existing rule-based [`classify_function`] into a single
+/// [`ClassificationStage`], so the built-in taxonomy can be composed
+/// alongside custom passes instead of being the only way to classify.
+pub struct TaxonomyStage {
+    config: TaxonomyConfig,
+}
+
+impl TaxonomyStage {
+    pub fn new(config: TaxonomyConfig) -> Self {
+        TaxonomyStage { config }
     }
+}
 
-    #[test]
-    fn test_ensures_calls_empty() {
-        let config = make_config(
+impl ClassificationStage for TaxonomyStage {
+    fn classify(&self, func: &FunctionInfo, _labels_so_far: &[String]) -> Vec<String> {
+        classify_function(func, &self.config)
+    }
+}
+
+/// A sequence of [`ClassificationStage`]s run over a function in order,
+/// merging and deduplicating each stage's labels (first-seen order
+/// preserved) and short-circuiting before any stage once a label in
+/// [`ClassifierPipeline::terminal_labels`] has already been produced.
+#[derive(Default)]
+pub struct ClassifierPipeline {
+    stages: Vec<Box<dyn ClassificationStage>>,
+    terminal_labels: Vec<String>,
+}
+
+impl ClassifierPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a stage to run after every stage already registered.
+    pub fn with_stage(mut self, stage: impl ClassificationStage + 'static) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Register a label that, once produced by any stage, stops the
+    /// pipeline before any later stage runs.
+    pub fn with_terminal_label(mut self, label: impl Into<String>) -> Self {
+        self.terminal_labels.push(label.into());
+        self
+    }
+
+    /// Run every stage in order against `func`, returning the merged,
+    /// deduplicated label set.
+    pub fn run(&self, func: &FunctionInfo) -> Vec<String> {
+        let mut labels: Vec<String> = Vec::new();
+
+        for stage in &self.stages {
+            for label in stage.classify(func, &labels) {
+                if !labels.contains(&label) {
+                    labels.push(label);
+                }
+            }
+            if labels
+                .iter()
+                .any(|label| self.terminal_labels.iter().any(|t| t == label))
+            {
+                break;
+            }
+        }
+
+        labels
+    }
+}
+
+// =============================================================================
+// SSR-style structural patterns (ensures_pattern/requires_pattern)
+// =============================================================================
+//
+// A deliberately small structural-search-and-replace grammar -- literal
+// tokens, `name(args, ...)` calls, and single binary operators -- just rich
+// enough to express shapes like `$result == spec_$f($x)` over an
+// ensures/requires clause's raw text. Not a full Verus spec-expression
+// parser: it only needs to parse as much of a clause as a rule's pattern
+// might name.
+
+/// Structural representation of a parsed ensures/requires clause.
+#[derive(Debug, Clone, PartialEq)]
+enum SpecExpr {
+    /// A bare identifier or literal, e.g. `self`, `42`.
+    Atom(String),
+    /// `name(args, ...)`, e.g. a call or `old(...)`.
+    Call { name: String, args: Vec<SpecExpr> },
+    /// `lhs op rhs`, e.g. `result == spec_f(x)`.
+    BinOp {
+        op: String,
+        lhs: Box<SpecExpr>,
+        rhs: Box<SpecExpr>,
+    },
+}
+
+impl SpecExpr {
+    /// `self` and every sub-node reachable from it, so a pattern can unify
+    /// against any sub-node of the clause, not only its root.
+    fn subexprs(&self) -> Vec<&SpecExpr> {
+        let mut out = vec![self];
+        match self {
+            SpecExpr::Atom(_) => {}
+            SpecExpr::Call { args, .. } => {
+                for arg in args {
+                    out.extend(arg.subexprs());
+                }
+            }
+            SpecExpr::BinOp { lhs, rhs, .. } => {
+                out.extend(lhs.subexprs());
+                out.extend(rhs.subexprs());
+            }
+        }
+        out
+    }
+
+    /// Render back to clause-like text, used to report captured placeholder
+    /// bindings as strings on [`RuleExplanation`].
+    fn render(&self) -> String {
+        match self {
+            SpecExpr::Atom(s) => s.clone(),
+            SpecExpr::Call { name, args } => format!(
+                "{}({})",
+                name,
+                args.iter()
+                    .map(SpecExpr::render)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            SpecExpr::BinOp { op, lhs, rhs } => {
+                format!("{} {} {}", lhs.render(), op, rhs.render())
+            }
+        }
+    }
+}
+
+/// Split an identifier-ish pattern token like `spec_$f` into a literal
+/// prefix/suffix plus an (optional) placeholder name, so a single call name
+/// can be partly literal and partly a capture, e.g. matching `spec_foo`
+/// against `spec_$f` binds `f` to `"foo"`.
+#[derive(Debug, Clone, PartialEq)]
+struct NamePattern {
+    prefix: String,
+    placeholder: Option<String>,
+    suffix: String,
+}
+
+impl NamePattern {
+    fn from_token(token: &str) -> NamePattern {
+        match token.find('$') {
+            None => NamePattern {
+                prefix: token.to_string(),
+                placeholder: None,
+                suffix: String::new(),
+            },
+            Some(idx) => {
+                let prefix = token[..idx].to_string();
+                let rest = &token[idx + 1..];
+                let name_len = rest
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '_')
+                    .count();
+                let placeholder: String = rest.chars().take(name_len).collect();
+                let suffix: String = rest.chars().skip(name_len).collect();
+                NamePattern {
+                    prefix,
+                    placeholder: Some(placeholder),
+                    suffix,
+                }
+            }
+        }
+    }
+
+    /// If `name` matches this prefix/suffix, the substring captured for the
+    /// placeholder (empty if this pattern has none).
+    fn matches<'a>(&self, name: &'a str) -> Option<&'a str> {
+        if self.placeholder.is_none() {
+            return (name == self.prefix).then_some("");
+        }
+        name.strip_prefix(self.prefix.as_str())?
+            .strip_suffix(self.suffix.as_str())
+    }
+}
+
+/// A `$name`-bearing template parsed from an `ensures_pattern`/
+/// `requires_pattern` criterion string.
+#[derive(Debug, Clone, PartialEq)]
+enum SpecPattern {
+    /// A bare `$name` used as a whole operand -- matches any subexpression.
+    Placeholder(String),
+    /// A literal atom that must match exactly.
+    Atom(String),
+    Call {
+        name: NamePattern,
+        args: Vec<SpecPattern>,
+    },
+    BinOp {
+        op: String,
+        lhs: Box<SpecPattern>,
+        rhs: Box<SpecPattern>,
+    },
+}
+
+const PATTERN_OPERATORS: &[&str] = &["==", "!=", "<=", ">=", "&&", "||", "+", "-", "*", "/", "<", ">"];
+
+/// Split clause/pattern text into identifier, punctuation, and operator
+/// tokens. Identifiers may contain `$` so placeholders tokenize as a single
+/// unit.
+fn tokenize(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.trim().chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_alphanumeric() || c == '_' || c == '$' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+            {
+                i += 1;
+            }
+            tokens.push(chars[start..i].iter().collect());
+        } else if matches!(c, '(' | ')' | ',') {
+            tokens.push(c.to_string());
+            i += 1;
+        } else {
+            let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            if PATTERN_OPERATORS.contains(&two.as_str()) {
+                tokens.push(two);
+                i += 2;
+            } else {
+                tokens.push(c.to_string());
+                i += 1;
+            }
+        }
+    }
+    tokens
+}
+
+/// Parse raw clause text (e.g. `"result == spec_f(old(self))"`) into a
+/// [`SpecExpr`] tree. Returns `None` on anything this tiny grammar can't
+/// parse -- treated by callers as "criterion not met", not an error.
+fn parse_spec_expr(text: &str) -> Option<SpecExpr> {
+    let tokens = tokenize(text);
+    let mut pos = 0;
+    let expr = parse_expr_at(&tokens, &mut pos)?;
+    if pos == tokens.len() {
+        Some(expr)
+    } else {
+        None
+    }
+}
+
+fn parse_expr_at(tokens: &[String], pos: &mut usize) -> Option<SpecExpr> {
+    let lhs = parse_primary_expr_at(tokens, pos)?;
+    if let Some(op) = tokens.get(*pos) {
+        if PATTERN_OPERATORS.contains(&op.as_str()) {
+            let op = op.clone();
+            *pos += 1;
+            let rhs = parse_expr_at(tokens, pos)?;
+            return Some(SpecExpr::BinOp {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            });
+        }
+    }
+    Some(lhs)
+}
+
+fn parse_primary_expr_at(tokens: &[String], pos: &mut usize) -> Option<SpecExpr> {
+    let tok = tokens.get(*pos)?.clone();
+    *pos += 1;
+    if tok == "(" {
+        let inner = parse_expr_at(tokens, pos)?;
+        if tokens.get(*pos).map(String::as_str) != Some(")") {
+            return None;
+        }
+        *pos += 1;
+        return Some(inner);
+    }
+    if tokens.get(*pos).map(String::as_str) == Some("(") {
+        *pos += 1;
+        let mut args = Vec::new();
+        if tokens.get(*pos).map(String::as_str) != Some(")") {
+            loop {
+                args.push(parse_expr_at(tokens, pos)?);
+                if tokens.get(*pos).map(String::as_str) == Some(",") {
+                    *pos += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+        if tokens.get(*pos).map(String::as_str) != Some(")") {
+            return None;
+        }
+        *pos += 1;
+        return Some(SpecExpr::Call { name: tok, args });
+    }
+    Some(SpecExpr::Atom(tok))
+}
+
+/// Parse an `ensures_pattern`/`requires_pattern` string (e.g.
+/// `"$result == spec_$f($x)"`) into a [`SpecPattern`] template.
+fn parse_spec_pattern(text: &str) -> Option<SpecPattern> {
+    let tokens = tokenize(text);
+    let mut pos = 0;
+    let pattern = parse_pattern_at(&tokens, &mut pos)?;
+    if pos == tokens.len() {
+        Some(pattern)
+    } else {
+        None
+    }
+}
+
+fn parse_pattern_at(tokens: &[String], pos: &mut usize) -> Option<SpecPattern> {
+    let lhs = parse_primary_pattern_at(tokens, pos)?;
+    if let Some(op) = tokens.get(*pos) {
+        if PATTERN_OPERATORS.contains(&op.as_str()) {
+            let op = op.clone();
+            *pos += 1;
+            let rhs = parse_pattern_at(tokens, pos)?;
+            return Some(SpecPattern::BinOp {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            });
+        }
+    }
+    Some(lhs)
+}
+
+fn parse_primary_pattern_at(tokens: &[String], pos: &mut usize) -> Option<SpecPattern> {
+    let tok = tokens.get(*pos)?.clone();
+    *pos += 1;
+    if tok == "(" {
+        let inner = parse_pattern_at(tokens, pos)?;
+        if tokens.get(*pos).map(String::as_str) != Some(")") {
+            return None;
+        }
+        *pos += 1;
+        return Some(inner);
+    }
+    if tokens.get(*pos).map(String::as_str) == Some("(") {
+        *pos += 1;
+        let mut args = Vec::new();
+        if tokens.get(*pos).map(String::as_str) != Some(")") {
+            loop {
+                args.push(parse_pattern_at(tokens, pos)?);
+                if tokens.get(*pos).map(String::as_str) == Some(",") {
+                    *pos += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+        if tokens.get(*pos).map(String::as_str) != Some(")") {
+            return None;
+        }
+        *pos += 1;
+        return Some(SpecPattern::Call {
+            name: NamePattern::from_token(&tok),
+            args,
+        });
+    }
+    if let Some(name) = tok.strip_prefix('$') {
+        if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Some(SpecPattern::Placeholder(name.to_string()));
+        }
+    }
+    Some(SpecPattern::Atom(tok))
+}
+
+/// Try to unify `pattern` against `expr`, extending `bindings`. A
+/// placeholder matches any subexpression; the same placeholder name used
+/// twice must bind to structurally-equal subexpressions.
+fn unify(pattern: &SpecPattern, expr: &SpecExpr, bindings: &mut HashMap<String, SpecExpr>) -> bool {
+    match pattern {
+        SpecPattern::Placeholder(name) => bind(name, expr.clone(), bindings),
+        SpecPattern::Atom(text) => matches!(expr, SpecExpr::Atom(s) if s == text),
+        SpecPattern::Call { name, args } => {
+            let SpecExpr::Call {
+                name: ename,
+                args: eargs,
+            } = expr
+            else {
+                return false;
+            };
+            if args.len() != eargs.len() {
+                return false;
+            }
+            let Some(captured) = name.matches(ename) else {
+                return false;
+            };
+            if let Some(placeholder) = &name.placeholder {
+                if !placeholder.is_empty()
+                    && !bind(placeholder, SpecExpr::Atom(captured.to_string()), bindings)
+                {
+                    return false;
+                }
+            }
+            args.iter()
+                .zip(eargs.iter())
+                .all(|(p, e)| unify(p, e, bindings))
+        }
+        SpecPattern::BinOp { op, lhs, rhs } => {
+            let SpecExpr::BinOp {
+                op: eop,
+                lhs: elhs,
+                rhs: erhs,
+            } = expr
+            else {
+                return false;
+            };
+            op == eop && unify(lhs, elhs, bindings) && unify(rhs, erhs, bindings)
+        }
+    }
+}
+
+/// Bind `name` to `value`, requiring structural equality with any existing
+/// binding of the same name.
+fn bind(name: &str, value: SpecExpr, bindings: &mut HashMap<String, SpecExpr>) -> bool {
+    match bindings.get(name) {
+        Some(existing) => *existing == value,
+        None => {
+            bindings.insert(name.to_string(), value);
+            true
+        }
+    }
+}
+
+/// Try `pattern` against every sub-node of `root`, returning the bindings
+/// of the first node it unifies with.
+fn match_pattern_anywhere(
+    pattern: &SpecPattern,
+    root: &SpecExpr,
+) -> Option<HashMap<String, SpecExpr>> {
+    root.subexprs().into_iter().find_map(|sub| {
+        let mut bindings = HashMap::new();
+        unify(pattern, sub, &mut bindings).then_some(bindings)
+    })
+}
+
+/// Evaluate a [`CallMatcher`] against a function's call names. `calls` are
+/// unqualified names (e.g. `ensures_calls`), used for `contains`; `calls_full`
+/// are fully-qualified paths (e.g. `ensures_calls_full`), used for
+/// `exact`/`suffix` and as the primary text for `regex`.
+fn call_matcher_matches(matcher: &CallMatcher, calls: &[String], calls_full: &[String]) -> bool {
+    let values = matcher.values();
+    match matcher.mode() {
+        MatchMode::Contains => calls
+            .iter()
+            .any(|call| values.iter().any(|v| call.contains(v.as_str()))),
+        MatchMode::Exact => calls_full
+            .iter()
+            .any(|call| values.iter().any(|v| call == v)),
+        MatchMode::Suffix => calls_full
+            .iter()
+            .any(|call| values.iter().any(|v| call.ends_with(v.as_str()))),
+        MatchMode::Regex => values.iter().any(|v| match regex::Regex::new(v) {
+            Ok(re) => calls_full.iter().chain(calls).any(|call| re.is_match(call)),
+            Err(_) => false,
+        }),
+    }
+}
+
+/// Which form of a clause's calls satisfied an `*_ufcs_contain` criterion,
+/// so [`RuleExplanation`] can report not just that it matched but how.
+#[derive(Debug, Clone, PartialEq)]
+enum UfcsMatch {
+    /// A free-function call's fully-qualified path matched directly.
+    FnCall(String),
+    /// A method call's UFCS form, synthesized from its entry in
+    /// `*_calls_full` (the receiver type's fully-qualified path), matched.
+    SynthesizedPath(String),
+    /// No `*_calls_full` entry was found for the method call (e.g. the
+    /// receiver type couldn't be resolved), but the bare method name
+    /// matched the pattern.
+    MethodName(String),
+}
+
+/// Match a UFCS-style pattern (e.g. `"foo::Bar::baz"`) against the union of
+/// free-function and method calls in a clause. Free calls are tested
+/// against their fully-qualified path in `calls_full`; method calls have
+/// their UFCS form synthesized by finding the `calls_full` entry ending in
+/// `::<method name>` (the receiver type's resolved path), tested against
+/// the pattern alongside the bare method name as a fallback. One-directional:
+/// the pattern is written in UFCS form to match a method call, not vice
+/// versa.
+fn ufcs_matches(
+    patterns: &[String],
+    fn_calls: &[String],
+    method_calls: &[String],
+    calls_full: &[String],
+) -> Option<UfcsMatch> {
+    for full in calls_full {
+        if fn_calls.iter().any(|call| full.ends_with(call.as_str()))
+            && patterns.iter().any(|pat| full.contains(pat.as_str()))
+        {
+            return Some(UfcsMatch::FnCall(full.clone()));
+        }
+    }
+
+    for method in method_calls {
+        let suffix = format!("::{method}");
+        let synthesized = calls_full.iter().find(|full| full.ends_with(suffix.as_str()));
+        if let Some(full) = synthesized {
+            if patterns.iter().any(|pat| full.contains(pat.as_str())) {
+                return Some(UfcsMatch::SynthesizedPath(full.clone()));
+            }
+        }
+        if patterns.iter().any(|pat| method.contains(pat.as_str())) {
+            return Some(UfcsMatch::MethodName(method.clone()));
+        }
+    }
+
+    None
+}
+
+/// Parse `pattern_str` and `clause_text` and try to unify the pattern
+/// against any sub-node of the clause's expression tree. Returns the
+/// captured placeholder bindings rendered back to text, or `None` if either
+/// side fails to parse or nothing unifies -- always "criterion not met",
+/// never an error.
+fn match_clause_pattern(
+    pattern_str: &str,
+    clause_text: Option<&str>,
+) -> Option<HashMap<String, String>> {
+    let clause_text = clause_text?;
+    let pattern = parse_spec_pattern(pattern_str)?;
+    let clause = parse_spec_expr(clause_text)?;
+    match_pattern_anywhere(&pattern, &clause)
+        .map(|bindings| bindings.into_iter().map(|(k, v)| (k, v.render())).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verus_parser::SpecText;
+
+    fn make_func(mode: FunctionMode, ensures_calls: Vec<&str>) -> FunctionInfo {
+        FunctionInfo {
+            name: "test_fn".to_string(),
+            file: Some("src/test.rs".to_string()),
+            spec_text: SpecText {
+                lines_start: 1,
+                lines_end: 10,
+            },
+            mode,
+            kind: None,
+            visibility: None,
+            context: Some("standalone".to_string()),
+            specified: !ensures_calls.is_empty(),
+            has_requires: false,
+            has_ensures: !ensures_calls.is_empty(),
+            has_decreases: false,
+            has_trusted_assumption: false,
+            requires_text: None,
+            ensures_text: None,
+            ensures_calls: ensures_calls.into_iter().map(String::from).collect(),
+            requires_calls: Vec::new(),
+            ensures_calls_full: Vec::new(),
+            requires_calls_full: Vec::new(),
+            ensures_fn_calls: Vec::new(),
+            ensures_method_calls: Vec::new(),
+            requires_fn_calls: Vec::new(),
+            requires_method_calls: Vec::new(),
+            attrs: Vec::new(),
+        }
+    }
+
+    fn make_config(toml_str: &str) -> TaxonomyConfig {
+        toml::from_str(toml_str).expect("Failed to parse test TOML")
+    }
+
+    #[test]
+    fn test_mode_match() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "spec-def"
+            description = "Specification definition"
+            trust = "n/a"
+            [taxonomy.rules.match]
+            mode = ["spec"]
+        "#,
+        );
+        let func = make_func(FunctionMode::Spec, vec![]);
+        assert_eq!(classify_function(&func, &config), vec!["spec-def"]);
+
+        let exec_func = make_func(FunctionMode::Exec, vec![]);
+        assert!(classify_function(&exec_func, &config).is_empty());
+    }
+
+    #[test]
+    fn test_ensures_calls_contain() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "data-invariant"
+            description = "Data invariant"
+            trust = "high"
+            [taxonomy.rules.match]
+            ensures_calls_contain = ["is_canonical", "is_valid"]
+        "#,
+        );
+        let func = make_func(
+            FunctionMode::Exec,
+            vec!["is_canonical_scalar52", "scalar52_to_nat"],
+        );
+        assert_eq!(classify_function(&func, &config), vec!["data-invariant"]);
+
+        let no_match = make_func(FunctionMode::Exec, vec!["scalar52_to_nat"]);
+        assert!(classify_function(&no_match, &config).is_empty());
+    }
+
+    #[test]
+    fn test_multiple_labels() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "data-invariant"
+            description = "Data invariant"
+            trust = "high"
+            [taxonomy.rules.match]
+            ensures_calls_contain = ["is_canonical"]
+            [[taxonomy.rules]]
+            label = "functional-correctness"
+            description = "Functional correctness"
+            trust = "highest"
+            [taxonomy.rules.match]
+            ensures_calls_contain = ["_to_nat"]
+            mode = ["exec"]
+        "#,
+        );
+        let func = make_func(
+            FunctionMode::Exec,
+            vec!["is_canonical_scalar52", "scalar52_to_nat"],
+        );
+        let labels = classify_function(&func, &config);
+        assert_eq!(labels, vec!["data-invariant", "functional-correctness"]);
+    }
+
+    #[test]
+    fn test_ensures_calls_empty() {
+        let config = make_config(
             r#"
             [taxonomy]
             version = "1"
@@ -691,4 +1931,1088 @@ mod tests {
         let func2 = make_func(FunctionMode::Exec, vec!["spec_foo"]);
         assert_eq!(classify_function(&func2, &config), vec!["fc"]);
     }
+
+    fn make_func_with_ensures_text(text: &str) -> FunctionInfo {
+        let mut func = make_func(FunctionMode::Exec, vec![]);
+        func.has_ensures = true;
+        func.ensures_text = Some(text.to_string());
+        func
+    }
+
+    #[test]
+    fn test_ensures_pattern_matches_and_captures_placeholder() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "fc"
+            description = "Functional correctness"
+            trust = "highest"
+            [taxonomy.rules.match]
+            ensures_pattern = "$result == spec_$f($x)"
+        "#,
+        );
+        let func = make_func_with_ensures_text("result == spec_foo(old(self))");
+        let explanations = explain_function(&func, &config);
+        assert!(explanations[0].matched);
+        assert_eq!(explanations[0].pattern_bindings.get("f").unwrap(), "foo");
+        assert_eq!(explanations[0].pattern_bindings.get("result").unwrap(), "result");
+        assert_eq!(
+            explanations[0].pattern_bindings.get("x").unwrap(),
+            "old(self)"
+        );
+
+        let no_match = make_func_with_ensures_text("result == other_thing(self)");
+        assert!(classify_function(&no_match, &config).is_empty());
+    }
+
+    #[test]
+    fn test_ensures_pattern_matches_anywhere_in_the_clause() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "fc"
+            description = "Functional correctness"
+            trust = "highest"
+            [taxonomy.rules.match]
+            ensures_pattern = "spec_$f($x)"
+        "#,
+        );
+        // The pattern only names the right-hand side of the `==`; it
+        // should still match a sub-node of the full clause.
+        let func = make_func_with_ensures_text("result == spec_bar(self)");
+        assert_eq!(classify_function(&func, &config), vec!["fc"]);
+    }
+
+    #[test]
+    fn test_ensures_pattern_same_placeholder_requires_structural_equality() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "symmetric"
+            description = "Symmetric relation"
+            trust = "high"
+            [taxonomy.rules.match]
+            ensures_pattern = "$a == $a"
+        "#,
+        );
+        let matching = make_func_with_ensures_text("foo(self) == foo(self)");
+        assert_eq!(classify_function(&matching, &config), vec!["symmetric"]);
+
+        let mismatching = make_func_with_ensures_text("foo(self) == bar(self)");
+        assert!(classify_function(&mismatching, &config).is_empty());
+    }
+
+    #[test]
+    fn test_ensures_pattern_absent_clause_does_not_match() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "fc"
+            description = "Functional correctness"
+            trust = "highest"
+            [taxonomy.rules.match]
+            ensures_pattern = "$result == spec_$f($x)"
+        "#,
+        );
+        let func = make_func(FunctionMode::Exec, vec![]);
+        assert!(classify_function(&func, &config).is_empty());
+    }
+
+    #[test]
+    fn parse_spec_expr_parses_a_comparison_call() {
+        let expr = parse_spec_expr("result == spec_foo(old(self))").unwrap();
+        assert_eq!(
+            expr,
+            SpecExpr::BinOp {
+                op: "==".to_string(),
+                lhs: Box::new(SpecExpr::Atom("result".to_string())),
+                rhs: Box::new(SpecExpr::Call {
+                    name: "spec_foo".to_string(),
+                    args: vec![SpecExpr::Call {
+                        name: "old".to_string(),
+                        args: vec![SpecExpr::Atom("self".to_string())],
+                    }],
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn name_pattern_splits_a_partial_placeholder_token() {
+        let pattern = NamePattern::from_token("spec_$f");
+        assert_eq!(pattern.matches("spec_foo"), Some("foo"));
+        assert_eq!(pattern.matches("other"), None);
+    }
+
+    fn make_func_with_full_calls(ensures_calls_full: Vec<&str>) -> FunctionInfo {
+        let mut func = make_func(FunctionMode::Exec, vec![]);
+        func.ensures_calls_full = ensures_calls_full.into_iter().map(String::from).collect();
+        func
+    }
+
+    #[test]
+    fn test_ensures_calls_exact_does_not_match_lookalikes() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "canonical"
+            description = "Canonical check"
+            trust = "high"
+            [taxonomy.rules.match]
+            ensures_calls = { match = "exact", values = ["crate::scalar::is_canonical"] }
+        "#,
+        );
+        let exact = make_func_with_full_calls(vec!["crate::scalar::is_canonical"]);
+        assert_eq!(classify_function(&exact, &config), vec!["canonical"]);
+
+        // Substring lookalike in a different module -- must NOT match with exact.
+        let lookalike = make_func_with_full_calls(vec!["crate::other::is_canonical_subset"]);
+        assert!(classify_function(&lookalike, &config).is_empty());
+
+        let different_module = make_func_with_full_calls(vec!["crate::other::is_canonical"]);
+        assert!(classify_function(&different_module, &config).is_empty());
+    }
+
+    #[test]
+    fn test_ensures_calls_suffix_matches_the_qualified_path() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "canonical"
+            description = "Canonical check"
+            trust = "high"
+            [taxonomy.rules.match]
+            ensures_calls = { match = "suffix", values = ["::is_canonical"] }
+        "#,
+        );
+        let func = make_func_with_full_calls(vec!["crate::scalar::is_canonical"]);
+        assert_eq!(classify_function(&func, &config), vec!["canonical"]);
+
+        // "is_invalid" shares no suffix with "::is_canonical".
+        let unrelated = make_func_with_full_calls(vec!["crate::scalar::is_invalid"]);
+        assert!(classify_function(&unrelated, &config).is_empty());
+    }
+
+    #[test]
+    fn test_ensures_calls_plain_list_defaults_to_contains() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "data-invariant"
+            description = "Data invariant"
+            trust = "high"
+            [taxonomy.rules.match]
+            ensures_calls = ["is_canonical"]
+        "#,
+        );
+        let func = make_func(FunctionMode::Exec, vec!["is_canonical_scalar52"]);
+        assert_eq!(classify_function(&func, &config), vec!["data-invariant"]);
+    }
+
+    #[test]
+    fn test_ensures_calls_regex_mode() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "canonical"
+            description = "Canonical check"
+            trust = "high"
+            [taxonomy.rules.match]
+            ensures_calls = { match = "regex", values = ["^crate::scalar::is_\\w+$"] }
+        "#,
+        );
+        let func = make_func_with_full_calls(vec!["crate::scalar::is_canonical"]);
+        assert_eq!(classify_function(&func, &config), vec!["canonical"]);
+
+        let no_match = make_func_with_full_calls(vec!["crate::scalar::to_bytes"]);
+        assert!(classify_function(&no_match, &config).is_empty());
+    }
+
+    #[test]
+    fn test_explain_reports_the_match_mode_used() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "canonical"
+            description = "Canonical check"
+            trust = "high"
+            [taxonomy.rules.match]
+            ensures_calls = { match = "exact", values = ["crate::scalar::is_canonical"] }
+        "#,
+        );
+        let func = make_func_with_full_calls(vec!["crate::scalar::is_canonical"]);
+        let explanations = explain_function(&func, &config);
+        let criterion = &explanations[0].criteria_results[0];
+        assert!(criterion.0.contains("match=Exact"));
+        assert!(criterion.1);
+    }
+
+    fn make_func_with_method_call(method_call: &str, full_path: Option<&str>) -> FunctionInfo {
+        let mut func = make_func(FunctionMode::Exec, vec![]);
+        func.ensures_method_calls = vec![method_call.to_string()];
+        func.ensures_calls_full = full_path.map(String::from).into_iter().collect();
+        func
+    }
+
+    #[test]
+    fn test_ufcs_pattern_matches_a_method_call_via_synthesized_path() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "baz"
+            description = "Calls baz"
+            trust = "high"
+            [taxonomy.rules.match]
+            ensures_ufcs_contain = ["foo::Bar::baz"]
+        "#,
+        );
+        // s.baz(a) resolved to foo::Bar::baz -- matches the UFCS pattern
+        // even though the clause only spells it as a method call.
+        let func = make_func_with_method_call("baz", Some("foo::Bar::baz"));
+        assert_eq!(classify_function(&func, &config), vec!["baz"]);
+
+        // Different receiver type -- should not match.
+        let other = make_func_with_method_call("baz", Some("foo::Quux::baz"));
+        assert!(classify_function(&other, &config).is_empty());
+    }
+
+    #[test]
+    fn test_ufcs_pattern_falls_back_to_the_bare_method_name() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "baz"
+            description = "Calls baz"
+            trust = "high"
+            [taxonomy.rules.match]
+            ensures_ufcs_contain = ["baz"]
+        "#,
+        );
+        // No resolved full path for this method call; the bare name still matches.
+        let func = make_func_with_method_call("baz", None);
+        assert_eq!(classify_function(&func, &config), vec!["baz"]);
+    }
+
+    #[test]
+    fn test_ufcs_pattern_is_one_directional() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "baz"
+            description = "Calls baz"
+            trust = "high"
+            [taxonomy.rules.match]
+            ensures_ufcs_contain = ["foo::Bar::baz"]
+        "#,
+        );
+        // A free-function call spelled the other way around (not UFCS at
+        // all) must not satisfy a UFCS pattern naming a specific method.
+        let mut func = make_func(FunctionMode::Exec, vec![]);
+        func.ensures_fn_calls = vec!["unrelated_helper".to_string()];
+        func.ensures_calls_full = vec!["crate::unrelated_helper".to_string()];
+        assert!(classify_function(&func, &config).is_empty());
+    }
+
+    #[test]
+    fn test_explain_reports_which_ufcs_form_matched() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "baz"
+            description = "Calls baz"
+            trust = "high"
+            [taxonomy.rules.match]
+            ensures_ufcs_contain = ["foo::Bar::baz"]
+        "#,
+        );
+        let func = make_func_with_method_call("baz", Some("foo::Bar::baz"));
+        let explanations = explain_function(&func, &config);
+        let criterion = &explanations[0].criteria_results[0];
+        assert!(criterion.0.contains("synthesized UFCS path"));
+        assert!(criterion.1);
+    }
+
+    #[test]
+    fn test_not_inverts_its_child() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "public-spec"
+            description = "Spec function not marked internal"
+            trust = "high"
+            [taxonomy.rules.match]
+            mode = ["spec"]
+            [taxonomy.rules.match.not]
+            name_contains = ["internal"]
+        "#,
+        );
+        let public = make_func(FunctionMode::Spec, vec![]);
+        assert_eq!(classify_function(&public, &config), vec!["public-spec"]);
+
+        let mut internal = make_func(FunctionMode::Spec, vec![]);
+        internal.name = "internal_helper".to_string();
+        assert!(classify_function(&internal, &config).is_empty());
+    }
+
+    #[test]
+    fn test_any_of_passes_when_one_child_passes() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "terminates"
+            description = "Has a decreases clause, or is non-recursive"
+            trust = "high"
+            [[taxonomy.rules.match.any_of]]
+            has_decreases = true
+            [[taxonomy.rules.match.any_of]]
+            name_contains = ["leaf"]
+        "#,
+        );
+        let mut decreases = make_func(FunctionMode::Proof, vec![]);
+        decreases.has_decreases = true;
+        assert_eq!(classify_function(&decreases, &config), vec!["terminates"]);
+
+        let mut leaf = make_func(FunctionMode::Proof, vec![]);
+        leaf.name = "leaf_case".to_string();
+        assert_eq!(classify_function(&leaf, &config), vec!["terminates"]);
+
+        let neither = make_func(FunctionMode::Proof, vec![]);
+        assert!(classify_function(&neither, &config).is_empty());
+    }
+
+    #[test]
+    fn test_all_of_requires_every_child() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "strict"
+            description = "Exec and has ensures"
+            trust = "high"
+            [[taxonomy.rules.match.all_of]]
+            mode = ["exec"]
+            [[taxonomy.rules.match.all_of]]
+            has_ensures = true
+        "#,
+        );
+        let mut both = make_func(FunctionMode::Exec, vec![]);
+        both.has_ensures = true;
+        assert_eq!(classify_function(&both, &config), vec!["strict"]);
+
+        let only_mode = make_func(FunctionMode::Exec, vec![]);
+        assert!(classify_function(&only_mode, &config).is_empty());
+    }
+
+    #[test]
+    fn test_nested_combinator_explanation_attributes_failure_to_its_child() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "public-spec"
+            description = "Spec function not marked internal"
+            trust = "high"
+            [taxonomy.rules.match]
+            mode = ["spec"]
+            [taxonomy.rules.match.not]
+            name_contains = ["internal"]
+        "#,
+        );
+        let mut internal = make_func(FunctionMode::Spec, vec![]);
+        internal.name = "internal_helper".to_string();
+        let explanations = explain_function(&internal, &config);
+        assert!(!explanations[0].matched);
+        let not_child = explanations[0].criteria.not.as_ref().unwrap();
+        // The `not` child itself matched (name_contains passed) -- that's
+        // exactly why the overall rule failed.
+        assert!(not_child.matched);
+        assert!(not_child
+            .leaf_results
+            .iter()
+            .any(|(name, passed)| name.contains("name_contains") && *passed));
+    }
+
+    #[test]
+    fn test_additive_mode_emits_every_matching_label_by_default() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "memory-safety"
+            description = "Memory safety"
+            trust = "high"
+            group = "correctness"
+            priority = 1
+            [taxonomy.rules.match]
+            ensures_calls_contain = ["spec_"]
+            [[taxonomy.rules]]
+            label = "functional-correctness"
+            description = "Functional correctness"
+            trust = "highest"
+            group = "correctness"
+            priority = 2
+            [taxonomy.rules.match]
+            ensures_calls_contain = ["spec_"]
+        "#,
+        );
+        let func = make_func(FunctionMode::Exec, vec!["spec_foo"]);
+        assert_eq!(
+            classify_function(&func, &config),
+            vec!["memory-safety", "functional-correctness"]
+        );
+    }
+
+    #[test]
+    fn test_winner_take_all_keeps_only_the_highest_priority_label_in_a_group() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            classification_mode = "winner_take_all"
+            [[taxonomy.rules]]
+            label = "memory-safety"
+            description = "Memory safety"
+            trust = "high"
+            group = "correctness"
+            priority = 1
+            [taxonomy.rules.match]
+            ensures_calls_contain = ["spec_"]
+            [[taxonomy.rules]]
+            label = "functional-correctness"
+            description = "Functional correctness"
+            trust = "highest"
+            group = "correctness"
+            priority = 2
+            [taxonomy.rules.match]
+            ensures_calls_contain = ["spec_"]
+        "#,
+        );
+        let func = make_func(FunctionMode::Exec, vec!["spec_foo"]);
+        assert_eq!(
+            classify_function(&func, &config),
+            vec!["functional-correctness"]
+        );
+
+        let explanations = explain_function(&func, &config);
+        assert!(explanations[0].matched);
+        assert_eq!(
+            explanations[0].suppressed_by.as_deref(),
+            Some("functional-correctness")
+        );
+        assert!(explanations[1].matched);
+        assert_eq!(explanations[1].suppressed_by, None);
+    }
+
+    #[test]
+    fn test_winner_take_all_ties_break_by_declaration_order() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            classification_mode = "winner_take_all"
+            [[taxonomy.rules]]
+            label = "first"
+            description = "First declared"
+            trust = "high"
+            group = "g"
+            priority = 5
+            [taxonomy.rules.match]
+            has_ensures = true
+            [[taxonomy.rules]]
+            label = "second"
+            description = "Second declared"
+            trust = "high"
+            group = "g"
+            priority = 5
+            [taxonomy.rules.match]
+            has_ensures = true
+        "#,
+        );
+        let mut func = make_func(FunctionMode::Exec, vec![]);
+        func.has_ensures = true;
+        assert_eq!(classify_function(&func, &config), vec!["first"]);
+    }
+
+    #[test]
+    fn test_winner_take_all_ungrouped_rules_stay_additive() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            classification_mode = "winner_take_all"
+            [[taxonomy.rules]]
+            label = "a"
+            description = "A"
+            trust = "high"
+            [taxonomy.rules.match]
+            has_ensures = true
+            [[taxonomy.rules]]
+            label = "b"
+            description = "B"
+            trust = "high"
+            [taxonomy.rules.match]
+            has_ensures = true
+        "#,
+        );
+        let mut func = make_func(FunctionMode::Exec, vec![]);
+        func.has_ensures = true;
+        assert_eq!(classify_function(&func, &config), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_rule_expr_mode_is_and_calls_matching() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "fc"
+            description = "Functional correctness"
+            trust = "high"
+            [taxonomy.rules.match]
+            [taxonomy.rules.rule_expr]
+            all = [
+                { mode_is = "exec" },
+                { calls_matching = "spec_*" },
+            ]
+        "#,
+        );
+        let func = make_func(FunctionMode::Exec, vec!["spec_valid"]);
+        assert_eq!(classify_function(&func, &config), vec!["fc"]);
+
+        let wrong_mode = make_func(FunctionMode::Spec, vec!["spec_valid"]);
+        assert!(classify_function(&wrong_mode, &config).is_empty());
+
+        let no_spec_call = make_func(FunctionMode::Exec, vec!["to_bytes"]);
+        assert!(classify_function(&no_spec_call, &config).is_empty());
+    }
+
+    #[test]
+    fn test_rule_expr_not_has_attr() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "fc"
+            description = "Functional correctness"
+            trust = "high"
+            [taxonomy.rules.match]
+            [taxonomy.rules.rule_expr]
+            all = [
+                { mode_is = "exec" },
+                { not = { has_attr = "verifier::external" } },
+            ]
+        "#,
+        );
+        let mut func = make_func(FunctionMode::Exec, vec![]);
+        assert_eq!(classify_function(&func, &config), vec!["fc"]);
+
+        func.attrs.push("verifier::external".to_string());
+        assert!(classify_function(&func, &config).is_empty());
+    }
+
+    #[test]
+    fn test_rule_expr_any_is_vacuously_false_when_empty() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "fc"
+            description = "Functional correctness"
+            trust = "high"
+            [taxonomy.rules.match]
+            [taxonomy.rules.rule_expr]
+            any = []
+        "#,
+        );
+        let func = make_func(FunctionMode::Exec, vec![]);
+        assert!(classify_function(&func, &config).is_empty());
+    }
+
+    #[test]
+    fn test_rule_expr_name_matching_glob() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "test-fn"
+            description = "Test function"
+            trust = "n/a"
+            [taxonomy.rules.match]
+            [taxonomy.rules.rule_expr]
+            name_matching = "test_*"
+        "#,
+        );
+        let func = make_func(FunctionMode::Exec, vec![]);
+        assert_eq!(classify_function(&func, &config), vec!["test-fn"]);
+    }
+
+    #[test]
+    fn test_rule_expr_combines_with_match_criteria() {
+        // A rule's match_criteria and rule_expr are ANDed together: both
+        // must agree for the rule to match.
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "fc"
+            description = "Functional correctness"
+            trust = "high"
+            [taxonomy.rules.match]
+            has_ensures = true
+            [taxonomy.rules.rule_expr]
+            mode_is = "exec"
+        "#,
+        );
+        let mut func = make_func(FunctionMode::Exec, vec![]);
+        func.has_ensures = true;
+        assert_eq!(classify_function(&func, &config), vec!["fc"]);
+
+        func.has_ensures = false;
+        assert!(classify_function(&func, &config).is_empty());
+    }
+
+    #[test]
+    fn glob_matches_wildcards_and_single_characters() {
+        let glob = Glob("spec_*".to_string());
+        assert!(glob.matches("spec_foo"));
+        assert!(glob.matches("spec_"));
+        assert!(!glob.matches("not_spec_foo"));
+
+        let glob = Glob("is_?alid".to_string());
+        assert!(glob.matches("is_valid"));
+        assert!(!glob.matches("is_valiid"));
+    }
+
+    fn make_corpus_func(name: &str, calls: Vec<&str>) -> FunctionInfo {
+        let mut func = make_func(FunctionMode::Exec, vec![]);
+        func.name = name.to_string();
+        func.ensures_calls = calls.into_iter().map(String::from).collect();
+        func
+    }
+
+    #[test]
+    fn call_graph_finds_a_transitively_reachable_match() {
+        // outer -> helper -> spec_inner; no direct call to anything spec_*.
+        let corpus = vec![
+            make_corpus_func("outer", vec!["helper"]),
+            make_corpus_func("helper", vec!["spec_inner"]),
+            make_corpus_func("spec_inner", vec![]),
+        ];
+        let graph = CallGraph::build(&corpus);
+        let path = graph
+            .find_reachable_match("outer", &Glob("spec_*".to_string()), None)
+            .unwrap();
+        assert_eq!(path, vec!["outer", "helper", "spec_inner"]);
+    }
+
+    #[test]
+    fn call_graph_respects_the_depth_bound() {
+        let corpus = vec![
+            make_corpus_func("outer", vec!["helper"]),
+            make_corpus_func("helper", vec!["spec_inner"]),
+            make_corpus_func("spec_inner", vec![]),
+        ];
+        let graph = CallGraph::build(&corpus);
+        assert!(graph
+            .find_reachable_match("outer", &Glob("spec_*".to_string()), Some(1))
+            .is_none());
+        assert!(graph
+            .find_reachable_match("outer", &Glob("spec_*".to_string()), Some(2))
+            .is_some());
+    }
+
+    #[test]
+    fn call_graph_handles_cycles_without_looping_forever() {
+        let corpus = vec![
+            make_corpus_func("a", vec!["b"]),
+            make_corpus_func("b", vec!["a"]),
+        ];
+        let graph = CallGraph::build(&corpus);
+        assert!(graph
+            .find_reachable_match("a", &Glob("spec_*".to_string()), None)
+            .is_none());
+    }
+
+    #[test]
+    fn rule_expr_calls_matching_transitive_classifies_through_a_wrapper() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "fc-leak"
+            description = "Spec call reachable through a wrapper"
+            trust = "high"
+            [taxonomy.rules.match]
+            [taxonomy.rules.rule_expr]
+            calls_matching_transitive = { pattern = "spec_*" }
+        "#,
+        );
+        let corpus = vec![
+            make_corpus_func("outer", vec!["helper"]),
+            make_corpus_func("helper", vec!["spec_inner"]),
+        ];
+        let graph = CallGraph::build(&corpus);
+        let labels = classify_function_with_graph(&corpus[0], &config, &graph);
+        assert_eq!(labels, vec!["fc-leak"]);
+
+        // Without a graph, the same rule only sees direct calls -- "outer"
+        // doesn't call anything spec_* directly.
+        assert!(classify_function(&corpus[0], &config).is_empty());
+    }
+
+    struct NameClassifierStage;
+
+    impl ClassificationStage for NameClassifierStage {
+        fn classify(&self, func: &FunctionInfo, _labels_so_far: &[String]) -> Vec<String> {
+            if func.name.starts_with("spec_") {
+                vec!["name-spec".to_string()]
+            } else {
+                vec![]
+            }
+        }
+    }
+
+    struct EscalateIfFlaggedStage;
+
+    impl ClassificationStage for EscalateIfFlaggedStage {
+        fn classify(&self, _func: &FunctionInfo, labels_so_far: &[String]) -> Vec<String> {
+            if labels_so_far.iter().any(|l| l == "name-spec") {
+                vec!["escalated".to_string()]
+            } else {
+                vec![]
+            }
+        }
+    }
+
+    #[test]
+    fn pipeline_runs_stages_in_order_and_merges_labels() {
+        let config = make_config(
+            r#"
+            [taxonomy]
+            version = "1"
+            [[taxonomy.rules]]
+            label = "spec-def"
+            description = "Specification definition"
+            trust = "n/a"
+            [taxonomy.rules.match]
+            mode = ["spec"]
+        "#,
+        );
+        let pipeline = ClassifierPipeline::new()
+            .with_stage(TaxonomyStage::new(config))
+            .with_stage(NameClassifierStage)
+            .with_stage(EscalateIfFlaggedStage);
+
+        let mut func = make_func(FunctionMode::Spec, vec![]);
+        func.name = "spec_foo".to_string();
+        assert_eq!(
+            pipeline.run(&func),
+            vec!["spec-def", "name-spec", "escalated"]
+        );
+    }
+
+    #[test]
+    fn pipeline_deduplicates_labels_across_stages() {
+        let pipeline = ClassifierPipeline::new()
+            .with_stage(NameClassifierStage)
+            .with_stage(NameClassifierStage);
+
+        let mut func = make_func(FunctionMode::Exec, vec![]);
+        func.name = "spec_foo".to_string();
+        assert_eq!(pipeline.run(&func), vec!["name-spec"]);
+    }
+
+    #[test]
+    fn pipeline_short_circuits_once_a_terminal_label_is_produced() {
+        let pipeline = ClassifierPipeline::new()
+            .with_stage(NameClassifierStage)
+            .with_terminal_label("name-spec")
+            .with_stage(EscalateIfFlaggedStage);
+
+        let mut func = make_func(FunctionMode::Exec, vec![]);
+        func.name = "spec_foo".to_string();
+        // EscalateIfFlaggedStage never runs, so "escalated" is absent even
+        // though its condition would otherwise be satisfied.
+        assert_eq!(pipeline.run(&func), vec!["name-spec"]);
+    }
+
+    // =========================================================================
+    // Golden-file corpus harness
+    // =========================================================================
+    //
+    // `make_func`-style inline tests don't scale past a handful of cases, so
+    // this walks `tests/fixtures/taxonomy/` for self-contained `*.toml`
+    // fixtures -- each pairing a `[taxonomy]` rule set with a small
+    // `[[function]]` corpus -- classifies every function, and compares the
+    // deterministic `"<name>: <labels>"` output against a checked-in
+    // `.expected` file, the same golden-file shape `expect.rs`'s `//~`
+    // annotations and `OutputConflictHandling::Bless` use for verification
+    // output. A fixture's functions are a stand-in for a SCIP-derived
+    // `FunctionInfo` list, not a real `index.scip` -- this module isn't
+    // wired to SCIP parsing, so fixtures describe the same facts
+    // `MatchCriteria`/`RuleExpr` inspect directly.
+
+    /// One `*.toml` fixture: a taxonomy rule set plus the function corpus to
+    /// classify it against.
+    #[derive(Debug, Deserialize)]
+    struct GoldenFixture {
+        taxonomy: TaxonomyRoot,
+        #[serde(rename = "function", default)]
+        functions: Vec<GoldenFunction>,
+    }
+
+    /// A fixture's minimal stand-in for [`FunctionInfo`] -- just the facts a
+    /// rule can actually condition on.
+    #[derive(Debug, Deserialize)]
+    struct GoldenFunction {
+        name: String,
+        mode: String,
+        #[serde(default)]
+        ensures_calls: Vec<String>,
+        #[serde(default)]
+        requires_calls: Vec<String>,
+        #[serde(default)]
+        attrs: Vec<String>,
+    }
+
+    fn golden_function_to_function_info(f: &GoldenFunction) -> FunctionInfo {
+        let mode = match f.mode.as_str() {
+            "spec" => FunctionMode::Spec,
+            "proof" => FunctionMode::Proof,
+            _ => FunctionMode::Exec,
+        };
+        FunctionInfo {
+            name: f.name.clone(),
+            file: None,
+            spec_text: SpecText {
+                lines_start: 0,
+                lines_end: 0,
+            },
+            mode,
+            kind: None,
+            visibility: None,
+            context: None,
+            specified: !f.ensures_calls.is_empty(),
+            has_requires: !f.requires_calls.is_empty(),
+            has_ensures: !f.ensures_calls.is_empty(),
+            has_decreases: false,
+            has_trusted_assumption: false,
+            requires_text: None,
+            ensures_text: None,
+            ensures_calls: f.ensures_calls.clone(),
+            requires_calls: f.requires_calls.clone(),
+            ensures_calls_full: Vec::new(),
+            requires_calls_full: Vec::new(),
+            ensures_fn_calls: Vec::new(),
+            ensures_method_calls: Vec::new(),
+            requires_fn_calls: Vec::new(),
+            requires_method_calls: Vec::new(),
+            attrs: f.attrs.clone(),
+        }
+    }
+
+    /// Classify every function in `fixture`, rendering one deterministic
+    /// `"<name>: <labels>"` line per function (sorted by name so output
+    /// doesn't depend on declaration order).
+    fn render_golden_output(fixture: GoldenFixture) -> String {
+        let config = TaxonomyConfig {
+            taxonomy: fixture.taxonomy,
+        };
+        let mut lines: Vec<String> = fixture
+            .functions
+            .iter()
+            .map(|f| {
+                let info = golden_function_to_function_info(f);
+                let labels = classify_function(&info, &config);
+                format!("{}: {}", f.name, labels.join(","))
+            })
+            .collect();
+        lines.sort();
+        lines.join("\n") + "\n"
+    }
+
+    /// One line of a rendered line-diff: unchanged, or only on one side.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum DiffOp<'a> {
+        Equal(&'a str),
+        Removed(&'a str),
+        Added(&'a str),
+    }
+
+    /// Longest-common-subsequence line diff between `old` and `new`.
+    /// Quadratic in the number of lines, which is fine for the small golden
+    /// files this harness compares.
+    fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffOp<'a>> {
+        let (n, m) = (old.len(), new.len());
+        let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old[i] == new[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut ops = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if old[i] == new[j] {
+                ops.push(DiffOp::Equal(old[i]));
+                i += 1;
+                j += 1;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                ops.push(DiffOp::Removed(old[i]));
+                i += 1;
+            } else {
+                ops.push(DiffOp::Added(new[j]));
+                j += 1;
+            }
+        }
+        while i < n {
+            ops.push(DiffOp::Removed(old[i]));
+            i += 1;
+        }
+        while j < m {
+            ops.push(DiffOp::Added(new[j]));
+            j += 1;
+        }
+        ops
+    }
+
+    fn push_hunk(ops: &[DiffOp], start: usize, end: usize, out: &mut String) {
+        for op in &ops[start..end] {
+            match op {
+                DiffOp::Equal(l) => out.push_str(&format!("  {l}\n")),
+                DiffOp::Removed(l) => out.push_str(&format!("- {l}\n")),
+                DiffOp::Added(l) => out.push_str(&format!("+ {l}\n")),
+            }
+        }
+    }
+
+    /// Render `ops` unified-diff style, keeping `context` lines of unchanged
+    /// text around each run of changes; separate runs further apart than
+    /// `2 * context` are shown as separate hunks split by a `---` marker.
+    fn render_unified_diff(ops: &[DiffOp], context: usize) -> String {
+        let changed: Vec<usize> = ops
+            .iter()
+            .enumerate()
+            .filter(|(_, op)| !matches!(op, DiffOp::Equal(_)))
+            .map(|(i, _)| i)
+            .collect();
+        let Some(&first) = changed.first() else {
+            return String::new();
+        };
+
+        let mut out = String::new();
+        let mut hunk_start = first.saturating_sub(context);
+        let mut hunk_end = (first + context + 1).min(ops.len());
+
+        for &idx in &changed[1..] {
+            let next_start = idx.saturating_sub(context);
+            if next_start <= hunk_end {
+                hunk_end = (idx + context + 1).min(ops.len());
+            } else {
+                push_hunk(ops, hunk_start, hunk_end, &mut out);
+                out.push_str("---\n");
+                hunk_start = next_start;
+                hunk_end = (idx + context + 1).min(ops.len());
+            }
+        }
+        push_hunk(ops, hunk_start, hunk_end, &mut out);
+        out
+    }
+
+    /// Classify `path`'s fixture and compare against its `.expected` file.
+    /// Under `UPDATE_EXPECT=1`, the `.expected` file is (re)written to match
+    /// the current output instead of being compared against.
+    fn check_or_bless_golden_fixture(path: &Path) -> Result<(), String> {
+        let toml_str =
+            std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let fixture: GoldenFixture =
+            toml::from_str(&toml_str).map_err(|e| format!("{}: {e}", path.display()))?;
+        let actual = render_golden_output(fixture);
+
+        let expected_path = path.with_extension("expected");
+        if std::env::var_os("UPDATE_EXPECT").is_some() {
+            std::fs::write(&expected_path, &actual)
+                .map_err(|e| format!("{}: {e}", expected_path.display()))?;
+            return Ok(());
+        }
+
+        let expected = std::fs::read_to_string(&expected_path).unwrap_or_default();
+        if expected == actual {
+            return Ok(());
+        }
+
+        let old_lines: Vec<&str> = expected.lines().collect();
+        let new_lines: Vec<&str> = actual.lines().collect();
+        let ops = diff_lines(&old_lines, &new_lines);
+        Err(format!(
+            "golden mismatch for {}:\n{}(re-run with UPDATE_EXPECT=1 to accept)",
+            path.display(),
+            render_unified_diff(&ops, 3)
+        ))
+    }
+
+    #[test]
+    fn golden_corpus_matches_expected_output() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/taxonomy");
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            // No fixture corpus checked in -- nothing to regress against.
+            return;
+        };
+
+        let mut failures = Vec::new();
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("toml") {
+                continue;
+            }
+            if let Err(e) = check_or_bless_golden_fixture(&path) {
+                failures.push(e);
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+    }
 }
@@ -0,0 +1,263 @@
+//! AST-based type string normalization.
+//!
+//! The old `clean_type_string`/`clean_type_string_preserve_ref`/
+//! `extract_self_type` trio normalized types by `trim_start_matches`-ing a
+//! hard-coded set of leading lifetime tokens (`'a `, `'b `, `'_ `, `mut `).
+//! That silently does nothing for `&'static mut T`, nested generics like
+//! `Vec<&'a Scalar>`, qualified paths (`<T as Trait>::Output`), tuples,
+//! arrays, and fn pointers. [`normalize_type`] instead parses the string
+//! into a `verus_syn::Type` and walks the tree, stripping every lifetime
+//! wherever it appears and recursing into generic argument lists -- the
+//! same approach rust-analyzer's `hir-ty` type display uses -- so
+//! `Mul<&'a Scalar>` and `Mul<&Scalar>` collapse identically while
+//! `From<&T>` stays distinct from `From<T>`.
+
+use verus_syn::{Expr, GenericArgument, Lit, Path, PathArguments, Type};
+
+/// A type string normalized by parsing it into a `verus_syn::Type` and
+/// walking the tree, rather than pattern-matching the raw text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedType {
+    /// Whether the type itself (not a nested generic argument) is a
+    /// reference, e.g. `&MontgomeryPoint` -- tracked separately so callers
+    /// that only care about owned-vs-reference at the top level (like
+    /// `extract_self_type`) don't have to re-parse `rendered`.
+    pub is_top_level_ref: bool,
+    /// The type with every lifetime stripped and whitespace normalized.
+    /// References are preserved at every depth -- including nested ones,
+    /// e.g. `Mul<&Scalar>` -- since those distinguish one impl from
+    /// another; only the top-level one is split out into
+    /// `is_top_level_ref`.
+    pub rendered: String,
+}
+
+impl NormalizedType {
+    /// `rendered`, with a leading `&` re-added if the type's top level was
+    /// a reference -- the "preserve-ref" form `extract_self_type` and
+    /// `extract_type_from_param` need.
+    pub fn rendered_with_ref(&self) -> String {
+        if self.is_top_level_ref {
+            format!("&{}", self.rendered)
+        } else {
+            self.rendered.clone()
+        }
+    }
+}
+
+/// Parse `type_str` as a `verus_syn::Type` and normalize it, falling back
+/// to stripping lifetimes from the raw text (still far more thorough than
+/// the old prefix-only matching) for type syntax the walk doesn't handle.
+/// Returns `None` only for an empty string.
+pub fn normalize_type(type_str: &str) -> Option<NormalizedType> {
+    let type_str = type_str.trim();
+    if type_str.is_empty() {
+        return None;
+    }
+
+    let parsed = verus_syn::parse_str::<Type>(type_str)
+        .ok()
+        .and_then(|ty| render(&ty));
+
+    let (is_top_level_ref, rendered) = parsed.unwrap_or_else(|| {
+        (
+            type_str.starts_with('&'),
+            strip_lifetimes_textually(type_str),
+        )
+    });
+    let rendered = if is_top_level_ref {
+        rendered.strip_prefix('&').map(str::to_string).unwrap_or(rendered)
+    } else {
+        rendered
+    };
+
+    Some(NormalizedType {
+        is_top_level_ref,
+        rendered,
+    })
+}
+
+/// Render a type with every lifetime stripped, recursing into generic
+/// argument lists, tuples, arrays, etc. Returns `None` for type syntax
+/// this walk doesn't model (trait objects, `impl Trait`, fn pointers, ...),
+/// so the caller can fall back to the textual stripper instead of
+/// silently losing information.
+fn render(ty: &Type) -> Option<(bool, String)> {
+    match ty {
+        Type::Reference(r) => {
+            let (_, inner) = render(&r.elem)?;
+            Some((true, format!("&{inner}")))
+        }
+        Type::Path(type_path) => {
+            let rendered = match &type_path.qself {
+                Some(qself) => {
+                    let (_, self_ty) = render(&qself.ty)?;
+                    let trait_segments = &type_path.path.segments[..qself.position];
+                    let rest_segments = &type_path.path.segments[qself.position..];
+                    let trait_str = render_segments(trait_segments)?;
+                    let rest_str = render_segments(rest_segments)?;
+                    if trait_str.is_empty() {
+                        format!("<{self_ty}>::{rest_str}")
+                    } else {
+                        format!("<{self_ty} as {trait_str}>::{rest_str}")
+                    }
+                }
+                None => render_path(&type_path.path)?,
+            };
+            Some((false, rendered))
+        }
+        Type::Tuple(tuple) => {
+            let parts: Option<Vec<String>> =
+                tuple.elems.iter().map(|t| render(t).map(|r| r.1)).collect();
+            Some((false, format!("({})", parts?.join(", "))))
+        }
+        Type::Slice(slice) => {
+            let (_, inner) = render(&slice.elem)?;
+            Some((false, format!("[{inner}]")))
+        }
+        Type::Array(array) => {
+            let (_, inner) = render(&array.elem)?;
+            Some((false, format!("[{inner}; {}]", render_expr(&array.len))))
+        }
+        Type::Paren(paren) => render(&paren.elem),
+        Type::Group(group) => render(&group.elem),
+        Type::Ptr(ptr) => {
+            let (_, inner) = render(&ptr.elem)?;
+            let prefix = if ptr.const_token.is_some() {
+                "*const "
+            } else {
+                "*mut "
+            };
+            Some((false, format!("{prefix}{inner}")))
+        }
+        // Trait objects, `impl Trait`, fn pointers, macros, and other
+        // syntax this walk doesn't model -- let the caller fall back to
+        // the textual stripper.
+        _ => None,
+    }
+}
+
+fn render_path(path: &Path) -> Option<String> {
+    let rendered = render_segments(&path.segments)?;
+    Some(if path.leading_colon.is_some() {
+        format!("::{rendered}")
+    } else {
+        rendered
+    })
+}
+
+fn render_segments(
+    segments: &verus_syn::punctuated::Punctuated<verus_syn::PathSegment, verus_syn::Token![::]>,
+) -> Option<String> {
+    let rendered: Option<Vec<String>> = segments.iter().map(render_path_segment).collect();
+    Some(rendered?.join("::"))
+}
+
+fn render_path_segment(segment: &verus_syn::PathSegment) -> Option<String> {
+    let name = segment.ident.to_string();
+    match &segment.arguments {
+        PathArguments::None => Some(name),
+        PathArguments::AngleBracketed(args) => {
+            let rendered_args: Vec<String> = args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    // Lifetimes don't distinguish one impl from another --
+                    // drop them entirely rather than render `'a`.
+                    GenericArgument::Lifetime(_) => None,
+                    GenericArgument::Type(ty) => render(ty).map(|r| r.1),
+                    GenericArgument::Const(expr) => Some(render_expr(expr)),
+                    _ => Some("_".to_string()),
+                })
+                .collect();
+            if rendered_args.is_empty() {
+                Some(name)
+            } else {
+                Some(format!("{name}<{}>", rendered_args.join(", ")))
+            }
+        }
+        PathArguments::Parenthesized(_) => Some(format!("{name}(..)")),
+    }
+}
+
+/// Render a const-generic or array-length expression. Only literals are
+/// rendered exactly; anything else (a const generic parameter, a path
+/// expression) becomes `_` since rendering arbitrary expressions back to
+/// source isn't needed for type-identity comparisons.
+fn render_expr(expr: &Expr) -> String {
+    if let Expr::Lit(expr_lit) = expr {
+        match &expr_lit.lit {
+            Lit::Int(i) => return i.base10_digits().to_string(),
+            Lit::Bool(b) => return b.value.to_string(),
+            Lit::Str(s) => return format!("{:?}", s.value()),
+            _ => {}
+        }
+    }
+    "_".to_string()
+}
+
+/// Strip lifetime tokens and the `mut` keyword wherever they appear in raw
+/// type text, then collapse whitespace. Used only when the type doesn't
+/// parse as a `verus_syn::Type` at all -- still strictly more thorough
+/// than the old prefix-only `trim_start_matches`, since it catches
+/// lifetimes anywhere in the string, not just a leading one.
+fn strip_lifetimes_textually(type_str: &str) -> String {
+    let lifetime_re = regex::Regex::new(r"'[A-Za-z_][A-Za-z0-9_]*\b\s*")
+        .expect("lifetime-stripping regex is a fixed valid pattern");
+    let mut_re =
+        regex::Regex::new(r"\bmut\b\s*").expect("mut-stripping regex is a fixed valid pattern");
+
+    let without_lifetimes = lifetime_re.replace_all(type_str, "");
+    let without_mut = mut_re.replace_all(&without_lifetimes, "");
+    without_mut.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_lifetime_and_preserves_reference() {
+        let normalized = normalize_type("&'a Scalar").expect("should parse");
+        assert!(normalized.is_top_level_ref);
+        assert_eq!(normalized.rendered, "Scalar");
+        assert_eq!(normalized.rendered_with_ref(), "&Scalar");
+    }
+
+    #[test]
+    fn collapses_static_mut_reference_like_plain_reference() {
+        let a = normalize_type("&'static mut Scalar").expect("should parse");
+        let b = normalize_type("&Scalar").expect("should parse");
+        assert_eq!(a.rendered, b.rendered);
+        assert_eq!(a.is_top_level_ref, b.is_top_level_ref);
+    }
+
+    #[test]
+    fn strips_nested_lifetimes_in_generic_arguments() {
+        let a = normalize_type("Mul<&'a Scalar>").expect("should parse");
+        let b = normalize_type("Mul<&Scalar>").expect("should parse");
+        assert_eq!(a.rendered, b.rendered);
+        assert_eq!(a.rendered, "Mul<&Scalar>");
+    }
+
+    #[test]
+    fn preserves_reference_distinction_in_generic_arguments() {
+        let owned = normalize_type("From<T>").expect("should parse");
+        let reference = normalize_type("From<&T>").expect("should parse");
+        assert_ne!(owned.rendered, reference.rendered);
+    }
+
+    #[test]
+    fn renders_qualified_path() {
+        let normalized = normalize_type("<T as Trait>::Output").expect("should parse");
+        assert_eq!(normalized.rendered, "<T as Trait>::Output");
+    }
+
+    #[test]
+    fn renders_tuple_and_array_types() {
+        let tuple = normalize_type("(Scalar, &'a Point)").expect("should parse");
+        assert_eq!(tuple.rendered, "(Scalar, &Point)");
+
+        let array = normalize_type("[u8; 32]").expect("should parse");
+        assert_eq!(array.rendered, "[u8; 32]");
+    }
+}
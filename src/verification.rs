@@ -7,10 +7,12 @@
 use regex::Regex;
 use rust_lapper::{Interval, Lapper};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fs;
+use std::io::{IsTerminal, Read, Write};
 use std::path::Path;
 use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
 
 /// Function metadata stored in the interval tree
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -37,13 +39,17 @@ struct FunctionIndex {
 }
 
 impl FunctionIndex {
-    /// Build a function index from parsed function info
-    pub fn from_functions(functions: &[crate::verus_parser::FunctionInfo]) -> Self {
+    /// Build a function index from parsed function info, keeping only
+    /// functions whose file is in scope per `narrow`.
+    pub fn from_functions(
+        functions: &[crate::verus_parser::FunctionInfo],
+        narrow: &crate::narrow::Matcher,
+    ) -> Self {
         let mut intervals_by_file: HashMap<String, Vec<FuncInterval>> = HashMap::new();
 
         for func in functions {
             let file_path = func.file.clone().unwrap_or_default();
-            if file_path.is_empty() {
+            if file_path.is_empty() || !narrow.matches(&file_path) {
                 continue;
             }
 
@@ -131,6 +137,60 @@ impl FunctionIndex {
     }
 }
 
+/// Verus error messages that indicate a verification failure rather than a
+/// plain compilation error, shared between the regex and JSON diagnostic
+/// parsing paths so both classify failures the same way.
+pub(crate) const VERIFICATION_ERROR_TYPES: &[&str] = &[
+    "assertion failed",
+    "postcondition not satisfied",
+    "precondition not satisfied",
+    "loop invariant not preserved",
+    "loop invariant not satisfied on entry",
+    "assertion not satisfied",
+];
+
+/// A normalization rule: every match of the pattern in a line of output is
+/// replaced with the given placeholder before any other parsing happens.
+/// The replacement uses [`Regex::replace_all`] syntax: `$1`/`${name}` expand
+/// to capture groups and `$$` is a literal `$`.
+pub type Filter = (Regex, String);
+
+/// Default filters for the volatile substrings that make Verus/cargo output
+/// non-reproducible across machines and runs: absolute paths, elapsed-time
+/// notes, and thread counts.
+fn default_filters() -> Vec<Filter> {
+    vec![
+        (
+            Regex::new(r"has been running for [\d.]+s").unwrap(),
+            "has been running for $$TIME".to_string(),
+        ),
+        (
+            Regex::new(r"finished in [\d.]+s").unwrap(),
+            "finished in $$TIME".to_string(),
+        ),
+        (
+            Regex::new(r"using \d+ threads?").unwrap(),
+            "using $$THREADS threads".to_string(),
+        ),
+        (
+            Regex::new(r"(^|[\s(])/[^\s:()]+").unwrap(),
+            "${1}$$DIR".to_string(),
+        ),
+    ]
+}
+
+/// Apply `filters` to `line` in order, replacing every match with its
+/// configured placeholder.
+fn apply_filters(filters: &[Filter], line: &str) -> String {
+    let mut line = line.to_string();
+    for (pattern, replacement) in filters {
+        if pattern.is_match(&line) {
+            line = pattern.replace_all(&line, replacement.as_str()).to_string();
+        }
+    }
+    line
+}
+
 /// A compilation or verification error
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompilationError {
@@ -153,6 +213,174 @@ pub struct VerificationFailure {
     pub full_error_text: String,
 }
 
+/// One line of `cargo --message-format=json` output.
+#[derive(Debug, Deserialize)]
+struct CargoDiagnosticLine {
+    reason: String,
+    #[serde(default)]
+    message: Option<RustcDiagnostic>,
+}
+
+/// A single rustc/Verus diagnostic, as embedded in a `compiler-message` line.
+#[derive(Debug, Deserialize)]
+struct RustcDiagnostic {
+    message: String,
+    level: String,
+    #[serde(default)]
+    spans: Vec<DiagnosticSpan>,
+    #[serde(default)]
+    children: Vec<RustcDiagnostic>,
+}
+
+/// A source span within a JSON diagnostic.
+#[derive(Debug, Deserialize)]
+struct DiagnosticSpan {
+    file_name: String,
+    line_start: i32,
+    column_start: i32,
+    is_primary: bool,
+    #[serde(default)]
+    byte_start: usize,
+    #[serde(default)]
+    byte_end: usize,
+    #[serde(default)]
+    suggested_replacement: Option<String>,
+    #[serde(default)]
+    suggestion_applicability: Option<String>,
+}
+
+/// A single machine-applicable edit extracted from a diagnostic's spans.
+#[derive(Debug, Clone)]
+struct Suggestion {
+    byte_start: usize,
+    byte_end: usize,
+    replacement: String,
+}
+
+/// Walk `output` (newline-delimited `cargo --message-format=json`) and
+/// collect every `MachineApplicable` suggestion, grouped by file. Mirrors
+/// compiletest's `get_suggestions_from_json`.
+fn collect_machine_applicable_suggestions(output: &str) -> HashMap<String, Vec<Suggestion>> {
+    let mut by_file: HashMap<String, Vec<Suggestion>> = HashMap::new();
+
+    for line in output.lines() {
+        let line = line.trim();
+        if !line.starts_with('{') {
+            continue;
+        }
+        let Ok(cargo_message) = serde_json::from_str::<CargoDiagnosticLine>(line) else {
+            continue;
+        };
+        if cargo_message.reason != "compiler-message" {
+            continue;
+        }
+        let Some(diagnostic) = cargo_message.message else {
+            continue;
+        };
+        collect_suggestion_spans(&diagnostic, &mut by_file);
+    }
+
+    by_file
+}
+
+/// Recurse into a diagnostic's `children` (rustc nests suggestions as `help`
+/// sub-diagnostics), collecting any span marked `MachineApplicable`.
+fn collect_suggestion_spans(
+    diagnostic: &RustcDiagnostic,
+    by_file: &mut HashMap<String, Vec<Suggestion>>,
+) {
+    for span in &diagnostic.spans {
+        if span.suggestion_applicability.as_deref() == Some("MachineApplicable") {
+            if let Some(replacement) = &span.suggested_replacement {
+                by_file
+                    .entry(span.file_name.clone())
+                    .or_default()
+                    .push(Suggestion {
+                        byte_start: span.byte_start,
+                        byte_end: span.byte_end,
+                        replacement: replacement.clone(),
+                    });
+            }
+        }
+    }
+    for child in &diagnostic.children {
+        collect_suggestion_spans(child, by_file);
+    }
+}
+
+/// Apply `suggestions` to `source`, like compiletest's `apply_suggestions`.
+///
+/// Edits are applied highest-byte-offset-first so that applying one never
+/// invalidates the offsets of the ones still pending, and any suggestion
+/// whose range overlaps one already applied is skipped rather than risking a
+/// corrupt rewrite. Returns the patched source and the suggestions that were
+/// actually applied, in original (ascending) order.
+fn apply_suggestions(source: &str, suggestions: &mut Vec<Suggestion>) -> (String, Vec<Suggestion>) {
+    suggestions.sort_by(|a, b| b.byte_start.cmp(&a.byte_start));
+
+    let mut result = source.to_string();
+    let mut applied = Vec::new();
+    let mut last_applied_start = source.len() + 1;
+
+    for suggestion in suggestions.drain(..) {
+        if suggestion.byte_start > suggestion.byte_end
+            || suggestion.byte_end > result.len()
+            || suggestion.byte_end > last_applied_start
+        {
+            continue;
+        }
+        result.replace_range(suggestion.byte_start..suggestion.byte_end, &suggestion.replacement);
+        last_applied_start = suggestion.byte_start;
+        applied.push(suggestion);
+    }
+
+    applied.reverse();
+    (result, applied)
+}
+
+/// Render a minimal unified-style hunk for the edits applied to one file.
+fn render_fix_diff(file: &str, original: &str, applied: &[Suggestion]) -> String {
+    let mut out = format!("--- {file}\n+++ {file}\n");
+    for suggestion in applied {
+        let before = &original[suggestion.byte_start..suggestion.byte_end];
+        out.push_str(&format!("- {}\n+ {}\n", before.trim(), suggestion.replacement.trim()));
+    }
+    out
+}
+
+/// Build the file -> error-line-numbers map [`VerificationAnalyzer::analyze_output`]
+/// uses to mark functions failed, from JSON diagnostics instead of scraped
+/// text. Combines plain compilation errors and verification failures, since
+/// either can land inside a function body.
+fn json_errors_by_file(
+    errors: &[CompilationError],
+    failures: &[VerificationFailure],
+    narrow: &crate::narrow::Matcher,
+) -> HashMap<String, Vec<i32>> {
+    let mut errors_by_file: HashMap<String, Vec<i32>> = HashMap::new();
+    for (file, line) in errors
+        .iter()
+        .map(|e| (&e.file, e.line))
+        .chain(failures.iter().map(|f| (&f.file, f.line)))
+    {
+        if let (Some(file), Some(line)) = (file, line) {
+            if narrow.matches(file) {
+                errors_by_file.entry(file.clone()).or_default().push(line);
+            }
+        }
+    }
+    errors_by_file
+}
+
+/// Flatten a diagnostic's nested `children` (notes/help) into `full_message`,
+/// the same role the regex parser's continuation lines play.
+fn collect_diagnostic_children(children: &[RustcDiagnostic], out: &mut Vec<String>) {
+    for child in children {
+        out.push(format!("{}: {}", child.level, child.message));
+        collect_diagnostic_children(&child.children, out);
+    }
+}
+
 /// Parser for compilation errors from cargo/verus output
 pub struct CompilationErrorParser {
     error_pattern: Regex,
@@ -165,6 +393,7 @@ pub struct CompilationErrorParser {
     verus_command_exit_pattern: Regex,
     verification_results_pattern: Regex,
     verification_error_patterns: Vec<Regex>,
+    filters: Vec<Filter>,
 }
 
 impl Default for CompilationErrorParser {
@@ -199,14 +428,108 @@ impl CompilationErrorParser {
                 Regex::new(r"error: loop invariant not satisfied on entry").unwrap(),
                 Regex::new(r"error: assertion not satisfied").unwrap(),
             ],
+            filters: default_filters(),
         }
     }
 
+    /// Register an additional normalization filter, applied after the
+    /// defaults, in registration order.
+    pub fn with_filter(mut self, pattern: Regex, replacement: impl Into<String>) -> Self {
+        self.filters.push((pattern, replacement.into()));
+        self
+    }
+
     /// Check if the output contains verification results summary
     pub fn has_verification_results(&self, output_content: &str) -> bool {
         self.verification_results_pattern.is_match(output_content)
     }
 
+    /// Parse newline-delimited JSON diagnostics, as emitted by
+    /// `cargo verus verify --message-format=json` / `rustc --error-format=json`.
+    ///
+    /// This is the preferred ingestion path over [`Self::parse_compilation_output`]
+    /// when JSON output is available: it reads exact spans straight off each
+    /// diagnostic instead of reverse-engineering them from rendered text, so it
+    /// isn't thrown off by ANSI codes or wording changes between Verus/rustc
+    /// versions. Lines that aren't `"reason":"compiler-message"` (e.g. build
+    /// script or artifact messages) are skipped. Messages matching
+    /// [`VERIFICATION_ERROR_TYPES`] are classified as verification failures
+    /// instead of plain compilation errors, mirroring [`VerificationParser`].
+    pub fn parse_json_diagnostics(
+        &self,
+        output: &str,
+    ) -> (Vec<CompilationError>, Vec<CompilationError>, Vec<VerificationFailure>) {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut verification_failures = Vec::new();
+
+        for line in output.lines() {
+            let line = line.trim();
+            if !line.starts_with('{') {
+                continue;
+            }
+            let Ok(cargo_message) = serde_json::from_str::<CargoDiagnosticLine>(line) else {
+                continue;
+            };
+            if cargo_message.reason != "compiler-message" {
+                continue;
+            }
+            let Some(diagnostic) = cargo_message.message else {
+                continue;
+            };
+            if diagnostic.level != "error" && diagnostic.level != "warning" {
+                continue;
+            }
+
+            let primary_span = diagnostic.spans.iter().find(|s| s.is_primary);
+            let file = primary_span.map(|s| s.file_name.clone());
+            let line_num = primary_span.map(|s| s.line_start);
+            let column = primary_span.map(|s| s.column_start);
+
+            let mut full_message = vec![diagnostic.message.clone()];
+            collect_diagnostic_children(&diagnostic.children, &mut full_message);
+
+            if let Some(&error_type) = VERIFICATION_ERROR_TYPES
+                .iter()
+                .find(|&&t| diagnostic.message.contains(t))
+            {
+                let assertion_details: Vec<String> = full_message
+                    .iter()
+                    .filter(|l| l.contains("assert") || l.contains('|') || l.starts_with("-->"))
+                    .cloned()
+                    .collect();
+
+                verification_failures.push(VerificationFailure {
+                    error_type: error_type.to_string(),
+                    file,
+                    line: line_num,
+                    column,
+                    message: diagnostic.message,
+                    assertion_details,
+                    full_error_text: full_message.join("\n"),
+                });
+            } else if diagnostic.level == "error" {
+                errors.push(CompilationError {
+                    message: diagnostic.message,
+                    file,
+                    line: line_num,
+                    column,
+                    full_message,
+                });
+            } else {
+                warnings.push(CompilationError {
+                    message: diagnostic.message,
+                    file,
+                    line: line_num,
+                    column,
+                    full_message,
+                });
+            }
+        }
+
+        (errors, warnings, verification_failures)
+    }
+
     /// Parse compilation output and extract errors and warnings
     pub fn parse_compilation_output(
         &self,
@@ -221,7 +544,8 @@ impl CompilationErrorParser {
         let lines: Vec<&str> = output_content.lines().collect();
 
         for line in &lines {
-            let line = line.trim();
+            let line = apply_filters(&self.filters, line.trim());
+            let line = line.as_str();
 
             // Skip verification results summary lines
             if self.verification_results_pattern.is_match(line) {
@@ -410,6 +734,7 @@ pub struct VerificationParser {
     error_pattern: Regex,
     verification_error_types: Vec<&'static str>,
     ansi_escape_pattern: Regex,
+    filters: Vec<Filter>,
 }
 
 impl Default for VerificationParser {
@@ -422,38 +747,49 @@ impl VerificationParser {
     pub fn new() -> Self {
         Self {
             error_pattern: Regex::new(r"-->\s+([^:]+):(\d+):\d+").unwrap(),
-            verification_error_types: vec![
-                "assertion failed",
-                "postcondition not satisfied",
-                "precondition not satisfied",
-                "loop invariant not preserved",
-                "loop invariant not satisfied on entry",
-                "assertion not satisfied",
-            ],
+            verification_error_types: VERIFICATION_ERROR_TYPES.to_vec(),
             ansi_escape_pattern: Regex::new(r"\x1b\[[0-9;]*m").unwrap(),
+            filters: default_filters(),
         }
     }
 
+    /// Register an additional normalization filter, applied after the
+    /// defaults, in registration order.
+    pub fn with_filter(mut self, pattern: Regex, replacement: impl Into<String>) -> Self {
+        self.filters.push((pattern, replacement.into()));
+        self
+    }
+
     /// Parse verification output file and extract files with errors and their line numbers
     pub fn parse_verification_output(
         &self,
         output_file_path: &Path,
+        narrow: &crate::narrow::Matcher,
     ) -> Result<HashMap<String, Vec<i32>>, std::io::Error> {
         let content = fs::read_to_string(output_file_path)?;
-        Ok(self.parse_verification_output_from_content(&content))
+        Ok(self.parse_verification_output_from_content(&content, narrow))
     }
 
-    /// Parse verification output content and extract files with errors and their line numbers
+    /// Parse verification output content and extract files with errors and
+    /// their line numbers, keeping only files in scope per `narrow`.
     pub fn parse_verification_output_from_content(
         &self,
         output_content: &str,
+        narrow: &crate::narrow::Matcher,
     ) -> HashMap<String, Vec<i32>> {
         let mut errors_by_file: HashMap<String, Vec<i32>> = HashMap::new();
-        let lines: Vec<&str> = output_content.lines().collect();
+        let filtered_lines: Vec<String> = output_content
+            .lines()
+            .map(|l| apply_filters(&self.filters, l))
+            .collect();
+        let lines: Vec<&str> = filtered_lines.iter().map(String::as_str).collect();
 
         for (i, line) in lines.iter().enumerate() {
             if let Some(caps) = self.error_pattern.captures(line) {
                 let file_path = caps[1].to_string();
+                if !narrow.matches(&file_path) {
+                    continue;
+                }
                 let line_number: i32 = caps[2].parse().unwrap_or(0);
 
                 // Look back to see if this is an actual error
@@ -498,7 +834,11 @@ impl VerificationParser {
     /// Parse verification failures and return detailed information
     pub fn parse_verification_failures(&self, output_content: &str) -> Vec<VerificationFailure> {
         let mut failures = Vec::new();
-        let lines: Vec<&str> = output_content.lines().collect();
+        let filtered_lines: Vec<String> = output_content
+            .lines()
+            .map(|l| apply_filters(&self.filters, l))
+            .collect();
+        let lines: Vec<&str> = filtered_lines.iter().map(String::as_str).collect();
 
         let mut i = 0;
         while i < lines.len() {
@@ -672,6 +1012,203 @@ impl VerificationParser {
     }
 }
 
+/// Cap on the combined stdout+stderr captured from a single `cargo verus`
+/// invocation. Verbose Verus runs can otherwise produce tens of megabytes of
+/// diagnostics; past this cap we keep the head and tail and drop the middle.
+const MAX_CAPTURE_BYTES: usize = 10 * 1024 * 1024;
+
+/// Combined stdout+stderr captured from a `cargo verus` invocation, bounded
+/// to [`MAX_CAPTURE_BYTES`].
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CapturedOutput {
+    pub text: String,
+    /// Whether bytes in the middle of the stream were dropped to stay under
+    /// the cap.
+    pub truncated: bool,
+}
+
+/// A byte sink that keeps only the first and last `cap` bytes pushed to it,
+/// in memory bounded by `cap` regardless of how much is pushed overall.
+///
+/// Used to abbreviate `cargo verus` output the same way compiletest's
+/// `read2_abbreviated` abbreviates rustc output: keep the head (likely
+/// setup/compile diagnostics) and the tail (the final summary line), and
+/// drop whatever doesn't fit in between.
+struct AbbreviatedCapture {
+    head: Vec<u8>,
+    tail: VecDeque<u8>,
+    head_cap: usize,
+    tail_cap: usize,
+    total: usize,
+}
+
+impl AbbreviatedCapture {
+    fn new(cap: usize) -> Self {
+        let head_cap = cap / 2;
+        let tail_cap = cap - head_cap;
+        Self {
+            head: Vec::new(),
+            tail: VecDeque::with_capacity(tail_cap),
+            head_cap,
+            tail_cap,
+            total: 0,
+        }
+    }
+
+    fn push(&mut self, data: &[u8]) {
+        self.total += data.len();
+
+        if self.head.len() < self.head_cap {
+            let take = (self.head_cap - self.head.len()).min(data.len());
+            self.head.extend_from_slice(&data[..take]);
+        }
+
+        if self.tail_cap > 0 {
+            for &byte in data {
+                if self.tail.len() == self.tail_cap {
+                    self.tail.pop_front();
+                }
+                self.tail.push_back(byte);
+            }
+        }
+    }
+
+    /// Consume into the final bounded text plus whether anything was dropped.
+    fn finish(self) -> CapturedOutput {
+        let truncated = self.total > self.head.len() + self.tail.len();
+        let remaining = self.total.saturating_sub(self.head.len());
+        let keep_from_tail = remaining.min(self.tail.len());
+        let skip = self.tail.len() - keep_from_tail;
+
+        let mut bytes = self.head;
+        if truncated {
+            let omitted = self.total - bytes.len() - self.tail.len();
+            bytes.extend_from_slice(format!("\n<{omitted} bytes omitted>\n").as_bytes());
+        }
+        bytes.extend(self.tail.into_iter().skip(skip));
+
+        CapturedOutput {
+            text: String::from_utf8_lossy(&bytes).into_owned(),
+            truncated,
+        }
+    }
+}
+
+/// Drain `pipe` into `sink` in fixed-size chunks until EOF. Reading in small
+/// chunks rather than `read_to_end` means a full pipe buffer on one stream
+/// never blocks the writer on the other side from making progress, the same
+/// deadlock compiletest's `read2` avoids by draining both pipes concurrently.
+///
+/// When `on_line` is given, each complete line is also handed to it as it
+/// arrives (used to drive [`ProgressReporter`] off the live stream, not just
+/// the final captured text).
+fn drain_into(
+    mut pipe: impl Read,
+    sink: &Arc<Mutex<AbbreviatedCapture>>,
+    on_line: Option<&(dyn Fn(&str) + Send + Sync)>,
+) {
+    let mut buf = [0u8; 8192];
+    let mut pending = Vec::new();
+    loop {
+        match pipe.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                sink.lock().unwrap().push(&buf[..n]);
+                let Some(on_line) = on_line else { continue };
+                pending.extend_from_slice(&buf[..n]);
+                while let Some(pos) = pending.iter().position(|&b| b == b'\n') {
+                    on_line(&String::from_utf8_lossy(&pending[..pos]));
+                    pending.drain(..=pos);
+                }
+            }
+        }
+    }
+}
+
+/// Live progress reporting for a long `cargo verus verify` run.
+///
+/// Verus doesn't announce "function N of M done" on stdout, but it does
+/// print a `--> file:line:col` location for every diagnostic as one is
+/// produced, interleaved with the run. [`ProgressReporter::on_line`] treats
+/// each distinct location as a proxy for "one more function reached",
+/// counted against `total` (typically the function count from
+/// [`crate::verus_parser::parse_all_functions`]), and renders either a
+/// live carriage-return-updated bar when stderr is a TTY, or periodic plain
+/// lines otherwise.
+pub struct ProgressReporter {
+    total: usize,
+    tty: bool,
+    location_pattern: Regex,
+    state: Mutex<ProgressState>,
+}
+
+struct ProgressState {
+    seen: HashSet<(String, usize)>,
+    last_reported: usize,
+}
+
+impl ProgressReporter {
+    pub fn new(total: usize) -> Self {
+        Self {
+            total,
+            tty: std::io::stderr().is_terminal(),
+            location_pattern: Regex::new(r"-->\s+([^:]+):(\d+):\d+").unwrap(),
+            state: Mutex::new(ProgressState {
+                seen: HashSet::new(),
+                last_reported: 0,
+            }),
+        }
+    }
+
+    /// Feed one line of Verus output; renders an updated progress line
+    /// whenever it names a file:line not already counted.
+    pub fn on_line(&self, line: &str) {
+        let Some(caps) = self.location_pattern.captures(line) else {
+            return;
+        };
+        let file = caps[1].to_string();
+        let Ok(line_no) = caps[2].parse::<usize>() else {
+            return;
+        };
+
+        let mut state = self.state.lock().unwrap();
+        if !state.seen.insert((file, line_no)) {
+            return;
+        }
+        let done = state.seen.len().min(self.total.max(1));
+        if done == state.last_reported {
+            return;
+        }
+        state.last_reported = done;
+        self.render(done);
+    }
+
+    fn render(&self, done: usize) {
+        let total = self.total.max(1);
+        if self.tty {
+            const WIDTH: usize = 30;
+            let filled = (done * WIDTH) / total;
+            eprint!(
+                "\r[{}{}] {}/{}",
+                "=".repeat(filled),
+                " ".repeat(WIDTH - filled),
+                done,
+                total
+            );
+            let _ = std::io::stderr().flush();
+        } else if done % 10 == 0 || done == total {
+            eprintln!("  ...{}/{} functions processed", done, total);
+        }
+    }
+
+    /// Finish the progress display, moving output past the in-place bar.
+    pub fn finish(&self) {
+        if self.tty {
+            eprintln!();
+        }
+    }
+}
+
 /// Runner for Verus verification
 pub struct VerusRunner;
 
@@ -706,22 +1243,82 @@ impl VerusRunner {
         module: Option<&str>,
         function: Option<&str>,
         extra_args: Option<&[String]>,
-    ) -> Result<(String, i32), std::io::Error> {
+    ) -> Result<(CapturedOutput, i32), std::io::Error> {
+        self.run_verification_impl(work_dir, package, module, function, extra_args, false, None)
+    }
+
+    /// Like [`Self::run_verification`], but reports live progress to
+    /// `progress` as Verus output streams in (see [`ProgressReporter`])
+    /// instead of only returning the final captured text.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_verification_with_progress(
+        &self,
+        work_dir: &Path,
+        package: Option<&str>,
+        module: Option<&str>,
+        function: Option<&str>,
+        extra_args: Option<&[String]>,
+        progress: &ProgressReporter,
+    ) -> Result<(CapturedOutput, i32), std::io::Error> {
+        self.run_verification_impl(
+            work_dir,
+            package,
+            module,
+            function,
+            extra_args,
+            false,
+            Some(&|line: &str| progress.on_line(line)),
+        )
+    }
+
+    /// Like [`Self::run_verification`], but asks for structured JSON
+    /// diagnostics (`--message-format=json` plus Verus's own JSON diagnostic
+    /// flag) instead of rendered text, so the output can be consumed with
+    /// [`CompilationErrorParser::parse_json_diagnostics`] for exact
+    /// file+line+column on every diagnostic.
+    pub fn run_verification_json(
+        &self,
+        work_dir: &Path,
+        package: Option<&str>,
+        module: Option<&str>,
+        function: Option<&str>,
+    ) -> Result<(CapturedOutput, i32), std::io::Error> {
+        self.run_verification_impl(work_dir, package, module, function, None, true, None)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn run_verification_impl(
+        &self,
+        work_dir: &Path,
+        package: Option<&str>,
+        module: Option<&str>,
+        function: Option<&str>,
+        extra_args: Option<&[String]>,
+        json: bool,
+        on_line: Option<&(dyn Fn(&str) + Send + Sync)>,
+    ) -> Result<(CapturedOutput, i32), std::io::Error> {
         self.setup_environment();
 
         let mut cmd = Command::new("cargo");
         cmd.arg("verus").arg("verify");
 
+        if json {
+            cmd.arg("--message-format=json");
+        }
+
         if let Some(pkg) = package {
             cmd.arg("-p").arg(pkg);
         }
 
         // Verus-specific args go after --
         let mut has_verus_args = false;
-        if module.is_some() || function.is_some() {
+        if json || module.is_some() || function.is_some() {
             cmd.arg("--");
             has_verus_args = true;
 
+            if json {
+                cmd.arg("--output-json");
+            }
             if let Some(mod_name) = module {
                 cmd.arg("--verify-only-module").arg(mod_name);
             }
@@ -743,15 +1340,234 @@ impl VerusRunner {
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
 
-        let output = cmd.output()?;
+        let mut child = cmd.spawn()?;
+        let child_stdout = child.stdout.take().expect("stdout was piped");
+        let child_stderr = child.stderr.take().expect("stderr was piped");
+
+        // Both pipes drain into the same sink so the combined text preserves
+        // the order diagnostics actually arrived in, not stdout-then-stderr.
+        // Scoped (rather than spawned) so `on_line`, which may borrow a
+        // caller-owned `ProgressReporter`, doesn't need to be `'static`.
+        let sink = Arc::new(Mutex::new(AbbreviatedCapture::new(MAX_CAPTURE_BYTES)));
+        std::thread::scope(|scope| {
+            let stderr_sink = Arc::clone(&sink);
+            let stderr_thread =
+                scope.spawn(move || drain_into(child_stderr, &stderr_sink, on_line));
+            drain_into(child_stdout, &sink, on_line);
+            let _ = stderr_thread.join();
+        });
+
+        let status = child.wait()?;
+        let exit_code = status.code().unwrap_or(1);
+        let captured = Arc::try_unwrap(sink)
+            .expect("both reader threads have finished")
+            .into_inner()
+            .unwrap()
+            .finish();
+
+        Ok((captured, exit_code))
+    }
+
+    /// Run verification, then rewrite every file `cargo verus` flagged with a
+    /// `MachineApplicable` suggestion (a missing `decreases`, a malformed
+    /// `requires`/`ensures`, a trivially fixable Rust error) straight in
+    /// `work_dir`, mirroring compiletest's rustfix flow
+    /// (`get_suggestions_from_json` + `apply_suggestions`).
+    pub fn run_and_fix(
+        &self,
+        work_dir: &Path,
+        package: Option<&str>,
+        module: Option<&str>,
+        function: Option<&str>,
+    ) -> Result<FixSummary, std::io::Error> {
+        let (captured, _exit_code) = self.run_verification_json(work_dir, package, module, function)?;
+        let mut suggestions_by_file = collect_machine_applicable_suggestions(&captured.text);
+
+        let parsed = crate::verus_parser::parse_all_functions(work_dir, false, true, false, false, true);
+        let function_index =
+            FunctionIndex::from_functions(&parsed.functions, &crate::narrow::Matcher::all());
+
+        let mut patched_functions = Vec::new();
+        let mut diff = String::new();
+
+        for (file, suggestions) in suggestions_by_file.iter_mut() {
+            let path = work_dir.join(file);
+            let Ok(original) = fs::read_to_string(&path) else {
+                continue;
+            };
+
+            let (patched, applied) = apply_suggestions(&original, suggestions);
+            if applied.is_empty() {
+                continue;
+            }
+
+            for suggestion in &applied {
+                let line = original[..suggestion.byte_start].matches('\n').count() + 1;
+                if let Some(func) = function_index.find_at_line(file, line) {
+                    patched_functions.push(FunctionLocation {
+                        display_name: func.name.clone(),
+                        code_path: func.file.clone(),
+                        code_text: CodeTextInfo {
+                            lines_start: func.start_line,
+                            lines_end: func.end_line,
+                        },
+                    });
+                }
+            }
+
+            diff.push_str(&render_fix_diff(file, &original, &applied));
+            fs::write(&path, patched)?;
+        }
+
+        patched_functions.sort_by(|a, b| {
+            (&a.code_path, a.code_text.lines_start).cmp(&(&b.code_path, b.code_text.lines_start))
+        });
+        patched_functions
+            .dedup_by(|a, b| a.code_path == b.code_path && a.code_text.lines_start == b.code_text.lines_start);
+
+        Ok(FixSummary {
+            patched_functions,
+            diff,
+        })
+    }
+
+    /// Run verification once per revision, tagging each resulting
+    /// [`AnalysisResult`] with its revision name and aggregating into a
+    /// [`MultiRevisionResult`]. Adapts compiletest's "revisions" idea (one
+    /// source file, several named configurations) to Verus: a function whose
+    /// specs are gated behind a `cfg` or optional feature can verify under
+    /// one revision and fail under another, and the matrix makes that
+    /// visible in a single pass instead of requiring a separate run per
+    /// configuration.
+    pub fn run_revisions(
+        &self,
+        work_dir: &Path,
+        package: Option<&str>,
+        revisions: &[Revision],
+        analyzer: &VerificationAnalyzer,
+    ) -> Result<MultiRevisionResult, std::io::Error> {
+        let mut results = Vec::with_capacity(revisions.len());
+        for revision in revisions {
+            let (captured, exit_code) = self.run_verification(
+                work_dir,
+                package,
+                None,
+                None,
+                Some(&revision.extra_args),
+            )?;
+            let result =
+                analyzer.analyze_output(work_dir, &captured.text, Some(exit_code), None, None, None);
+            results.push((revision.name.clone(), result));
+        }
+
+        Ok(MultiRevisionResult::compute(results))
+    }
+}
+
+/// A named `cargo verus verify` invocation configuration -- e.g. a feature
+/// flag or `cfg` passed through as extra arguments -- compiletest's
+/// "revisions" concept adapted to verification runs. See
+/// [`VerusRunner::run_revisions`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Revision {
+    pub name: String,
+    pub extra_args: Vec<String>,
+}
+
+/// Result of [`VerusRunner::run_and_fix`]: the functions whose bodies were
+/// touched by an applied suggestion, plus a diff-style summary of the edits.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FixSummary {
+    pub patched_functions: Vec<FunctionLocation>,
+    pub diff: String,
+}
+
+/// A function's verification category under every [`Revision`] it was seen
+/// in. A function missing from a revision's map simply wasn't verifiable
+/// (no requires/ensures) or wasn't reached under that configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionRevisionStatus {
+    pub function: FunctionLocation,
+    pub status_by_revision: BTreeMap<String, FunctionCategory>,
+}
+
+impl FunctionRevisionStatus {
+    /// Whether this function's category differs across at least two of the
+    /// revisions it appeared in -- the configuration-dependent proof
+    /// breakage [`VerusRunner::run_revisions`] exists to surface.
+    pub fn is_configuration_dependent(&self) -> bool {
+        let mut statuses = self.status_by_revision.values();
+        let Some(first) = statuses.next() else {
+            return false;
+        };
+        statuses.any(|status| status != first)
+    }
+}
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let combined = format!("{}\n{}", stdout, stderr);
+/// The result of [`VerusRunner::run_revisions`]: every revision's own
+/// [`AnalysisResult`], plus a per-function matrix of status-by-revision so
+/// configuration-dependent proof breakage shows up in a single pass.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct MultiRevisionResult {
+    pub results: Vec<(String, AnalysisResult)>,
+    pub matrix: Vec<FunctionRevisionStatus>,
+}
 
-        let exit_code = output.status.code().unwrap_or(1);
+impl MultiRevisionResult {
+    fn compute(results: Vec<(String, AnalysisResult)>) -> Self {
+        let mut by_function: BTreeMap<(String, usize), (FunctionLocation, BTreeMap<String, FunctionCategory>)> =
+            BTreeMap::new();
+
+        for (name, result) in &results {
+            for (key, (location, category)) in categorize(result) {
+                by_function
+                    .entry(key)
+                    .or_insert_with(|| (location, BTreeMap::new()))
+                    .1
+                    .insert(name.clone(), category);
+            }
+        }
+
+        let matrix = by_function
+            .into_values()
+            .map(|(function, status_by_revision)| FunctionRevisionStatus {
+                function,
+                status_by_revision,
+            })
+            .collect();
+
+        Self { results, matrix }
+    }
+
+    /// The combined summary counts across every revision.
+    pub fn combined_summary(&self) -> AnalysisSummary {
+        let mut summary = AnalysisSummary {
+            total_functions: 0,
+            failed_functions: 0,
+            verified_functions: 0,
+            unverified_functions: 0,
+            verification_errors: 0,
+            compilation_errors: 0,
+            compilation_warnings: 0,
+        };
+        for (_, result) in &self.results {
+            summary.total_functions += result.summary.total_functions;
+            summary.failed_functions += result.summary.failed_functions;
+            summary.verified_functions += result.summary.verified_functions;
+            summary.unverified_functions += result.summary.unverified_functions;
+            summary.verification_errors += result.summary.verification_errors;
+            summary.compilation_errors += result.summary.compilation_errors;
+            summary.compilation_warnings += result.summary.compilation_warnings;
+        }
+        summary
+    }
 
-        Ok((combined, exit_code))
+    /// Functions whose category differs across revisions.
+    pub fn configuration_dependent(&self) -> Vec<&FunctionRevisionStatus> {
+        self.matrix
+            .iter()
+            .filter(|entry| entry.is_configuration_dependent())
+            .collect()
     }
 }
 
@@ -802,7 +1618,7 @@ pub struct VerificationResult {
 }
 
 /// Function location info - aligned with atoms.json format
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionLocation {
     #[serde(rename = "display-name")]
     pub display_name: String,
@@ -813,7 +1629,7 @@ pub struct FunctionLocation {
 }
 
 /// Code text info with line ranges - aligned with atoms.json format
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CodeTextInfo {
     #[serde(rename = "lines-start")]
     pub lines_start: usize,
@@ -849,7 +1665,11 @@ impl VerificationAnalyzer {
         }
     }
 
-    /// Analyze verification output content
+    /// Analyze verification output content.
+    ///
+    /// `narrow`, if given, restricts both which functions get indexed and
+    /// which failures get collected to files it matches -- see
+    /// [`crate::narrow::Matcher`]. `None` means "everything in scope".
     pub fn analyze_output(
         &self,
         path: &Path,
@@ -857,11 +1677,41 @@ impl VerificationAnalyzer {
         exit_code: Option<i32>,
         module_filter: Option<&str>,
         function_filter: Option<&str>,
+        narrow: Option<&crate::narrow::Matcher>,
     ) -> AnalysisResult {
-        // Parse compilation errors and warnings
-        let (compilation_errors, compilation_warnings) = self
+        let default_narrow = crate::narrow::Matcher::all();
+        let narrow = narrow.unwrap_or(&default_narrow);
+
+        // Prefer structured JSON diagnostics when the output is in that
+        // format: they carry exact file+line+column per diagnostic, so
+        // there's no fuzzy suffix/filename matching to get wrong. Fall back
+        // to the text parser for plain-text transcripts (e.g. captured
+        // without `--message-format=json`, or from an older Verus).
+        let (json_errors, json_warnings, json_failures) = self
             .compilation_parser
-            .parse_compilation_output(output_content);
+            .parse_json_diagnostics(output_content);
+        let has_json = !json_errors.is_empty() || !json_warnings.is_empty() || !json_failures.is_empty();
+
+        let (compilation_errors, compilation_warnings, verification_failures, errors_by_file) =
+            if has_json {
+                let errors_by_file = json_errors_by_file(&json_errors, &json_failures, narrow);
+                (json_errors, json_warnings, json_failures, errors_by_file)
+            } else {
+                let (compilation_errors, compilation_warnings) = self
+                    .compilation_parser
+                    .parse_compilation_output(output_content);
+                let errors_by_file = self
+                    .verification_parser
+                    .parse_verification_output_from_content(output_content, narrow);
+                let verification_failures =
+                    self.verification_parser.parse_verification_failures(output_content);
+                (
+                    compilation_errors,
+                    compilation_warnings,
+                    verification_failures,
+                    errors_by_file,
+                )
+            };
 
         // Get all functions with full info (including end lines and spec info)
         // Note: We set include_verus_constructs to false to exclude spec fn (no body to verify)
@@ -871,6 +1721,7 @@ impl VerificationAnalyzer {
             true,  // include_methods
             false, // show_visibility
             false, // show_kind
+            true,  // include_tests
         );
 
         // Filter to only verifiable functions (those with requires or ensures)
@@ -882,17 +1733,7 @@ impl VerificationAnalyzer {
             .collect();
 
         // Build interval tree index for O(log n) lookups
-        let function_index = FunctionIndex::from_functions(&verifiable_functions);
-
-        // Parse verification errors from content
-        let errors_by_file = self
-            .verification_parser
-            .parse_verification_output_from_content(output_content);
-
-        // Parse detailed verification failures
-        let verification_failures = self
-            .verification_parser
-            .parse_verification_failures(output_content);
+        let function_index = FunctionIndex::from_functions(&verifiable_functions, narrow);
 
         // Track which specific function locations failed (by key: name, file, start_line)
         let mut failed_function_keys: std::collections::HashSet<(String, String, usize)> =
@@ -1047,6 +1888,226 @@ impl VerificationAnalyzer {
             },
         }
     }
+
+    /// Compare `result` against a canonical `.expected.json` snapshot,
+    /// following compiletest's `expected_output_path` / `--bless` model. When
+    /// `bless` is set, or no snapshot exists yet, `result` is written as the
+    /// new golden snapshot and an empty diff is returned. Otherwise the
+    /// stored snapshot is read back and diffed against `result`.
+    ///
+    /// `FunctionLocation::code_path` is already crate-relative, so the only
+    /// normalization needed before comparing is putting each category into a
+    /// stable order -- [`SnapshotDiff::compute`] does that.
+    pub fn compare_to_snapshot(
+        &self,
+        result: &AnalysisResult,
+        snapshot_path: &Path,
+        bless: bool,
+    ) -> std::io::Result<SnapshotDiff> {
+        if bless || !snapshot_path.exists() {
+            let json = serde_json::to_string_pretty(result)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            fs::write(snapshot_path, json)?;
+            return Ok(SnapshotDiff::default());
+        }
+
+        let existing = fs::read_to_string(snapshot_path)?;
+        let golden: AnalysisResult = serde_json::from_str(&existing)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        Ok(SnapshotDiff::compute(&golden, result))
+    }
+
+    /// Cross-check `//~ VERIFY-FAIL` / `//~ ASSUME` function-level
+    /// annotations (see [`crate::expect`]) against `result`'s actual
+    /// per-function categorization, catching drift between a crate's
+    /// intended verification status and its real one.
+    pub fn run_expectations(
+        &self,
+        path: &Path,
+        result: &AnalysisResult,
+    ) -> Vec<crate::expect::FunctionExpectationMismatch> {
+        let parsed_output = crate::verus_parser::parse_all_functions(path, false, true, false, false, true);
+        let verifiable_functions: Vec<_> = parsed_output
+            .functions
+            .into_iter()
+            .filter(|f| f.has_requires || f.has_ensures)
+            .collect();
+
+        let categories = categorize(result);
+
+        // Group by file so each source is read (and annotations parsed) once.
+        let mut by_file: HashMap<String, Vec<crate::verus_parser::FunctionInfo>> = HashMap::new();
+        for func in verifiable_functions {
+            by_file
+                .entry(func.file.clone().unwrap_or_default())
+                .or_default()
+                .push(func);
+        }
+
+        let mut mismatches = Vec::new();
+        for (file, functions) in &by_file {
+            let Ok(source) = fs::read_to_string(path.join(file)) else {
+                continue;
+            };
+            mismatches.extend(crate::expect::check_function_expectations(
+                &source,
+                functions,
+                |func| {
+                    categories
+                        .get(&(func.file.clone().unwrap_or_default(), func.start_line))
+                        .map(|(_, category)| *category)
+                        .unwrap_or(FunctionCategory::Verified)
+                },
+            ));
+        }
+
+        mismatches
+    }
+}
+
+/// Per-function verification outcome, as tracked by [`SnapshotDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionCategory {
+    Failed,
+    Verified,
+    Unverified,
+}
+
+/// A function whose category changed between two snapshots (e.g.
+/// verified -> failed after a spec edit).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedCategory {
+    pub function: FunctionLocation,
+    pub before: FunctionCategory,
+    pub after: FunctionCategory,
+}
+
+/// Change in each [`AnalysisSummary`] count between two snapshots
+/// (`after - before`; negative means the count shrank).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct SummaryDelta {
+    pub total_functions: i64,
+    pub failed_functions: i64,
+    pub verified_functions: i64,
+    pub unverified_functions: i64,
+    pub verification_errors: i64,
+    pub compilation_errors: i64,
+    pub compilation_warnings: i64,
+}
+
+impl SummaryDelta {
+    fn compute(before: &AnalysisSummary, after: &AnalysisSummary) -> Self {
+        Self {
+            total_functions: after.total_functions as i64 - before.total_functions as i64,
+            failed_functions: after.failed_functions as i64 - before.failed_functions as i64,
+            verified_functions: after.verified_functions as i64 - before.verified_functions as i64,
+            unverified_functions: after.unverified_functions as i64
+                - before.unverified_functions as i64,
+            verification_errors: after.verification_errors as i64
+                - before.verification_errors as i64,
+            compilation_errors: after.compilation_errors as i64 - before.compilation_errors as i64,
+            compilation_warnings: after.compilation_warnings as i64
+                - before.compilation_warnings as i64,
+        }
+    }
+}
+
+/// The delta [`VerificationAnalyzer::compare_to_snapshot`] reports between a
+/// golden snapshot and a fresh [`AnalysisResult`].
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SnapshotDiff {
+    /// Functions that weren't failing in the snapshot but are now.
+    pub newly_failed: Vec<FunctionLocation>,
+    /// Functions that weren't verified in the snapshot but are now.
+    pub newly_verified: Vec<FunctionLocation>,
+    /// Every function whose category changed, in either direction.
+    pub changed_category: Vec<ChangedCategory>,
+    pub summary_delta: SummaryDelta,
+}
+
+impl SnapshotDiff {
+    fn compute(before: &AnalysisResult, after: &AnalysisResult) -> Self {
+        let before_by_key = categorize(before);
+        let after_by_key = categorize(after);
+
+        let mut newly_failed = Vec::new();
+        let mut newly_verified = Vec::new();
+        let mut changed_category = Vec::new();
+
+        for (key, (location, category)) in &after_by_key {
+            match before_by_key.get(key) {
+                None => match category {
+                    FunctionCategory::Failed => newly_failed.push(location.clone()),
+                    FunctionCategory::Verified => newly_verified.push(location.clone()),
+                    FunctionCategory::Unverified => {}
+                },
+                Some((_, before_category)) if before_category != category => {
+                    changed_category.push(ChangedCategory {
+                        function: location.clone(),
+                        before: *before_category,
+                        after: *category,
+                    });
+                    match category {
+                        FunctionCategory::Failed => newly_failed.push(location.clone()),
+                        FunctionCategory::Verified => newly_verified.push(location.clone()),
+                        FunctionCategory::Unverified => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        newly_failed.sort_by(location_sort_key);
+        newly_verified.sort_by(location_sort_key);
+        changed_category.sort_by(|a, b| location_sort_key(&a.function, &b.function));
+
+        Self {
+            newly_failed,
+            newly_verified,
+            changed_category,
+            summary_delta: SummaryDelta::compute(&before.summary, &after.summary),
+        }
+    }
+
+    /// Whether any function newly failed -- the signal a regression gate
+    /// should act on.
+    pub fn has_regressions(&self) -> bool {
+        !self.newly_failed.is_empty()
+    }
+}
+
+fn location_sort_key(a: &FunctionLocation, b: &FunctionLocation) -> std::cmp::Ordering {
+    (&a.code_path, a.code_text.lines_start).cmp(&(&b.code_path, b.code_text.lines_start))
+}
+
+/// Index an [`AnalysisResult`]'s functions by `(code_path, lines_start)` ->
+/// `(location, category)`, so the three category lists can be compared as
+/// one map instead of three separate `Vec` scans.
+fn categorize(
+    result: &AnalysisResult,
+) -> HashMap<(String, usize), (FunctionLocation, FunctionCategory)> {
+    let mut map = HashMap::new();
+    for location in &result.verification.failed_functions {
+        map.insert(
+            (location.code_path.clone(), location.code_text.lines_start),
+            (location.clone(), FunctionCategory::Failed),
+        );
+    }
+    for location in &result.verification.verified_functions {
+        map.insert(
+            (location.code_path.clone(), location.code_text.lines_start),
+            (location.clone(), FunctionCategory::Verified),
+        );
+    }
+    for location in &result.verification.unverified_functions {
+        map.insert(
+            (location.code_path.clone(), location.code_text.lines_start),
+            (location.clone(), FunctionCategory::Unverified),
+        );
+    }
+    map
 }
 
 #[cfg(test)]
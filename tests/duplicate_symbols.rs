@@ -7,7 +7,9 @@ fn get_test_data() -> (
     std::collections::HashMap<String, String>,
 ) {
     let scip_data = parse_scip_json("data/curve_top.json").expect("Failed to parse SCIP JSON");
-    build_call_graph(&scip_data)
+    let (call_graph, symbol_to_display_name, _all_function_symbols) =
+        build_call_graph(&scip_data, None);
+    (call_graph, symbol_to_display_name)
 }
 
 /// Test that multiple trait implementations with the same SCIP symbol
@@ -17,7 +19,8 @@ fn get_test_data() -> (
 #[test]
 fn test_duplicate_mul_implementations() {
     let scip_data = parse_scip_json("data/curve_top.json").expect("Failed to parse SCIP JSON");
-    let (call_graph, _symbol_to_display_name) = build_call_graph(&scip_data);
+    let (call_graph, _symbol_to_display_name, _all_function_symbols) =
+        build_call_graph(&scip_data, None);
 
     // Find all entries with "Mul#mul" in their symbol (for montgomery module)
     let mut mul_entries: Vec<_> = call_graph
@@ -69,7 +72,8 @@ fn test_duplicate_mul_implementations() {
 #[test]
 fn test_scip_names_include_type_info() {
     let scip_data = parse_scip_json("data/curve_top.json").expect("Failed to parse SCIP JSON");
-    let (call_graph, symbol_to_display_name) = build_call_graph(&scip_data);
+    let (call_graph, symbol_to_display_name, _all_function_symbols) =
+        build_call_graph(&scip_data, None);
     let atoms = convert_to_atoms_with_lines(&call_graph, &symbol_to_display_name);
 
     // With the self_type repair, the format is now: